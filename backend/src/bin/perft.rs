@@ -0,0 +1,43 @@
+use chess::domain::rulebook;
+use std::env;
+use std::process;
+
+const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Print the node count beneath each legal move from a position, and the total.
+///
+/// Usage: `perft <depth> [fen]`, where the FEN defaults to the starting position.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(depth) = args.first().and_then(|depth| depth.parse::<u8>().ok()) else {
+        exit_with_error("Usage: perft <depth> [fen]");
+    };
+    let fen = match args.get(1) {
+        Some(fen) if !fen.trim().is_empty() => fen.as_str(),
+        _ => STARTING_FEN,
+    };
+
+    let fen = match rulebook::Fen::parse(fen) {
+        Ok(fen) => fen,
+        Err(error) => exit_with_error(&format!("{}", error)),
+    };
+    let chessboard_history = fen.to_chessboard_history();
+
+    let mut divided = rulebook::divide(fen.to_play_colour, &chessboard_history, depth);
+    divided.sort_by_key(|(chess_move, _)| rulebook::to_uci(chess_move));
+    for (chess_move, node_count) in divided.iter() {
+        println!("{}: {}", rulebook::to_uci(chess_move), node_count);
+    }
+
+    let total: u64 = match depth {
+        0 => 1,
+        _ => divided.iter().map(|(_, node_count)| node_count).sum(),
+    };
+    println!();
+    println!("Nodes searched: {}", total);
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
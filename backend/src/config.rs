@@ -1,6 +1,9 @@
 use crate::domain::engine;
 use crate::repository;
 
+/// The score below which the minimax engine resigns, i.e. roughly a queen and two rooks down.
+const MINIMAX_RESIGN_THRESHOLD: i32 = -3000;
+
 /// Get the concrete game repository to use.
 pub fn get_game_repo() -> Box<dyn repository::GameRepository> {
     let repo = repository::DieselGameRepository::new();
@@ -33,7 +36,11 @@ fn get_random_chess_engine() -> Box<dyn engine::ChessEngine> {
 fn get_minimax_chess_engine() -> Box<dyn engine::ChessEngine> {
     let evaluator = get_chessboard_evaluator();
     let max_search_depth = 3;
-    let chess_engine = engine::Minimax::new(evaluator, max_search_depth);
+    let chess_engine = engine::Minimax::new_with_resign_threshold(
+        evaluator,
+        max_search_depth,
+        MINIMAX_RESIGN_THRESHOLD,
+    );
     Box::new(chess_engine)
 }
 
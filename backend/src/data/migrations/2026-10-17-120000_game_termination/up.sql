@@ -0,0 +1,2 @@
+ALTER TABLE game
+    ADD COLUMN termination SMALLINT CHECK(termination >= 0 AND termination <= 7);
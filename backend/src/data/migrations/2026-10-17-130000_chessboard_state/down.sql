@@ -0,0 +1 @@
+DROP TABLE chessboard_state;
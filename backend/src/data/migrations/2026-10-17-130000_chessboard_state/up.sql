@@ -0,0 +1,8 @@
+CREATE TABLE chessboard_state (
+    id SERIAL PRIMARY KEY,
+    game_id INTEGER NOT NULL REFERENCES game(id),
+    chessboard_history_index SMALLINT NOT NULL CHECK(chessboard_history_index >= 0),
+    castling_rights BIGINT NOT NULL,
+    en_passant_rank SMALLINT CHECK(en_passant_rank = 3 OR en_passant_rank = 6),
+    en_passant_file SMALLINT CHECK(en_passant_file >= 1 AND en_passant_file <= 8)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE game
+    DROP COLUMN draw_offered_by,
+    DROP COLUMN draw_offered_at_ply;
@@ -0,0 +1,3 @@
+ALTER TABLE game
+    ADD COLUMN draw_offered_by SMALLINT CHECK(draw_offered_by >= 0 AND draw_offered_by <= 1),
+    ADD COLUMN draw_offered_at_ply SMALLINT;
@@ -0,0 +1,8 @@
+ALTER TABLE game
+    DROP COLUMN time_control,
+    DROP COLUMN time_control_base_ms,
+    DROP COLUMN time_control_increment_ms,
+    DROP COLUMN white_remaining_ms,
+    DROP COLUMN black_remaining_ms,
+    DROP COLUMN turn_started_at_ms,
+    DROP COLUMN move_remaining_ms;
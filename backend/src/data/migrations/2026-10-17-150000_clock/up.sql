@@ -0,0 +1,9 @@
+-- The increment holds the delay for Bronstein time controls.
+ALTER TABLE game
+    ADD COLUMN time_control SMALLINT CHECK(time_control >= 0 AND time_control <= 1),
+    ADD COLUMN time_control_base_ms BIGINT,
+    ADD COLUMN time_control_increment_ms BIGINT,
+    ADD COLUMN white_remaining_ms BIGINT,
+    ADD COLUMN black_remaining_ms BIGINT,
+    ADD COLUMN turn_started_at_ms BIGINT,
+    ADD COLUMN move_remaining_ms BIGINT[];
@@ -0,0 +1,3 @@
+ALTER TABLE game
+    DROP COLUMN takeback_requested_by,
+    DROP COLUMN takeback_plies;
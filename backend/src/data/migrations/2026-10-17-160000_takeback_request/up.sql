@@ -0,0 +1,3 @@
+ALTER TABLE game
+    ADD COLUMN takeback_requested_by SMALLINT CHECK(takeback_requested_by >= 0 AND takeback_requested_by <= 1),
+    ADD COLUMN takeback_plies SMALLINT;
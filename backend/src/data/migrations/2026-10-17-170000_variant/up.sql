@@ -0,0 +1,2 @@
+ALTER TABLE game
+    ADD COLUMN variant SMALLINT NOT NULL DEFAULT 0 CHECK(variant >= 0 AND variant <= 1);
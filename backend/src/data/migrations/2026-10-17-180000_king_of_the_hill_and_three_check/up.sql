@@ -0,0 +1,5 @@
+ALTER TABLE game
+    DROP CONSTRAINT game_termination_check,
+    ADD CONSTRAINT game_termination_check CHECK(termination >= 0 AND termination <= 9),
+    DROP CONSTRAINT game_variant_check,
+    ADD CONSTRAINT game_variant_check CHECK(variant >= 0 AND variant <= 3);
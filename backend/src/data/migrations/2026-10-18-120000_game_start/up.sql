@@ -0,0 +1,2 @@
+ALTER TABLE game
+    ADD COLUMN starting_colour SMALLINT NOT NULL DEFAULT 0 CHECK(starting_colour >= 0 AND starting_colour <= 1);
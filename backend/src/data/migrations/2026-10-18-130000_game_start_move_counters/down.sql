@@ -0,0 +1,3 @@
+ALTER TABLE game
+    DROP COLUMN starting_halfmove_clock,
+    DROP COLUMN starting_fullmove_number;
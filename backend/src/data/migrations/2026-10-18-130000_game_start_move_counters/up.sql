@@ -0,0 +1,3 @@
+ALTER TABLE game
+    ADD COLUMN starting_halfmove_clock SMALLINT NOT NULL DEFAULT 0 CHECK(starting_halfmove_clock >= 0),
+    ADD COLUMN starting_fullmove_number SMALLINT NOT NULL DEFAULT 1 CHECK(starting_fullmove_number >= 1);
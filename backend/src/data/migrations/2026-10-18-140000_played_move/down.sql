@@ -0,0 +1 @@
+DROP TABLE played_move;
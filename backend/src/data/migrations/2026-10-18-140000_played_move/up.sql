@@ -0,0 +1,12 @@
+-- Each move is stored at the index of the chessboard it led to.
+CREATE TABLE played_move (
+    id SERIAL PRIMARY KEY,
+    game_id INTEGER NOT NULL REFERENCES game(id),
+    chessboard_history_index SMALLINT NOT NULL CHECK(chessboard_history_index >= 1),
+    san TEXT NOT NULL,
+    kind SMALLINT NOT NULL CHECK(kind >= 0 AND kind <= 6),
+    captured_piece_colour SMALLINT CHECK(captured_piece_colour >= 0 AND captured_piece_colour <= 1),
+    captured_piece_type SMALLINT CHECK(captured_piece_type >= 0 AND captured_piece_type <= 5),
+    gives_check BOOLEAN NOT NULL,
+    gives_checkmate BOOLEAN NOT NULL
+);
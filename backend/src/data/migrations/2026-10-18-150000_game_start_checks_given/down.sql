@@ -0,0 +1,3 @@
+ALTER TABLE game
+    DROP COLUMN starting_white_checks_given,
+    DROP COLUMN starting_black_checks_given;
@@ -0,0 +1,3 @@
+ALTER TABLE game
+    ADD COLUMN starting_white_checks_given SMALLINT NOT NULL DEFAULT 0 CHECK(starting_white_checks_given >= 0),
+    ADD COLUMN starting_black_checks_given SMALLINT NOT NULL DEFAULT 0 CHECK(starting_black_checks_given >= 0);
@@ -0,0 +1,2 @@
+ALTER TABLE played_move DROP CONSTRAINT played_move_game_id_chessboard_history_index_key;
+ALTER TABLE chessboard_state DROP CONSTRAINT chessboard_state_game_id_chessboard_history_index_key;
@@ -0,0 +1,31 @@
+-- Moves stored for games that were in progress before moves were stored don't line up with
+-- their chessboards, so drop them to be restored from the chessboards when the game is loaded.
+DELETE FROM played_move
+WHERE game_id IN (
+    SELECT played_move.game_id
+    FROM played_move
+    JOIN (
+        SELECT game_id, MAX(chessboard_history_index) AS latest_index
+        FROM occupied_chessboard_square
+        GROUP BY game_id
+    ) AS chessboard ON chessboard.game_id = played_move.game_id
+    GROUP BY played_move.game_id, chessboard.latest_index
+    HAVING COUNT(DISTINCT played_move.chessboard_history_index) <> COUNT(*)
+        OR COUNT(*) <> chessboard.latest_index
+        OR MIN(played_move.chessboard_history_index) <> 1
+        OR MAX(played_move.chessboard_history_index) <> chessboard.latest_index
+);
+
+DELETE FROM chessboard_state AS duplicate_state
+USING chessboard_state
+WHERE duplicate_state.game_id = chessboard_state.game_id
+    AND duplicate_state.chessboard_history_index = chessboard_state.chessboard_history_index
+    AND duplicate_state.id > chessboard_state.id;
+
+ALTER TABLE chessboard_state
+    ADD CONSTRAINT chessboard_state_game_id_chessboard_history_index_key
+    UNIQUE (game_id, chessboard_history_index);
+
+ALTER TABLE played_move
+    ADD CONSTRAINT played_move_game_id_chessboard_history_index_key
+    UNIQUE (game_id, chessboard_history_index);
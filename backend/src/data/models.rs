@@ -1,13 +1,32 @@
 use super::schema;
 use diesel::prelude::*;
 
-use crate::domain::{chess_set, game};
+use crate::domain::{chess_set, clock, game, rulebook};
 
 #[derive(Queryable, Selectable)]
 #[diesel(table_name = schema::game)]
 pub struct Game {
     pub id: i32,
     pub status: i16,
+    pub termination: Option<i16>,
+    pub draw_offered_by: Option<i16>,
+    pub draw_offered_at_ply: Option<i16>,
+    pub takeback_requested_by: Option<i16>,
+    pub takeback_plies: Option<i16>,
+    pub time_control: Option<i16>,
+    pub time_control_base_ms: Option<i64>,
+    /// The increment for Fischer time controls, or the delay for Bronstein ones.
+    pub time_control_increment_ms: Option<i64>,
+    pub white_remaining_ms: Option<i64>,
+    pub black_remaining_ms: Option<i64>,
+    pub turn_started_at_ms: Option<i64>,
+    pub move_remaining_ms: Option<Vec<i64>>,
+    pub variant: i16,
+    pub starting_colour: i16,
+    pub starting_halfmove_clock: i16,
+    pub starting_fullmove_number: i16,
+    pub starting_white_checks_given: i16,
+    pub starting_black_checks_given: i16,
 }
 
 #[derive(Insertable)]
@@ -39,6 +58,55 @@ struct NewChessboardSquare {
     piece_type: i16,
 }
 
+#[derive(Selectable, Queryable)]
+#[diesel(table_name = schema::chessboard_state)]
+pub struct ChessboardState {
+    pub id: i32,
+    pub game_id: i32,
+    pub chessboard_history_index: i16,
+    pub castling_rights: i64,
+    pub en_passant_rank: Option<i16>,
+    pub en_passant_file: Option<i16>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = schema::chessboard_state)]
+struct NewChessboardState {
+    game_id: i32,
+    chessboard_history_index: i16,
+    castling_rights: i64,
+    en_passant_rank: Option<i16>,
+    en_passant_file: Option<i16>,
+}
+
+#[derive(Selectable, Queryable)]
+#[diesel(table_name = schema::played_move)]
+pub struct PlayedMove {
+    pub id: i32,
+    pub game_id: i32,
+    /// The index of the chessboard the move led to.
+    pub chessboard_history_index: i16,
+    pub san: String,
+    pub kind: i16,
+    pub captured_piece_colour: Option<i16>,
+    pub captured_piece_type: Option<i16>,
+    pub gives_check: bool,
+    pub gives_checkmate: bool,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = schema::played_move)]
+struct NewPlayedMove {
+    game_id: i32,
+    chessboard_history_index: i16,
+    san: String,
+    kind: i16,
+    captured_piece_colour: Option<i16>,
+    captured_piece_type: Option<i16>,
+    gives_check: bool,
+    gives_checkmate: bool,
+}
+
 impl Game {
     // SQL.
     pub fn get(conn: &mut PgConnection, id: &i32) -> Option<Self> {
@@ -70,32 +138,186 @@ impl Game {
     }
 
     pub fn update_status(conn: &mut PgConnection, updated_game: &game::Game) {
-        use crate::data::schema::game::dsl::{game, status};
+        use crate::data::schema::game::dsl::{
+            draw_offered_at_ply, draw_offered_by, game, status, takeback_plies,
+            takeback_requested_by, termination, variant,
+        };
+
+        let termination_index = updated_game
+            .get_termination()
+            .as_ref()
+            .map(|game_termination| game_termination.to_index());
+        let draw_offer = updated_game.get_draw_offer().as_ref();
+        let takeback_request = updated_game.get_takeback_request().as_ref();
 
         let _ = diesel::update(game.find(updated_game.get_id()))
-            .set(status.eq(updated_game.get_status().to_index()))
+            .set((
+                status.eq(updated_game.get_status().to_index()),
+                termination.eq(termination_index),
+                draw_offered_by.eq(draw_offer.map(|offer| offer.offered_by.to_index())),
+                draw_offered_at_ply.eq(draw_offer.map(|offer| offer.ply as i16)),
+                takeback_requested_by
+                    .eq(takeback_request.map(|request| request.requested_by.to_index())),
+                takeback_plies.eq(takeback_request.map(|request| request.plies as i16)),
+                variant.eq(updated_game.get_variant().to_index()),
+            ))
+            .execute(conn);
+    }
+
+    /// Record what the game's first chessboard doesn't, once the game has been created.
+    pub fn update_start(conn: &mut PgConnection, created_game: &game::Game) {
+        use crate::data::schema::game::dsl;
+
+        let start = created_game.get_start();
+        let _ = diesel::update(dsl::game.find(created_game.get_id()))
+            .set((
+                dsl::starting_colour.eq(start.to_play_colour.to_index()),
+                dsl::starting_halfmove_clock.eq(start.halfmove_clock as i16),
+                dsl::starting_fullmove_number.eq(start.fullmove_number as i16),
+                dsl::starting_white_checks_given.eq(start.checks_given.white as i16),
+                dsl::starting_black_checks_given.eq(start.checks_given.black as i16),
+            ))
+            .execute(conn);
+    }
+
+    pub fn update_clock(conn: &mut PgConnection, updated_game: &game::Game) {
+        use crate::data::schema::game::dsl;
+
+        let Some(game_clock) = updated_game.get_clock() else {
+            return;
+        };
+        let (time_control_index, increment_ms) = match game_clock.get_time_control() {
+            clock::TimeControl::Fischer { increment_ms, .. } => (0, increment_ms),
+            clock::TimeControl::Bronstein { delay_ms, .. } => (1, delay_ms),
+        };
+        let move_remaining_ms: Vec<i64> = game_clock
+            .get_move_remaining_ms()
+            .iter()
+            .map(|remaining_ms| *remaining_ms as i64)
+            .collect();
+
+        let _ = diesel::update(dsl::game.find(updated_game.get_id()))
+            .set((
+                dsl::time_control.eq(time_control_index),
+                dsl::time_control_base_ms.eq(game_clock.get_time_control().get_base_ms() as i64),
+                dsl::time_control_increment_ms.eq(*increment_ms as i64),
+                dsl::white_remaining_ms
+                    .eq(game_clock.get_remaining_ms(chess_set::Colour::White) as i64),
+                dsl::black_remaining_ms
+                    .eq(game_clock.get_remaining_ms(chess_set::Colour::Black) as i64),
+                dsl::turn_started_at_ms.eq(game_clock.get_turn_started_at_ms() as i64),
+                dsl::move_remaining_ms.eq(move_remaining_ms),
+            ))
             .execute(conn);
     }
 
     // Domain factories.
 
-    pub fn to_domain(&self, chessboard_history: Vec<chess_set::Chessboard>) -> game::Game {
+    pub fn to_domain(
+        &self,
+        moves: Vec<game::PlayedMove>,
+        chessboard_history: Vec<chess_set::Chessboard>,
+    ) -> game::Game {
         game::Game::reincarnate(
             self.id,
             game::GameStatus::from_index(self.status),
+            self.termination.map(game::GameTermination::from_index),
+            self.to_domain_draw_offer(),
+            self.to_domain_takeback_request(),
+            self.to_domain_clock(),
+            game::GameVariant::from_index(self.variant),
+            game::GameStart {
+                to_play_colour: self.to_domain_starting_colour(),
+                halfmove_clock: self.starting_halfmove_clock as u16,
+                fullmove_number: self.starting_fullmove_number as u16,
+                checks_given: rulebook::ChecksGiven::new(
+                    self.starting_white_checks_given as u8,
+                    self.starting_black_checks_given as u8,
+                ),
+            },
+            moves,
             chessboard_history,
         )
     }
+
+    pub fn to_domain_starting_colour(&self) -> chess_set::Colour {
+        chess_set::Colour::from_index(self.starting_colour)
+    }
+
+    fn to_domain_draw_offer(&self) -> Option<game::DrawOffer> {
+        match (self.draw_offered_by, self.draw_offered_at_ply) {
+            (Some(offered_by), Some(ply)) => Some(game::DrawOffer {
+                offered_by: chess_set::Colour::from_index(offered_by),
+                ply: ply as usize,
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_domain_takeback_request(&self) -> Option<game::TakebackRequest> {
+        match (self.takeback_requested_by, self.takeback_plies) {
+            (Some(requested_by), Some(plies)) => Some(game::TakebackRequest {
+                requested_by: chess_set::Colour::from_index(requested_by),
+                plies: plies as usize,
+            }),
+            _ => None,
+        }
+    }
+
+    fn to_domain_clock(&self) -> Option<clock::Clock> {
+        let time_control = match (
+            self.time_control,
+            self.time_control_base_ms,
+            self.time_control_increment_ms,
+        ) {
+            (Some(0), Some(base_ms), Some(increment_ms)) => clock::TimeControl::Fischer {
+                base_ms: base_ms as u64,
+                increment_ms: increment_ms as u64,
+            },
+            (Some(1), Some(base_ms), Some(delay_ms)) => clock::TimeControl::Bronstein {
+                base_ms: base_ms as u64,
+                delay_ms: delay_ms as u64,
+            },
+            (None, _, _) => return None,
+            _ => panic!("Invalid time control!"),
+        };
+
+        Some(clock::Clock::reincarnate(
+            time_control,
+            self.white_remaining_ms.unwrap_or_default() as u64,
+            self.black_remaining_ms.unwrap_or_default() as u64,
+            self.turn_started_at_ms.unwrap_or_default() as u64,
+            self.move_remaining_ms
+                .iter()
+                .flatten()
+                .map(|remaining_ms| *remaining_ms as u64)
+                .collect(),
+        ))
+    }
 }
 
 impl OccupiedChessboardSquare {
     // SQL.
 
     pub fn bulk_create_for_latest_chessboard(conn: &mut PgConnection, game: &game::Game) {
+        let chessboard_history_index = game.get_chessboard_history().len() - 1;
+        Self::bulk_create_for_chessboard(conn, game, chessboard_history_index);
+    }
+
+    pub fn bulk_create_for_chessboard_history(conn: &mut PgConnection, game: &game::Game) {
+        for chessboard_history_index in 0..game.get_chessboard_history().len() {
+            Self::bulk_create_for_chessboard(conn, game, chessboard_history_index);
+        }
+    }
+
+    fn bulk_create_for_chessboard(
+        conn: &mut PgConnection,
+        game: &game::Game,
+        chessboard_history_index: usize,
+    ) {
         use crate::data::schema::occupied_chessboard_square;
 
-        let chessboard_history_index = game.get_chessboard_history().len() - 1;
-        let chessboard = game.current_chessboard().clone();
+        let chessboard = game.get_chessboard_history()[chessboard_history_index].clone();
 
         let mut new_squares = vec![];
         for (square, maybe_piece) in chessboard.position.into_iter() {
@@ -133,6 +355,24 @@ impl OccupiedChessboardSquare {
             .expect("Error loading chessboard!")
     }
 
+    /// Delete the squares of every chessboard from an index onwards, e.g. after a takeback.
+    pub fn delete_from_chessboard_history_index(
+        conn: &mut PgConnection,
+        for_game_id: &i32,
+        from_index: usize,
+    ) {
+        use crate::data::schema::occupied_chessboard_square::dsl::{
+            chessboard_history_index, game_id, occupied_chessboard_square,
+        };
+
+        let _ = diesel::delete(
+            occupied_chessboard_square
+                .filter(game_id.eq(for_game_id))
+                .filter(chessboard_history_index.ge(from_index as i16)),
+        )
+        .execute(conn);
+    }
+
     // Domain factories.
 
     pub fn to_domain_square(&self) -> chess_set::Square {
@@ -148,6 +388,194 @@ impl OccupiedChessboardSquare {
     }
 }
 
+impl ChessboardState {
+    // SQL.
+
+    pub fn create_for_latest_chessboard(conn: &mut PgConnection, game: &game::Game) {
+        let chessboard_history_index = game.get_chessboard_history().len() - 1;
+        Self::create_for_chessboard(conn, game, chessboard_history_index);
+    }
+
+    pub fn create_for_chessboard_history(conn: &mut PgConnection, game: &game::Game) {
+        for chessboard_history_index in 0..game.get_chessboard_history().len() {
+            Self::create_for_chessboard(conn, game, chessboard_history_index);
+        }
+    }
+
+    pub fn create_for_chessboard(
+        conn: &mut PgConnection,
+        game: &game::Game,
+        chessboard_history_index: usize,
+    ) {
+        use crate::data::schema::chessboard_state;
+
+        let chessboard = &game.get_chessboard_history()[chessboard_history_index];
+
+        // The castling rights are stored as a bitboard of the squares of the rooks that can castle.
+        let castling_rights = chess_set::Bitboard::from_squares(&chessboard.castling_rights);
+        let new_state = NewChessboardState {
+            game_id: *game.get_id(),
+            chessboard_history_index: chessboard_history_index as i16,
+            castling_rights: castling_rights.get_bits() as i64,
+            en_passant_rank: chessboard
+                .en_passant_square
+                .map(|square| square.get_rank().index() as i16),
+            en_passant_file: chessboard
+                .en_passant_square
+                .map(|square| square.get_file().index() as i16),
+        };
+
+        let _ = diesel::insert_into(chessboard_state::table)
+            .values(&new_state)
+            .execute(conn);
+    }
+
+    pub fn select_for_game(conn: &mut PgConnection, for_game_id: &i32) -> Vec<ChessboardState> {
+        use crate::data::schema::chessboard_state::dsl::{
+            chessboard_history_index, chessboard_state, game_id,
+        };
+
+        chessboard_state
+            .filter(game_id.eq(for_game_id))
+            .select(ChessboardState::as_select())
+            .order(chessboard_history_index.asc())
+            .load(conn)
+            .expect("Error loading chessboard state!")
+    }
+
+    /// Delete the state of every chessboard from an index onwards, e.g. after a takeback.
+    pub fn delete_from_chessboard_history_index(
+        conn: &mut PgConnection,
+        for_game_id: &i32,
+        from_index: usize,
+    ) {
+        use crate::data::schema::chessboard_state::dsl::{
+            chessboard_history_index, chessboard_state, game_id,
+        };
+
+        let _ = diesel::delete(
+            chessboard_state
+                .filter(game_id.eq(for_game_id))
+                .filter(chessboard_history_index.ge(from_index as i16)),
+        )
+        .execute(conn);
+    }
+
+    // Domain factories.
+
+    pub fn to_domain_castling_rights(&self) -> Vec<chess_set::Square> {
+        chess_set::Bitboard::new(self.castling_rights as u64)
+            .iter()
+            .collect()
+    }
+
+    pub fn to_domain_en_passant_square(&self) -> Option<chess_set::Square> {
+        match (self.en_passant_rank, self.en_passant_file) {
+            (Some(rank), Some(file)) => Some(chess_set::Square::new(
+                chess_set::Rank::from_index(rank as i8),
+                chess_set::File::from_index(file as i8),
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl PlayedMove {
+    // SQL.
+
+    pub fn create_for_latest_move(conn: &mut PgConnection, game: &game::Game) {
+        let Some(played_move) = game.get_moves().last() else {
+            return;
+        };
+        let chessboard_history_index = game.get_chessboard_history().len() - 1;
+        Self::create_for_move(conn, game, chessboard_history_index, played_move);
+    }
+
+    pub fn create_for_moves(conn: &mut PgConnection, game: &game::Game) {
+        for (index, played_move) in game.get_moves().iter().enumerate() {
+            Self::create_for_move(conn, game, index + 1, played_move);
+        }
+    }
+
+    pub fn create_for_move(
+        conn: &mut PgConnection,
+        game: &game::Game,
+        chessboard_history_index: usize,
+        played_move: &game::PlayedMove,
+    ) {
+        use crate::data::schema::played_move;
+
+        let classification = &played_move.classification;
+        let captured_piece = classification.captured_piece.as_ref();
+        let new_move = NewPlayedMove {
+            game_id: *game.get_id(),
+            chessboard_history_index: chessboard_history_index as i16,
+            san: played_move.san.clone(),
+            kind: classification.kind.to_index(),
+            captured_piece_colour: captured_piece.map(|piece| piece.get_colour().to_index()),
+            captured_piece_type: captured_piece.map(|piece| piece.get_piece_type().to_index()),
+            gives_check: classification.gives_check,
+            gives_checkmate: classification.gives_checkmate,
+        };
+
+        let _ = diesel::insert_into(played_move::table)
+            .values(&new_move)
+            .execute(conn);
+    }
+
+    pub fn select_for_game(conn: &mut PgConnection, for_game_id: &i32) -> Vec<PlayedMove> {
+        use crate::data::schema::played_move::dsl::{
+            chessboard_history_index, game_id, played_move,
+        };
+
+        played_move
+            .filter(game_id.eq(for_game_id))
+            .select(PlayedMove::as_select())
+            .order(chessboard_history_index.asc())
+            .load(conn)
+            .expect("Error loading played moves!")
+    }
+
+    /// Delete every move that led to a chessboard from an index onwards, e.g. after a takeback.
+    pub fn delete_from_chessboard_history_index(
+        conn: &mut PgConnection,
+        for_game_id: &i32,
+        from_index: usize,
+    ) {
+        use crate::data::schema::played_move::dsl::{
+            chessboard_history_index, game_id, played_move,
+        };
+
+        let _ = diesel::delete(
+            played_move
+                .filter(game_id.eq(for_game_id))
+                .filter(chessboard_history_index.ge(from_index as i16)),
+        )
+        .execute(conn);
+    }
+
+    // Domain factories.
+
+    pub fn to_domain(&self) -> game::PlayedMove {
+        let captured_piece = match (self.captured_piece_colour, self.captured_piece_type) {
+            (Some(colour), Some(piece_type)) => Some(chess_set::Piece::new(
+                chess_set::Colour::from_index(colour),
+                chess_set::PieceType::from_index(piece_type),
+            )),
+            _ => None,
+        };
+        game::PlayedMove {
+            san: self.san.clone(),
+            classification: rulebook::MoveClassification {
+                kind: rulebook::MoveKind::from_index(self.kind),
+                captured_piece,
+                gives_check: self.gives_check,
+                gives_checkmate: self.gives_checkmate,
+            },
+        }
+    }
+}
+
 // Db specific serializers & deserializers.
 
 impl game::GameStatus {
@@ -173,6 +601,87 @@ impl game::GameStatus {
     }
 }
 
+impl game::GameTermination {
+    fn to_index(&self) -> i16 {
+        match &self {
+            game::GameTermination::Checkmate => 0,
+            game::GameTermination::Stalemate => 1,
+            game::GameTermination::InsufficientMaterial => 2,
+            game::GameTermination::Repetition => 3,
+            game::GameTermination::FiftyMoveRule => 4,
+            game::GameTermination::Resignation => 5,
+            game::GameTermination::Timeout => 6,
+            game::GameTermination::Agreement => 7,
+            game::GameTermination::KingOfTheHill => 8,
+            game::GameTermination::ThreeChecks => 9,
+        }
+    }
+
+    fn from_index(index: i16) -> game::GameTermination {
+        match index {
+            0 => game::GameTermination::Checkmate,
+            1 => game::GameTermination::Stalemate,
+            2 => game::GameTermination::InsufficientMaterial,
+            3 => game::GameTermination::Repetition,
+            4 => game::GameTermination::FiftyMoveRule,
+            5 => game::GameTermination::Resignation,
+            6 => game::GameTermination::Timeout,
+            7 => game::GameTermination::Agreement,
+            8 => game::GameTermination::KingOfTheHill,
+            9 => game::GameTermination::ThreeChecks,
+            _ => panic!("Invalid game termination index!"),
+        }
+    }
+}
+
+impl game::GameVariant {
+    fn to_index(self) -> i16 {
+        match self {
+            game::GameVariant::Standard => 0,
+            game::GameVariant::Chess960 => 1,
+            game::GameVariant::KingOfTheHill => 2,
+            game::GameVariant::ThreeCheck => 3,
+        }
+    }
+
+    fn from_index(index: i16) -> game::GameVariant {
+        match index {
+            0 => game::GameVariant::Standard,
+            1 => game::GameVariant::Chess960,
+            2 => game::GameVariant::KingOfTheHill,
+            3 => game::GameVariant::ThreeCheck,
+            _ => panic!("Invalid game variant index!"),
+        }
+    }
+}
+
+impl rulebook::MoveKind {
+    fn to_index(self) -> i16 {
+        match self {
+            rulebook::MoveKind::Quiet => 0,
+            rulebook::MoveKind::Capture => 1,
+            rulebook::MoveKind::DoublePush => 2,
+            rulebook::MoveKind::EnPassant => 3,
+            rulebook::MoveKind::CastleKingside => 4,
+            rulebook::MoveKind::CastleQueenside => 5,
+            rulebook::MoveKind::Promotion => 6,
+        }
+    }
+
+    fn from_index(index: i16) -> rulebook::MoveKind {
+        match index {
+            0 => rulebook::MoveKind::Quiet,
+            1 => rulebook::MoveKind::Capture,
+            2 => rulebook::MoveKind::DoublePush,
+            3 => rulebook::MoveKind::EnPassant,
+            4 => rulebook::MoveKind::CastleKingside,
+            5 => rulebook::MoveKind::CastleQueenside,
+            6 => rulebook::MoveKind::Promotion,
+            _ => panic!("Invalid move kind index!"),
+        }
+    }
+}
+
 impl chess_set::Colour {
     fn to_index(&self) -> i16 {
         match &self {
@@ -263,19 +772,120 @@ mod tests {
     #[cfg(test)]
     mod game_domain_factory_tests {
         use super::super::Game;
-        use crate::domain::game;
+        use crate::domain::chess_set::Colour;
+        use crate::domain::{game, rulebook};
         use crate::testing::factories;
 
         #[test]
         fn occupied_chessboard_square_is_deserialized_to_a_square() {
-            let db_game = Game { id: 1, status: 1 };
+            let db_game = Game {
+                id: 1,
+                status: 1,
+                termination: None,
+                draw_offered_by: None,
+                draw_offered_at_ply: None,
+                takeback_requested_by: None,
+                takeback_plies: None,
+                time_control: None,
+                time_control_base_ms: None,
+                time_control_increment_ms: None,
+                white_remaining_ms: None,
+                black_remaining_ms: None,
+                turn_started_at_ms: None,
+                move_remaining_ms: None,
+                variant: 0,
+                starting_colour: 0,
+                starting_halfmove_clock: 0,
+                starting_fullmove_number: 1,
+                starting_white_checks_given: 0,
+                starting_black_checks_given: 0,
+            };
             let chessboard = factories::chessboard();
 
-            let domain_game = db_game.to_domain(vec![chessboard.clone()]);
+            let domain_game = db_game.to_domain(vec![], vec![chessboard.clone()]);
 
             assert_eq!(domain_game.get_id(), &db_game.id);
             assert_eq!(domain_game.get_status(), &game::GameStatus::ToPlayBlack);
+            assert_eq!(domain_game.get_termination(), &None);
+            assert_eq!(domain_game.get_variant(), &game::GameVariant::Standard);
             assert_eq!(domain_game.get_chessboard_history(), &vec![chessboard]);
         }
+
+        #[test]
+        fn ended_game_is_deserialized_with_its_termination() {
+            let db_game = Game {
+                id: 1,
+                status: 4,
+                termination: Some(3),
+                draw_offered_by: None,
+                draw_offered_at_ply: None,
+                takeback_requested_by: None,
+                takeback_plies: None,
+                time_control: None,
+                time_control_base_ms: None,
+                time_control_increment_ms: None,
+                white_remaining_ms: None,
+                black_remaining_ms: None,
+                turn_started_at_ms: None,
+                move_remaining_ms: None,
+                variant: 0,
+                starting_colour: 1,
+                starting_halfmove_clock: 57,
+                starting_fullmove_number: 40,
+                starting_white_checks_given: 2,
+                starting_black_checks_given: 1,
+            };
+            let chessboard = factories::chessboard();
+
+            let domain_game = db_game.to_domain(vec![], vec![chessboard]);
+
+            assert_eq!(domain_game.get_status(), &game::GameStatus::Drawn);
+            assert_eq!(
+                domain_game.get_termination(),
+                &Some(game::GameTermination::Repetition)
+            );
+            let expected_start = game::GameStart {
+                to_play_colour: Colour::Black,
+                halfmove_clock: 57,
+                fullmove_number: 40,
+                checks_given: rulebook::ChecksGiven::new(2, 1),
+            };
+            assert_eq!(domain_game.get_start(), &expected_start);
+        }
+
+        #[test]
+        fn game_is_deserialized_with_its_draw_offer() {
+            let db_game = Game {
+                id: 1,
+                status: 0,
+                termination: None,
+                draw_offered_by: Some(1),
+                draw_offered_at_ply: Some(4),
+                takeback_requested_by: None,
+                takeback_plies: None,
+                time_control: None,
+                time_control_base_ms: None,
+                time_control_increment_ms: None,
+                white_remaining_ms: None,
+                black_remaining_ms: None,
+                turn_started_at_ms: None,
+                move_remaining_ms: None,
+                variant: 0,
+                starting_colour: 0,
+                starting_halfmove_clock: 0,
+                starting_fullmove_number: 1,
+                starting_white_checks_given: 0,
+                starting_black_checks_given: 0,
+            };
+            let chessboard = factories::chessboard();
+
+            let domain_game = db_game.to_domain(vec![], vec![chessboard]);
+
+            let expected_offer = game::DrawOffer {
+                offered_by: Colour::Black,
+                ply: 4,
+            };
+            assert_eq!(domain_game.get_draw_offer(), &Some(expected_offer));
+        }
     }
 }
@@ -1,9 +1,38 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    chessboard_state (id) {
+        id -> Int4,
+        game_id -> Int4,
+        chessboard_history_index -> Int2,
+        castling_rights -> Int8,
+        en_passant_rank -> Nullable<Int2>,
+        en_passant_file -> Nullable<Int2>,
+    }
+}
+
 diesel::table! {
     game (id) {
         id -> Int4,
         status -> Int2,
+        termination -> Nullable<Int2>,
+        draw_offered_by -> Nullable<Int2>,
+        draw_offered_at_ply -> Nullable<Int2>,
+        takeback_requested_by -> Nullable<Int2>,
+        takeback_plies -> Nullable<Int2>,
+        time_control -> Nullable<Int2>,
+        time_control_base_ms -> Nullable<Int8>,
+        time_control_increment_ms -> Nullable<Int8>,
+        white_remaining_ms -> Nullable<Int8>,
+        black_remaining_ms -> Nullable<Int8>,
+        turn_started_at_ms -> Nullable<Int8>,
+        move_remaining_ms -> Nullable<Array<Int8>>,
+        variant -> Int2,
+        starting_colour -> Int2,
+        starting_halfmove_clock -> Int2,
+        starting_fullmove_number -> Int2,
+        starting_white_checks_given -> Int2,
+        starting_black_checks_given -> Int2,
     }
 }
 
@@ -19,9 +48,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    played_move (id) {
+        id -> Int4,
+        game_id -> Int4,
+        chessboard_history_index -> Int2,
+        san -> Text,
+        kind -> Int2,
+        captured_piece_colour -> Nullable<Int2>,
+        captured_piece_type -> Nullable<Int2>,
+        gives_check -> Bool,
+        gives_checkmate -> Bool,
+    }
+}
+
+diesel::joinable!(chessboard_state -> game (game_id));
 diesel::joinable!(occupied_chessboard_square -> game (game_id));
+diesel::joinable!(played_move -> game (game_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    chessboard_state,
     game,
     occupied_chessboard_square,
+    played_move,
 );
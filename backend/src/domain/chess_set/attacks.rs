@@ -0,0 +1,315 @@
+use super::bitboard;
+use super::piece;
+use super::square;
+
+// Attack tables are precomputed for every square, at compile time.
+static KNIGHT_ATTACKS: [u64; 64] = compute_step_attacks(&KNIGHT_STEPS);
+static KING_ATTACKS: [u64; 64] = compute_step_attacks(&KING_STEPS);
+static WHITE_PAWN_ATTACKS: [u64; 64] = compute_step_attacks(&[(1, -1), (1, 1)]);
+static BLACK_PAWN_ATTACKS: [u64; 64] = compute_step_attacks(&[(-1, -1), (-1, 1)]);
+static RAYS: [[u64; 64]; 8] = compute_rays();
+
+/// The (rank, file) steps that each ray travels in.
+///
+/// The first four directions are straight, and the last four are diagonal.
+const RAY_DIRECTIONS: [(i8, i8); 8] = [
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (0, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [
+    (2, 1),
+    (1, 2),
+    (-1, 2),
+    (-2, 1),
+    (-2, -1),
+    (-1, -2),
+    (1, -2),
+    (2, -1),
+];
+
+const KING_STEPS: [(i8, i8); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+/// Get the squares a piece on a square attacks, given the occupied squares.
+///
+/// Sliding pieces attack up to and including the first occupied square in each direction,
+/// regardless of which colour occupies it.
+pub fn get_piece_attacks(
+    piece: &piece::Piece,
+    square: &square::Square,
+    occupied_squares: bitboard::Bitboard,
+) -> bitboard::Bitboard {
+    match piece.get_piece_type() {
+        piece::PieceType::Pawn => get_pawn_attacks(square, piece.get_colour()),
+        piece::PieceType::Knight => get_knight_attacks(square),
+        piece::PieceType::Bishop => get_bishop_attacks(square, occupied_squares),
+        piece::PieceType::Rook => get_rook_attacks(square, occupied_squares),
+        piece::PieceType::Queen => {
+            get_bishop_attacks(square, occupied_squares)
+                | get_rook_attacks(square, occupied_squares)
+        }
+        piece::PieceType::King => get_king_attacks(square),
+    }
+}
+
+pub fn get_pawn_attacks(square: &square::Square, colour: &piece::Colour) -> bitboard::Bitboard {
+    let index = bitboard::get_square_index(square) as usize;
+    match colour {
+        piece::Colour::White => bitboard::Bitboard::new(WHITE_PAWN_ATTACKS[index]),
+        piece::Colour::Black => bitboard::Bitboard::new(BLACK_PAWN_ATTACKS[index]),
+    }
+}
+
+pub fn get_knight_attacks(square: &square::Square) -> bitboard::Bitboard {
+    bitboard::Bitboard::new(KNIGHT_ATTACKS[bitboard::get_square_index(square) as usize])
+}
+
+pub fn get_king_attacks(square: &square::Square) -> bitboard::Bitboard {
+    bitboard::Bitboard::new(KING_ATTACKS[bitboard::get_square_index(square) as usize])
+}
+
+pub fn get_bishop_attacks(
+    square: &square::Square,
+    occupied_squares: bitboard::Bitboard,
+) -> bitboard::Bitboard {
+    get_sliding_attacks(square, occupied_squares, 4..8)
+}
+
+pub fn get_rook_attacks(
+    square: &square::Square,
+    occupied_squares: bitboard::Bitboard,
+) -> bitboard::Bitboard {
+    get_sliding_attacks(square, occupied_squares, 0..4)
+}
+
+/// Get the squares strictly between two squares on the same rank, file or diagonal.
+///
+/// No squares are returned if the two squares are not on the same line.
+pub fn get_squares_between(
+    square: &square::Square,
+    other_square: &square::Square,
+) -> bitboard::Bitboard {
+    let square_bitboard = bitboard::Bitboard::from_square(square);
+    let other_square_bitboard = bitboard::Bitboard::from_square(other_square);
+
+    // Sliding from each square towards the other only overlaps on the squares in between.
+    if get_rook_attacks(square, bitboard::Bitboard::EMPTY).contains(other_square) {
+        get_rook_attacks(square, other_square_bitboard)
+            & get_rook_attacks(other_square, square_bitboard)
+    } else if get_bishop_attacks(square, bitboard::Bitboard::EMPTY).contains(other_square) {
+        get_bishop_attacks(square, other_square_bitboard)
+            & get_bishop_attacks(other_square, square_bitboard)
+    } else {
+        bitboard::Bitboard::EMPTY
+    }
+}
+
+fn get_sliding_attacks(
+    square: &square::Square,
+    occupied_squares: bitboard::Bitboard,
+    directions: std::ops::Range<usize>,
+) -> bitboard::Bitboard {
+    let index = bitboard::get_square_index(square) as usize;
+    let occupied_squares = occupied_squares.get_bits();
+
+    let mut attacks = 0;
+    for direction in directions {
+        let ray = RAYS[direction][index];
+        let blockers = ray & occupied_squares;
+        if blockers == 0 {
+            attacks |= ray;
+            continue;
+        };
+
+        // The nearest blocker is the lowest bit on rays heading towards H8, and the
+        // highest bit on rays heading towards A1.
+        let (rank_step, file_step) = RAY_DIRECTIONS[direction];
+        let nearest_blocker = match rank_step > 0 || (rank_step == 0 && file_step > 0) {
+            true => blockers.trailing_zeros(),
+            false => 63 - blockers.leading_zeros(),
+        };
+        attacks |= ray & !RAYS[direction][nearest_blocker as usize];
+    }
+    bitboard::Bitboard::new(attacks)
+}
+
+const fn compute_step_attacks(steps: &[(i8, i8)]) -> [u64; 64] {
+    let mut attacks = [0; 64];
+    let mut index = 0;
+    while index < 64 {
+        let mut step = 0;
+        while step < steps.len() {
+            let rank = (index / 8) as i8 + steps[step].0;
+            let file = (index % 8) as i8 + steps[step].1;
+            if rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                attacks[index] |= 1 << (rank * 8 + file);
+            };
+            step += 1;
+        }
+        index += 1;
+    }
+    attacks
+}
+
+const fn compute_rays() -> [[u64; 64]; 8] {
+    let mut rays = [[0; 64]; 8];
+    let mut direction = 0;
+    while direction < 8 {
+        let (rank_step, file_step) = RAY_DIRECTIONS[direction];
+        let mut index = 0;
+        while index < 64 {
+            let mut rank = (index / 8) as i8 + rank_step;
+            let mut file = (index % 8) as i8 + file_step;
+            while rank >= 0 && rank < 8 && file >= 0 && file < 8 {
+                rays[direction][index] |= 1 << (rank * 8 + file);
+                rank += rank_step;
+                file += file_step;
+            }
+            index += 1;
+        }
+        direction += 1;
+    }
+    rays
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+    use rstest::rstest;
+
+    fn to_bitboard(squares: &[(Rank, File)]) -> bitboard::Bitboard {
+        squares
+            .iter()
+            .fold(bitboard::Bitboard::EMPTY, |bitboard, (rank, file)| {
+                bitboard | bitboard::Bitboard::from_square(&Square::new(*rank, *file))
+            })
+    }
+
+    #[rstest]
+    #[case::corner(Rank::One, File::A, 2)]
+    #[case::edge(Rank::Four, File::A, 4)]
+    #[case::centre(Rank::Four, File::D, 8)]
+    fn knight_attacks_stay_on_the_board(
+        #[case] rank: Rank,
+        #[case] file: File,
+        #[case] expected_count: u32,
+    ) {
+        let attacks = get_knight_attacks(&Square::new(rank, file));
+
+        assert_eq!(attacks.count(), expected_count);
+    }
+
+    #[test]
+    fn king_attacks_surrounding_squares() {
+        let attacks = get_king_attacks(&Square::new(Rank::One, File::H));
+
+        let expected = to_bitboard(&[
+            (Rank::One, File::G),
+            (Rank::Two, File::G),
+            (Rank::Two, File::H),
+        ]);
+        assert_eq!(attacks, expected);
+    }
+
+    #[rstest]
+    #[case::white(Colour::White, Rank::Three)]
+    #[case::black(Colour::Black, Rank::One)]
+    fn pawn_attacks_diagonally_forwards(#[case] colour: Colour, #[case] attacked_rank: Rank) {
+        let attacks = get_pawn_attacks(&Square::new(Rank::Two, File::A), &colour);
+
+        assert_eq!(attacks, to_bitboard(&[(attacked_rank, File::B)]));
+    }
+
+    #[test]
+    fn rook_attacks_stop_at_first_blocker() {
+        let occupied_squares = to_bitboard(&[
+            (Rank::Six, File::D),
+            (Rank::Seven, File::D),
+            (Rank::Four, File::B),
+        ]);
+
+        let attacks = get_rook_attacks(&Square::new(Rank::Four, File::D), occupied_squares);
+
+        let expected = to_bitboard(&[
+            (Rank::Five, File::D),
+            (Rank::Six, File::D),
+            (Rank::Three, File::D),
+            (Rank::Two, File::D),
+            (Rank::One, File::D),
+            (Rank::Four, File::C),
+            (Rank::Four, File::B),
+            (Rank::Four, File::E),
+            (Rank::Four, File::F),
+            (Rank::Four, File::G),
+            (Rank::Four, File::H),
+        ]);
+        assert_eq!(attacks, expected);
+    }
+
+    #[test]
+    fn bishop_attacks_stop_at_first_blocker() {
+        let occupied_squares = to_bitboard(&[(Rank::Two, File::B), (Rank::Seven, File::G)]);
+
+        let attacks = get_bishop_attacks(&Square::new(Rank::Four, File::D), occupied_squares);
+
+        let expected = to_bitboard(&[
+            (Rank::Three, File::C),
+            (Rank::Two, File::B),
+            (Rank::Five, File::E),
+            (Rank::Six, File::F),
+            (Rank::Seven, File::G),
+            (Rank::Five, File::C),
+            (Rank::Six, File::B),
+            (Rank::Seven, File::A),
+            (Rank::Three, File::E),
+            (Rank::Two, File::F),
+            (Rank::One, File::G),
+        ]);
+        assert_eq!(attacks, expected);
+    }
+
+    #[test]
+    fn queen_attacks_combine_rook_and_bishop_attacks() {
+        let queen = Piece::new(Colour::White, PieceType::Queen);
+        let square = Square::new(Rank::One, File::A);
+
+        let attacks = get_piece_attacks(&queen, &square, bitboard::Bitboard::EMPTY);
+
+        assert_eq!(attacks.count(), 21);
+    }
+
+    #[rstest]
+    #[case::file((Rank::One, File::A), (Rank::Four, File::A), vec![(Rank::Two, File::A), (Rank::Three, File::A)])]
+    #[case::diagonal((Rank::Six, File::F), (Rank::Three, File::C), vec![(Rank::Five, File::E), (Rank::Four, File::D)])]
+    #[case::adjacent((Rank::Four, File::D), (Rank::Five, File::E), vec![])]
+    #[case::not_aligned((Rank::One, File::A), (Rank::Two, File::C), vec![])]
+    fn gets_squares_between_two_squares(
+        #[case] square: (Rank, File),
+        #[case] other_square: (Rank, File),
+        #[case] expected: Vec<(Rank, File)>,
+    ) {
+        let square = Square::new(square.0, square.1);
+        let other_square = Square::new(other_square.0, other_square.1);
+
+        let squares_between = get_squares_between(&square, &other_square);
+
+        assert_eq!(squares_between, to_bitboard(&expected));
+        assert_eq!(get_squares_between(&other_square, &square), squares_between);
+    }
+}
@@ -0,0 +1,512 @@
+use super::attacks;
+use super::chessboard;
+use super::piece;
+use super::square;
+use std::collections::BTreeMap;
+use std::ops;
+
+/// A set of squares, stored as one bit per square.
+///
+/// Bit 0 is A1, bit 7 is H1 and bit 63 is H8, so squares are iterated in the same
+/// order as the `Chessboard` position.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Bitboard(u64);
+
+/// Representation of a chessboard as one bitboard per piece.
+///
+/// This is much cheaper to copy and query than a `Chessboard`, so is used wherever
+/// many positions need to be considered (e.g. when searching for moves).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct BitboardChessboard {
+    pieces: [[Bitboard; 6]; 2],
+    occupied_squares: [Bitboard; 2],
+}
+
+impl Bitboard {
+    pub const EMPTY: Self = Self(0);
+
+    // Factories.
+
+    pub fn new(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub fn from_square(square: &square::Square) -> Self {
+        Self(1 << get_square_index(square))
+    }
+
+    pub fn from_squares(squares: &[square::Square]) -> Self {
+        squares.iter().fold(Self::EMPTY, |bitboard, square| {
+            bitboard | Self::from_square(square)
+        })
+    }
+
+    // Queries.
+
+    pub fn get_bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn contains(&self, square: &square::Square) -> bool {
+        self.0 & (1 << get_square_index(square)) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Iterate over the squares in the set, from A1 to H8.
+    pub fn iter(&self) -> impl Iterator<Item = square::Square> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            };
+            let index = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            Some(get_square_from_index(index))
+        })
+    }
+
+    // Mutators.
+
+    pub fn insert(&mut self, square: &square::Square) {
+        self.0 |= 1 << get_square_index(square);
+    }
+
+    pub fn remove(&mut self, square: &square::Square) {
+        self.0 &= !(1 << get_square_index(square));
+    }
+}
+
+impl BitboardChessboard {
+    // Factories.
+
+    pub fn from_chessboard(chessboard: &chessboard::Chessboard) -> Self {
+        let mut bitboard_chessboard = Self::default();
+        for (square, maybe_piece) in chessboard.position.iter() {
+            if let Some(piece) = maybe_piece {
+                bitboard_chessboard.put_piece(square, piece);
+            };
+        }
+        bitboard_chessboard
+    }
+
+    pub fn to_chessboard(&self) -> chessboard::Chessboard {
+        let mut position = BTreeMap::new();
+        for colour in [piece::Colour::White, piece::Colour::Black] {
+            for square in self.get_occupied_squares(&colour).iter() {
+                position.insert(square, self.get_piece(&square).unwrap());
+            }
+        }
+        chessboard::Chessboard::new(position)
+    }
+
+    // Queries.
+
+    pub fn get_piece(&self, square: &square::Square) -> Option<piece::Piece> {
+        for colour in [piece::Colour::White, piece::Colour::Black] {
+            if !self.get_occupied_squares(&colour).contains(square) {
+                continue;
+            };
+            for piece_type in PIECE_TYPES {
+                if self.get_squares_of(&colour, &piece_type).contains(square) {
+                    return Some(piece::Piece::new(colour, piece_type));
+                };
+            }
+        }
+        None
+    }
+
+    /// Get the squares occupied by a certain type of piece.
+    pub fn get_squares_of(
+        &self,
+        colour: &piece::Colour,
+        piece_type: &piece::PieceType,
+    ) -> Bitboard {
+        self.pieces[get_colour_index(colour)][get_piece_type_index(piece_type)]
+    }
+
+    pub fn get_occupied_squares(&self, colour: &piece::Colour) -> Bitboard {
+        self.occupied_squares[get_colour_index(colour)]
+    }
+
+    pub fn get_all_occupied_squares(&self) -> Bitboard {
+        self.occupied_squares[0] | self.occupied_squares[1]
+    }
+
+    pub fn get_square_king_is_on(&self, colour: &piece::Colour) -> square::Square {
+        match self
+            .get_squares_of(colour, &piece::PieceType::King)
+            .iter()
+            .next()
+        {
+            Some(square) => square,
+            None => panic!("No {} king on chessboard!", colour),
+        }
+    }
+
+    /// Get the squares attacked by the piece on a square, if there is one.
+    pub fn get_attacks(&self, square: &square::Square) -> Bitboard {
+        let Some(piece) = self.get_piece(square) else {
+            return Bitboard::EMPTY;
+        };
+        attacks::get_piece_attacks(&piece, square, self.get_all_occupied_squares())
+    }
+
+    /// Get the squares of a player's pieces that attack a square.
+    ///
+    /// The square does not need to be occupied, and pieces only attack through empty
+    /// squares, so a rook behind another rook on the same file is not counted.
+    pub fn get_attackers(&self, square: &square::Square, by_colour: &piece::Colour) -> Bitboard {
+        let occupied_squares = self.get_all_occupied_squares();
+        let attackers = |piece_type| self.get_squares_of(by_colour, &piece_type);
+
+        // A pawn attacks a square if a pawn of the opposite colour on that square would
+        // attack the pawn back.
+        let pawn_attacks = attacks::get_pawn_attacks(square, &by_colour.swap());
+        let straight_attacks = attacks::get_rook_attacks(square, occupied_squares);
+        let diagonal_attacks = attacks::get_bishop_attacks(square, occupied_squares);
+
+        (pawn_attacks & attackers(piece::PieceType::Pawn))
+            | (attacks::get_knight_attacks(square) & attackers(piece::PieceType::Knight))
+            | (attacks::get_king_attacks(square) & attackers(piece::PieceType::King))
+            | (straight_attacks
+                & (attackers(piece::PieceType::Rook) | attackers(piece::PieceType::Queen)))
+            | (diagonal_attacks
+                & (attackers(piece::PieceType::Bishop) | attackers(piece::PieceType::Queen)))
+    }
+
+    /// Get every square attacked by at least one of a player's pieces.
+    pub fn get_attacked_squares(&self, by_colour: &piece::Colour) -> Bitboard {
+        self.get_occupied_squares(by_colour)
+            .iter()
+            .fold(Bitboard::EMPTY, |attacked_squares, square| {
+                attacked_squares | self.get_attacks(&square)
+            })
+    }
+
+    /// Test whether any of a player's pieces attack a square.
+    ///
+    /// The square does not need to be occupied, so this also tells us which squares a
+    /// king cannot move to.
+    pub fn is_square_attacked(&self, square: &square::Square, by_colour: &piece::Colour) -> bool {
+        !self.get_attackers(square, by_colour).is_empty()
+    }
+
+    pub fn is_player_in_check(&self, player: &piece::Colour) -> bool {
+        let king_square = self.get_square_king_is_on(player);
+        self.is_square_attacked(&king_square, &player.swap())
+    }
+
+    // Mutators.
+
+    /// Put a piece on a square, replacing any piece already there.
+    pub fn put_piece(&mut self, square: &square::Square, piece: &piece::Piece) {
+        self.remove_piece(square);
+
+        let colour_index = get_colour_index(piece.get_colour());
+        let piece_type_index = get_piece_type_index(piece.get_piece_type());
+        self.pieces[colour_index][piece_type_index].insert(square);
+        self.occupied_squares[colour_index].insert(square);
+    }
+
+    /// Remove the piece from a square, returning it.
+    pub fn remove_piece(&mut self, square: &square::Square) -> Option<piece::Piece> {
+        let piece = self.get_piece(square)?;
+
+        let colour_index = get_colour_index(piece.get_colour());
+        let piece_type_index = get_piece_type_index(piece.get_piece_type());
+        self.pieces[colour_index][piece_type_index].remove(square);
+        self.occupied_squares[colour_index].remove(square);
+        Some(piece)
+    }
+}
+
+// Trait implementations.
+
+impl ops::BitAnd for Bitboard {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+}
+
+impl ops::BitOr for Bitboard {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ops::Not for Bitboard {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+// Helpers.
+
+const PIECE_TYPES: [piece::PieceType; 6] = [
+    piece::PieceType::Pawn,
+    piece::PieceType::Knight,
+    piece::PieceType::Bishop,
+    piece::PieceType::Rook,
+    piece::PieceType::Queen,
+    piece::PieceType::King,
+];
+
+pub(super) fn get_square_index(square: &square::Square) -> u8 {
+    ((square.get_rank().index() - 1) * 8 + (square.get_file().index() - 1)) as u8
+}
+
+pub(super) fn get_square_from_index(index: u8) -> square::Square {
+    square::Square::from_indexes((index / 8 + 1) as i8, (index % 8 + 1) as i8)
+}
+
+pub(super) fn get_colour_index(colour: &piece::Colour) -> usize {
+    match colour {
+        piece::Colour::White => 0,
+        piece::Colour::Black => 1,
+    }
+}
+
+pub(super) fn get_piece_type_index(piece_type: &piece::PieceType) -> usize {
+    match piece_type {
+        piece::PieceType::Pawn => 0,
+        piece::PieceType::Knight => 1,
+        piece::PieceType::Bishop => 2,
+        piece::PieceType::Rook => 3,
+        piece::PieceType::Queen => 4,
+        piece::PieceType::King => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+    use crate::testing::factories;
+    use rstest::rstest;
+
+    #[cfg(test)]
+    mod bitboard_tests {
+        use super::*;
+
+        #[test]
+        fn squares_are_indexed_from_a1_to_h8() {
+            assert_eq!(
+                Bitboard::from_square(&Square::new(Rank::One, File::A)),
+                Bitboard::new(1)
+            );
+            assert_eq!(
+                Bitboard::from_square(&Square::new(Rank::One, File::H)),
+                Bitboard::new(1 << 7)
+            );
+            assert_eq!(
+                Bitboard::from_square(&Square::new(Rank::Eight, File::H)),
+                Bitboard::new(1 << 63)
+            );
+        }
+
+        #[test]
+        fn can_insert_and_remove_squares() {
+            let square = Square::new(Rank::Four, File::E);
+            let mut bitboard = Bitboard::EMPTY;
+
+            bitboard.insert(&square);
+
+            assert!(bitboard.contains(&square));
+            assert_eq!(bitboard.count(), 1);
+
+            bitboard.remove(&square);
+
+            assert!(bitboard.is_empty());
+        }
+
+        #[test]
+        fn iterates_squares_in_chessboard_order() {
+            let e4 = Square::new(Rank::Four, File::E);
+            let b1 = Square::new(Rank::One, File::B);
+            let h8 = Square::new(Rank::Eight, File::H);
+            let bitboard = Bitboard::from_square(&h8)
+                | Bitboard::from_square(&e4)
+                | Bitboard::from_square(&b1);
+
+            assert_eq!(bitboard.iter().collect::<Vec<_>>(), vec![b1, e4, h8]);
+        }
+    }
+
+    #[cfg(test)]
+    mod bitboard_chessboard_tests {
+        use super::*;
+
+        #[test]
+        fn round_trips_through_chessboard() {
+            let chessboard = factories::chessboard();
+
+            let bitboard_chessboard = BitboardChessboard::from_chessboard(&chessboard);
+
+            assert_eq!(
+                bitboard_chessboard.to_chessboard().position,
+                chessboard.position
+            );
+            assert_eq!(bitboard_chessboard.get_all_occupied_squares().count(), 32);
+            assert_eq!(
+                bitboard_chessboard.get_piece(&Square::new(Rank::Eight, File::D)),
+                Some(Piece::new(Colour::Black, PieceType::Queen))
+            );
+            assert_eq!(
+                bitboard_chessboard.get_piece(&Square::new(Rank::Four, File::D)),
+                None
+            );
+        }
+
+        #[test]
+        fn putting_piece_replaces_existing_piece() {
+            let mut bitboard_chessboard =
+                BitboardChessboard::from_chessboard(&factories::chessboard());
+            let square = Square::new(Rank::Seven, File::E);
+            let white_queen = Piece::new(Colour::White, PieceType::Queen);
+
+            bitboard_chessboard.put_piece(&square, &white_queen);
+
+            assert_eq!(bitboard_chessboard.get_piece(&square), Some(white_queen));
+            assert_eq!(
+                bitboard_chessboard
+                    .get_squares_of(&Colour::Black, &PieceType::Pawn)
+                    .count(),
+                7
+            );
+            assert_eq!(
+                bitboard_chessboard
+                    .get_occupied_squares(&Colour::Black)
+                    .count(),
+                15
+            );
+        }
+
+        #[test]
+        fn can_remove_piece() {
+            let mut bitboard_chessboard =
+                BitboardChessboard::from_chessboard(&factories::chessboard());
+            let square = Square::new(Rank::One, File::G);
+
+            let removed_piece = bitboard_chessboard.remove_piece(&square);
+
+            assert_eq!(
+                removed_piece,
+                Some(Piece::new(Colour::White, PieceType::Knight))
+            );
+            assert_eq!(bitboard_chessboard.get_piece(&square), None);
+            assert_eq!(bitboard_chessboard.remove_piece(&square), None);
+        }
+
+        #[test]
+        fn gets_square_king_is_on() {
+            let bitboard_chessboard = BitboardChessboard::from_chessboard(&factories::chessboard());
+
+            assert_eq!(
+                bitboard_chessboard.get_square_king_is_on(&Colour::Black),
+                Square::new(Rank::Eight, File::E)
+            );
+        }
+
+        #[should_panic(expected = "No W king on chessboard!")]
+        #[test]
+        fn panics_when_no_king_matching_colour_is_on_board() {
+            let bitboard_chessboard = BitboardChessboard::default();
+
+            let _ = bitboard_chessboard.get_square_king_is_on(&Colour::White);
+        }
+
+        #[test]
+        fn gets_attacks_of_piece_on_square() {
+            let bitboard_chessboard = BitboardChessboard::from_chessboard(&factories::chessboard());
+
+            // The rook is hemmed in, but still defends its neighbours.
+            let rook_attacks = bitboard_chessboard.get_attacks(&Square::new(Rank::One, File::A));
+            let expected_attacks = Bitboard::from_square(&Square::new(Rank::Two, File::A))
+                | Bitboard::from_square(&Square::new(Rank::One, File::B));
+
+            assert_eq!(rook_attacks, expected_attacks);
+            assert_eq!(
+                bitboard_chessboard.get_attacks(&Square::new(Rank::Four, File::A)),
+                Bitboard::EMPTY
+            );
+        }
+
+        #[rstest]
+        #[case::pawn(Rank::Three, File::D, Colour::White, true)]
+        #[case::knight(Rank::Three, File::H, Colour::White, true)]
+        #[case::unattacked(Rank::Four, File::D, Colour::White, false)]
+        #[case::behind_pawns(Rank::Seven, File::D, Colour::White, false)]
+        #[case::black_pawn(Rank::Six, File::A, Colour::Black, true)]
+        #[case::own_piece(Rank::Two, File::E, Colour::Black, false)]
+        fn tests_whether_square_is_attacked(
+            #[case] rank: Rank,
+            #[case] file: File,
+            #[case] by_colour: Colour,
+            #[case] expected: bool,
+        ) {
+            let bitboard_chessboard = BitboardChessboard::from_chessboard(&factories::chessboard());
+
+            let is_attacked =
+                bitboard_chessboard.is_square_attacked(&Square::new(rank, file), &by_colour);
+
+            assert_eq!(is_attacked, expected);
+        }
+
+        #[test]
+        fn gets_attackers_of_square() {
+            let bitboard_chessboard = BitboardChessboard::from_chessboard(&factories::chessboard());
+
+            let attackers = bitboard_chessboard
+                .get_attackers(&Square::new(Rank::Three, File::F), &Colour::White);
+
+            let expected_attackers = Bitboard::from_squares(&[
+                Square::new(Rank::One, File::G),
+                Square::new(Rank::Two, File::E),
+                Square::new(Rank::Two, File::G),
+            ]);
+            assert_eq!(attackers, expected_attackers);
+        }
+
+        #[test]
+        fn gets_squares_attacked_by_player() {
+            let bitboard_chessboard = BitboardChessboard::from_chessboard(&factories::chessboard());
+
+            let attacked_squares = bitboard_chessboard.get_attacked_squares(&Colour::White);
+
+            // Every square on the second and third ranks, and every home rank square but A1 and H1.
+            assert_eq!(attacked_squares.count(), 22);
+            assert!(!attacked_squares.contains(&Square::new(Rank::One, File::A)));
+            assert!(attacked_squares.contains(&Square::new(Rank::Three, File::A)));
+            assert!(!attacked_squares.contains(&Square::new(Rank::Four, File::A)));
+        }
+
+        #[test]
+        fn sliding_attack_is_blocked_by_first_piece() {
+            let mut bitboard_chessboard = BitboardChessboard::default();
+            let white_king = Piece::new(Colour::White, PieceType::King);
+            bitboard_chessboard.put_piece(&Square::new(Rank::One, File::E), &white_king);
+            let black_rook = Piece::new(Colour::Black, PieceType::Rook);
+            bitboard_chessboard.put_piece(&Square::new(Rank::Eight, File::E), &black_rook);
+
+            assert!(bitboard_chessboard.is_player_in_check(&Colour::White));
+
+            let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+            bitboard_chessboard.put_piece(&Square::new(Rank::Four, File::E), &white_pawn);
+
+            assert!(!bitboard_chessboard.is_player_in_check(&Colour::White));
+        }
+    }
+}
@@ -7,12 +7,18 @@ use thiserror;
 
 /// Representation of a physical chessboard, and the current position of all pieces.
 ///
+/// Along with the pieces, the chessboard records the squares of the rooks that can still
+/// castle, and the square a pawn can be captured on en passant. Neither can be seen from
+/// the pieces alone, so they are set by whatever sets up the position or plays a move.
+///
 /// Note: this does not implement any gameplay logic or rules of the game.
 /// The only invariant enforced is that each square has at most one piece on it
 /// at any point in time (since the chessboard is represented by a BTreeMap).
 #[derive(Clone, Debug, PartialEq)]
 pub struct Chessboard {
     pub position: BTreeMap<square::Square, Option<piece::Piece>>,
+    pub castling_rights: Vec<square::Square>,
+    pub en_passant_square: Option<square::Square>,
 }
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -43,7 +49,11 @@ impl Chessboard {
             position.insert(square, Some(piece));
         }
 
-        Chessboard { position: position }
+        Chessboard {
+            position,
+            castling_rights: vec![],
+            en_passant_square: None,
+        }
     }
 
     // Queries
@@ -54,20 +64,20 @@ impl Chessboard {
 
     pub fn get_pieces(&self, colour: piece::Colour) -> BTreeMap<square::Square, piece::Piece> {
         let mut pieces = BTreeMap::new();
-        for (square, maybe_piece) in self.position.clone() {
+        for (square, maybe_piece) in self.position.iter() {
             let Some(piece) = maybe_piece else { continue };
             if piece.get_colour() == &colour {
-                pieces.insert(square, piece);
+                pieces.insert(*square, *piece);
             }
         }
         pieces
     }
 
     pub fn get_square_king_is_on(&self, colour: &piece::Colour) -> square::Square {
-        for (square, maybe_piece) in self.position.clone().into_iter() {
+        for (square, maybe_piece) in self.position.iter() {
             let Some(piece) = maybe_piece else { continue };
             if piece.get_colour() == colour && piece.get_piece_type() == &piece::PieceType::King {
-                return square;
+                return *square;
             }
         }
 
@@ -1,7 +1,15 @@
+mod attacks;
+mod bitboard;
 mod chessboard;
 mod piece;
 mod square;
+pub mod zobrist;
 
+pub use attacks::{
+    get_bishop_attacks, get_king_attacks, get_knight_attacks, get_pawn_attacks, get_piece_attacks,
+    get_rook_attacks, get_squares_between,
+};
+pub use bitboard::{Bitboard, BitboardChessboard};
 pub use chessboard::{Chessboard, ChessboardActionError};
 pub use piece::{Colour, Piece, PieceType};
 pub use square::{File, Rank, Square};
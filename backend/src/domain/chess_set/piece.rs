@@ -26,6 +26,21 @@ pub enum PieceType {
     King,
 }
 
+impl PieceType {
+    /// Get the piece type from its (uppercase) letter in chess notation, e.g. `N` for a knight.
+    pub fn from_notation(letter: char) -> Option<Self> {
+        match letter {
+            'P' => Some(PieceType::Pawn),
+            'N' => Some(PieceType::Knight),
+            'B' => Some(PieceType::Bishop),
+            'R' => Some(PieceType::Rook),
+            'Q' => Some(PieceType::Queen),
+            'K' => Some(PieceType::King),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Piece {
     colour: Colour,
@@ -146,5 +161,19 @@ mod tests {
 
             assert_eq!(deserialized, piece);
         }
+
+        #[test]
+        fn piece_type_round_trips_through_notation_letter() {
+            for piece_type in [PieceType::Pawn, PieceType::Knight, PieceType::King] {
+                let letter = piece_type.to_string().chars().next().unwrap();
+
+                assert_eq!(PieceType::from_notation(letter), Some(piece_type));
+            }
+        }
+
+        #[test]
+        fn lowercase_letter_is_not_a_piece_type() {
+            assert_eq!(PieceType::from_notation('n'), None);
+        }
     }
 }
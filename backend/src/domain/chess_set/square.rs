@@ -149,6 +149,24 @@ impl Square {
         }
     }
 
+    /// Parse a square from algebraic notation, e.g. `e4`.
+    pub fn from_algebraic(notation: &str) -> Option<Self> {
+        let chars: Vec<char> = notation.chars().collect();
+        if chars.len() != 2 {
+            return None;
+        };
+
+        let file_index = match chars[0] {
+            'a'..='h' => (chars[0] as u8 - b'a' + 1) as i8,
+            _ => return None,
+        };
+        let rank_index = match chars[1] {
+            '1'..='8' => (chars[1] as u8 - b'0') as i8,
+            _ => return None,
+        };
+        Some(Self::from_indexes(rank_index, file_index))
+    }
+
     pub fn get_rank(&self) -> &Rank {
         &self.rank
     }
@@ -156,6 +174,12 @@ impl Square {
     pub fn get_file(&self) -> &File {
         &self.file
     }
+
+    /// Write the square in algebraic notation, e.g. `e4`.
+    pub fn to_algebraic(&self) -> String {
+        let file = (b'a' + (self.file.index() - 1) as u8) as char;
+        format!("{}{}", file, self.rank.index())
+    }
 }
 
 // Trait implementations.
@@ -219,3 +243,34 @@ impl PartialOrd for Square {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::a1("a1", Rank::One, File::A)]
+    #[case::e4("e4", Rank::Four, File::E)]
+    #[case::h8("h8", Rank::Eight, File::H)]
+    fn square_round_trips_through_algebraic_notation(
+        #[case] notation: &str,
+        #[case] rank: Rank,
+        #[case] file: File,
+    ) {
+        let square = Square::from_algebraic(notation).unwrap();
+
+        assert_eq!(square, Square::new(rank, file));
+        assert_eq!(square.to_algebraic(), notation);
+    }
+
+    #[rstest]
+    #[case::uppercase_file("E4")]
+    #[case::rank_out_of_range("e9")]
+    #[case::file_out_of_range("i1")]
+    #[case::too_long("e44")]
+    #[case::empty("")]
+    fn invalid_algebraic_notation_is_not_a_square(#[case] notation: &str) {
+        assert_eq!(Square::from_algebraic(notation), None);
+    }
+}
@@ -0,0 +1,129 @@
+use super::bitboard;
+use super::piece;
+use super::square;
+
+// Keys are generated at compile time from a fixed seed, so that a position always
+// hashes to the same value (e.g. across restarts of the server).
+static KEYS: [u64; KEY_COUNT] = compute_keys(0x9E37_79B9_7F4A_7C15);
+
+// One key per piece on each square, then one for black to play, one per castling
+// rook square and one per en passant file.
+const PIECE_KEYS_OFFSET: usize = 0;
+const BLACK_TO_PLAY_KEY_OFFSET: usize = PIECE_KEYS_OFFSET + 2 * 6 * 64;
+const CASTLING_KEYS_OFFSET: usize = BLACK_TO_PLAY_KEY_OFFSET + 1;
+const EN_PASSANT_KEYS_OFFSET: usize = CASTLING_KEYS_OFFSET + 64;
+const KEY_COUNT: usize = EN_PASSANT_KEYS_OFFSET + 8;
+
+/// Hash a position, by combining the keys for everything that is in it.
+///
+/// Hashes can instead be updated incrementally as moves are played, since combining
+/// the same key twice removes it from the hash.
+pub fn get_zobrist_hash(
+    chessboard: &bitboard::BitboardChessboard,
+    to_play_colour: &piece::Colour,
+    castling_rights: bitboard::Bitboard,
+    en_passant_square: Option<square::Square>,
+) -> u64 {
+    let mut hash = 0;
+    for square in chessboard.get_all_occupied_squares().iter() {
+        let piece = chessboard.get_piece(&square).unwrap();
+        hash ^= get_piece_key(&piece, &square);
+    }
+
+    hash ^ get_to_play_key(to_play_colour)
+        ^ get_castling_rights_key(castling_rights)
+        ^ get_en_passant_key(en_passant_square)
+}
+
+pub fn get_piece_key(piece: &piece::Piece, square: &square::Square) -> u64 {
+    let piece_index = bitboard::get_colour_index(piece.get_colour()) * 6
+        + bitboard::get_piece_type_index(piece.get_piece_type());
+    let square_index = bitboard::get_square_index(square) as usize;
+    KEYS[PIECE_KEYS_OFFSET + piece_index * 64 + square_index]
+}
+
+pub fn get_to_play_key(to_play_colour: &piece::Colour) -> u64 {
+    match to_play_colour {
+        piece::Colour::White => 0,
+        piece::Colour::Black => KEYS[BLACK_TO_PLAY_KEY_OFFSET],
+    }
+}
+
+/// Get the key for the squares of the rooks that can still be castled with.
+pub fn get_castling_rights_key(castling_rights: bitboard::Bitboard) -> u64 {
+    castling_rights.iter().fold(0, |key, rook_square| {
+        key ^ KEYS[CASTLING_KEYS_OFFSET + bitboard::get_square_index(&rook_square) as usize]
+    })
+}
+
+/// Get the key for an en passant square, which only depends on its file.
+pub fn get_en_passant_key(en_passant_square: Option<square::Square>) -> u64 {
+    match en_passant_square {
+        Some(square) => KEYS[EN_PASSANT_KEYS_OFFSET + (square.get_file().index() - 1) as usize],
+        None => 0,
+    }
+}
+
+/// Generate pseudorandom keys, using the SplitMix64 generator.
+const fn compute_keys(seed: u64) -> [u64; KEY_COUNT] {
+    let mut keys = [0; KEY_COUNT];
+    let mut state = seed;
+    let mut index = 0;
+    while index < KEY_COUNT {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut key = state;
+        key = (key ^ (key >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        key = (key ^ (key >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        keys[index] = key ^ (key >> 31);
+        index += 1;
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
+    use crate::testing::factories;
+    use std::collections::HashSet;
+
+    #[test]
+    fn keys_are_unique() {
+        let unique_keys: HashSet<&u64> = KEYS.iter().collect();
+
+        assert_eq!(unique_keys.len(), KEY_COUNT);
+    }
+
+    #[test]
+    fn hash_depends_on_player_to_move() {
+        let chessboard = bitboard::BitboardChessboard::from_chessboard(&factories::chessboard());
+
+        let white_hash =
+            get_zobrist_hash(&chessboard, &Colour::White, bitboard::Bitboard::EMPTY, None);
+        let black_hash =
+            get_zobrist_hash(&chessboard, &Colour::Black, bitboard::Bitboard::EMPTY, None);
+
+        assert_ne!(white_hash, black_hash);
+    }
+
+    #[test]
+    fn moving_piece_updates_hash_incrementally() {
+        let mut chessboard =
+            bitboard::BitboardChessboard::from_chessboard(&Chessboard::new(Default::default()));
+        let rook = Piece::new(Colour::White, PieceType::Rook);
+        let from_square = Square::new(Rank::One, File::A);
+        let to_square = Square::new(Rank::Four, File::A);
+        chessboard.put_piece(&from_square, &rook);
+        let hash_before =
+            get_zobrist_hash(&chessboard, &Colour::White, bitboard::Bitboard::EMPTY, None);
+
+        chessboard.remove_piece(&from_square);
+        chessboard.put_piece(&to_square, &rook);
+        let hash_after =
+            get_zobrist_hash(&chessboard, &Colour::White, bitboard::Bitboard::EMPTY, None);
+
+        let incremental_hash =
+            hash_before ^ get_piece_key(&rook, &from_square) ^ get_piece_key(&rook, &to_square);
+        assert_eq!(incremental_hash, hash_after);
+    }
+}
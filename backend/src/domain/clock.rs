@@ -0,0 +1,331 @@
+use crate::domain::chess_set;
+use std::time;
+
+/// The number of moves an engine assumes are left to play when budgeting its search time.
+const EXPECTED_MOVES_TO_GO: u64 = 30;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ClockError {
+    #[error("{0} player has run out of time.")]
+    FlagFell(chess_set::Colour),
+}
+
+/// How much time each player has to make their moves, in milliseconds.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum TimeControl {
+    /// A player gains the increment after each of their moves.
+    Fischer { base_ms: u64, increment_ms: u64 },
+    /// A player gets back the time spent on each move, up to the delay.
+    Bronstein { base_ms: u64, delay_ms: u64 },
+}
+
+/// A chess clock, which counts down the time of the player to move.
+///
+/// Times are given in milliseconds, and timestamps in milliseconds since the Unix epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clock {
+    time_control: TimeControl,
+    white_remaining_ms: u64,
+    black_remaining_ms: u64,
+    turn_started_at_ms: u64,
+    move_remaining_ms: Vec<u64>,
+}
+
+impl Clock {
+    // Factories.
+    pub fn new(time_control: TimeControl, started_at_ms: u64) -> Self {
+        let base_ms = time_control.get_base_ms();
+        Self {
+            time_control,
+            white_remaining_ms: base_ms,
+            black_remaining_ms: base_ms,
+            turn_started_at_ms: started_at_ms,
+            move_remaining_ms: vec![],
+        }
+    }
+
+    pub fn reincarnate(
+        time_control: TimeControl,
+        white_remaining_ms: u64,
+        black_remaining_ms: u64,
+        turn_started_at_ms: u64,
+        move_remaining_ms: Vec<u64>,
+    ) -> Self {
+        Self {
+            time_control,
+            white_remaining_ms,
+            black_remaining_ms,
+            turn_started_at_ms,
+            move_remaining_ms,
+        }
+    }
+
+    // Mutators.
+
+    /// Stop the clock of the player who just moved, recording their remaining time,
+    /// and start their opponent's clock.
+    pub fn punch(&mut self, mover: chess_set::Colour, now_ms: u64) -> Result<u64, ClockError> {
+        let elapsed_ms = now_ms.saturating_sub(self.turn_started_at_ms);
+        let remaining_ms = self.get_remaining_ms(mover);
+        if elapsed_ms > remaining_ms {
+            return Err(ClockError::FlagFell(mover));
+        }
+
+        let bonus_ms = match self.time_control {
+            TimeControl::Fischer { increment_ms, .. } => increment_ms,
+            TimeControl::Bronstein { delay_ms, .. } => elapsed_ms.min(delay_ms),
+        };
+        let updated_remaining_ms = remaining_ms - elapsed_ms + bonus_ms;
+
+        self.set_remaining_ms(mover, updated_remaining_ms);
+        self.turn_started_at_ms = now_ms;
+        self.move_remaining_ms.push(updated_remaining_ms);
+        Ok(updated_remaining_ms)
+    }
+
+    /// Forget the remaining times recorded for moves that were taken back, and restart
+    /// the clock for the player now to move.
+    ///
+    /// Each player is left with the time they had before their moves that were taken back,
+    /// less the time charged to them since, so time spent is not refunded and the increments
+    /// earned on those moves are not kept.
+    pub fn take_back(&mut self, plies: usize, to_play_colour: chess_set::Colour, now_ms: u64) {
+        // The player who was to move before the takeback is charged for their current turn.
+        let mut mover = match plies % 2 {
+            0 => to_play_colour,
+            _ => to_play_colour.swap(),
+        };
+        let elapsed_ms = now_ms.saturating_sub(self.turn_started_at_ms);
+        self.set_remaining_ms(
+            mover,
+            self.get_remaining_ms(mover).saturating_sub(elapsed_ms),
+        );
+
+        let n_moves_kept = self.move_remaining_ms.len().saturating_sub(plies);
+        for _ in n_moves_kept..self.move_remaining_ms.len() {
+            mover = mover.swap();
+            let bonus_ms = match self.time_control {
+                TimeControl::Fischer { increment_ms, .. } => increment_ms,
+                // A delay only gives back time that was spent, so never adds to it.
+                TimeControl::Bronstein { .. } => 0,
+            };
+            self.set_remaining_ms(mover, self.get_remaining_ms(mover).saturating_sub(bonus_ms));
+        }
+
+        self.move_remaining_ms.truncate(n_moves_kept);
+        self.turn_started_at_ms = now_ms;
+    }
+
+    fn set_remaining_ms(&mut self, colour: chess_set::Colour, remaining_ms: u64) {
+        match colour {
+            chess_set::Colour::White => self.white_remaining_ms = remaining_ms,
+            chess_set::Colour::Black => self.black_remaining_ms = remaining_ms,
+        }
+    }
+
+    // Queries.
+    pub fn get_time_control(&self) -> &TimeControl {
+        &self.time_control
+    }
+
+    /// The player's remaining time, as of the start of the current turn.
+    pub fn get_remaining_ms(&self, colour: chess_set::Colour) -> u64 {
+        match colour {
+            chess_set::Colour::White => self.white_remaining_ms,
+            chess_set::Colour::Black => self.black_remaining_ms,
+        }
+    }
+
+    pub fn get_turn_started_at_ms(&self) -> u64 {
+        self.turn_started_at_ms
+    }
+
+    /// The remaining time of the player who made each move, once they had made it.
+    pub fn get_move_remaining_ms(&self) -> &Vec<u64> {
+        &self.move_remaining_ms
+    }
+
+    /// The time the player to move has left, with their current turn's time deducted.
+    pub fn get_time_left_ms(&self, to_play_colour: chess_set::Colour, now_ms: u64) -> u64 {
+        let elapsed_ms = now_ms.saturating_sub(self.turn_started_at_ms);
+        self.get_remaining_ms(to_play_colour)
+            .saturating_sub(elapsed_ms)
+    }
+
+    pub fn has_flag_fallen(&self, to_play_colour: chess_set::Colour, now_ms: u64) -> bool {
+        self.get_time_left_ms(to_play_colour, now_ms) == 0
+    }
+
+    /// How long the player to move can afford to spend on their move.
+    pub fn get_search_time_budget(
+        &self,
+        to_play_colour: chess_set::Colour,
+        now_ms: u64,
+    ) -> time::Duration {
+        let time_left_ms = self.get_time_left_ms(to_play_colour, now_ms);
+        let bonus_ms = match self.time_control {
+            TimeControl::Fischer { increment_ms, .. } => increment_ms,
+            TimeControl::Bronstein { delay_ms, .. } => delay_ms,
+        };
+
+        // Never budget more than the time left, to avoid losing on time.
+        let budget_ms = (time_left_ms / EXPECTED_MOVES_TO_GO + bonus_ms).min(time_left_ms);
+        time::Duration::from_millis(budget_ms)
+    }
+}
+
+impl TimeControl {
+    pub fn get_base_ms(&self) -> u64 {
+        match self {
+            TimeControl::Fischer { base_ms, .. } => *base_ms,
+            TimeControl::Bronstein { base_ms, .. } => *base_ms,
+        }
+    }
+}
+
+/// The current time, in milliseconds since the Unix epoch.
+pub fn now_ms() -> u64 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .expect("System time should be after the Unix epoch.")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+
+    const FISCHER: TimeControl = TimeControl::Fischer {
+        base_ms: 60_000,
+        increment_ms: 2_000,
+    };
+    const BRONSTEIN: TimeControl = TimeControl::Bronstein {
+        base_ms: 60_000,
+        delay_ms: 2_000,
+    };
+
+    #[cfg(test)]
+    mod punch_tests {
+        use super::*;
+
+        #[test]
+        fn fischer_increment_is_added_after_move() {
+            let mut clock = Clock::new(FISCHER, 1_000);
+
+            let result = clock.punch(Colour::White, 6_000);
+
+            assert_eq!(result, Ok(57_000));
+            assert_eq!(clock.get_remaining_ms(Colour::White), 57_000);
+            assert_eq!(clock.get_remaining_ms(Colour::Black), 60_000);
+            assert_eq!(clock.get_turn_started_at_ms(), 6_000);
+        }
+
+        #[test]
+        fn bronstein_delay_returns_time_spent_up_to_delay() {
+            let mut clock = Clock::new(BRONSTEIN, 0);
+
+            let quick_move_result = clock.punch(Colour::White, 1_500);
+            let slow_move_result = clock.punch(Colour::Black, 6_500);
+
+            assert_eq!(quick_move_result, Ok(60_000));
+            assert_eq!(slow_move_result, Ok(57_000));
+        }
+
+        #[test]
+        fn records_remaining_time_of_each_move() {
+            let mut clock = Clock::new(FISCHER, 0);
+
+            clock.punch(Colour::White, 10_000).unwrap();
+            clock.punch(Colour::Black, 15_000).unwrap();
+
+            assert_eq!(clock.get_move_remaining_ms(), &vec![52_000, 57_000]);
+        }
+
+        #[test]
+        fn take_back_forgets_recorded_times_without_refund() {
+            let mut clock = Clock::new(FISCHER, 0);
+            clock.punch(Colour::White, 10_000).unwrap();
+            clock.punch(Colour::Black, 15_000).unwrap();
+
+            clock.take_back(2, Colour::White, 20_000);
+
+            // White spent 10s on their move and 5s on their current turn, and Black 5s.
+            assert!(clock.get_move_remaining_ms().is_empty());
+            assert_eq!(clock.get_remaining_ms(Colour::White), 45_000);
+            assert_eq!(clock.get_remaining_ms(Colour::Black), 55_000);
+            assert_eq!(clock.get_turn_started_at_ms(), 20_000);
+        }
+
+        #[test]
+        fn take_back_does_not_increase_remaining_time() {
+            let mut clock = Clock::new(FISCHER, 0);
+            clock.punch(Colour::White, 10_000).unwrap();
+            clock.punch(Colour::Black, 10_100).unwrap();
+            let black_remaining_ms = clock.get_remaining_ms(Colour::Black);
+
+            // Black moves quickly to earn an increment, then takes the move back.
+            clock.take_back(1, Colour::Black, 10_200);
+
+            assert_eq!(clock.get_move_remaining_ms(), &vec![52_000]);
+            assert_eq!(clock.get_remaining_ms(Colour::Black), 59_900);
+            assert!(clock.get_remaining_ms(Colour::Black) < black_remaining_ms);
+            assert!(clock.get_remaining_ms(Colour::Black) < 60_000);
+            assert_eq!(clock.get_remaining_ms(Colour::White), 51_900);
+        }
+
+        #[test]
+        fn take_back_with_bronstein_delay_keeps_time_left() {
+            let mut clock = Clock::new(BRONSTEIN, 0);
+            clock.punch(Colour::White, 5_000).unwrap();
+
+            clock.take_back(1, Colour::White, 6_000);
+
+            assert_eq!(clock.get_remaining_ms(Colour::White), 57_000);
+            assert_eq!(clock.get_remaining_ms(Colour::Black), 59_000);
+        }
+
+        #[test]
+        fn flag_falls_when_player_runs_out_of_time() {
+            let mut clock = Clock::new(FISCHER, 0);
+
+            let result = clock.punch(Colour::White, 60_001);
+
+            assert_eq!(result, Err(ClockError::FlagFell(Colour::White)));
+            assert_eq!(clock.get_remaining_ms(Colour::White), 60_000);
+            assert!(clock.get_move_remaining_ms().is_empty());
+        }
+    }
+
+    #[cfg(test)]
+    mod query_tests {
+        use super::*;
+
+        #[test]
+        fn time_left_deducts_current_turn() {
+            let clock = Clock::new(FISCHER, 0);
+
+            assert_eq!(clock.get_time_left_ms(Colour::White, 20_000), 40_000);
+            assert!(!clock.has_flag_fallen(Colour::White, 20_000));
+            assert!(clock.has_flag_fallen(Colour::White, 60_000));
+        }
+
+        #[test]
+        fn search_time_budget_spreads_time_left_over_remaining_moves() {
+            let clock = Clock::new(FISCHER, 0);
+
+            let budget = clock.get_search_time_budget(Colour::White, 0);
+
+            assert_eq!(budget, time::Duration::from_millis(4_000));
+        }
+
+        #[test]
+        fn search_time_budget_never_exceeds_time_left() {
+            let clock = Clock::reincarnate(FISCHER, 1_000, 60_000, 0, vec![]);
+
+            let budget = clock.get_search_time_budget(Colour::White, 0);
+
+            assert_eq!(budget, time::Duration::from_millis(1_000));
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use crate::domain::{game, rulebook};
+use std::time;
 use thiserror;
 
 #[derive(thiserror::Error, Debug, PartialEq)]
 pub enum SuggestNextMoveError {
     #[error("The game has already ended.")]
     GameHasAlreadyEnded,
+
+    #[error("The engine resigned.")]
+    EngineResigned,
 }
 
 /// A type that is capable of suggesting chess moves.
@@ -13,4 +17,23 @@ pub enum SuggestNextMoveError {
 pub trait ChessEngine {
     fn generate_next_move(&self, game: &game::Game)
         -> Result<rulebook::Move, SuggestNextMoveError>;
+
+    /// Decide whether to accept the draw the opponent has offered.
+    ///
+    /// Engines decline every draw offer, unless they can judge the position.
+    fn should_accept_draw_offer(&self, _game: &game::Game) -> bool {
+        false
+    }
+
+    /// Decide whether to consent to the opponent taking back their last move.
+    ///
+    /// Engines consent to every takeback, so that casual players can undo their mistakes.
+    fn should_accept_takeback(&self, _game: &game::Game) -> bool {
+        true
+    }
+
+    /// Limit how long the engine may search for, e.g. so it doesn't lose on time.
+    ///
+    /// Engines that don't search for a fixed duration ignore the budget.
+    fn set_search_time_budget(&mut self, _budget: time::Duration) {}
 }
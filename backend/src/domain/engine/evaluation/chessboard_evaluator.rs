@@ -3,7 +3,7 @@ use crate::domain::chess_set;
 pub trait ChessboardEvaluator {
     fn evaluate_position(
         &self,
-        chessboard: &chess_set::Chessboard,
+        chessboard: &chess_set::BitboardChessboard,
         for_colour: &chess_set::Colour,
     ) -> i32;
 }
@@ -3,6 +3,15 @@ use crate::domain::chess_set;
 
 pub struct PiecePlacementChessboardEvaluator;
 
+const PIECE_TYPES: [chess_set::PieceType; 6] = [
+    chess_set::PieceType::Pawn,
+    chess_set::PieceType::Knight,
+    chess_set::PieceType::Bishop,
+    chess_set::PieceType::Rook,
+    chess_set::PieceType::Queen,
+    chess_set::PieceType::King,
+];
+
 impl chessboard_evaluator::ChessboardEvaluator for PiecePlacementChessboardEvaluator {
     /// Evaluate the chessboard based on fixed scores assigned to pieces and squares.
     ///
@@ -10,21 +19,23 @@ impl chessboard_evaluator::ChessboardEvaluator for PiecePlacementChessboardEvalu
     /// https://github.com/asdfjkl/neural_network_chess/releases
     fn evaluate_position(
         &self,
-        chessboard: &chess_set::Chessboard,
+        chessboard: &chess_set::BitboardChessboard,
         for_colour: &chess_set::Colour,
     ) -> i32 {
         let mut total_score = 0;
 
-        for (square, maybe_piece) in chessboard.position.clone().into_iter() {
-            let Some(piece) = maybe_piece else { continue };
+        for colour in [chess_set::Colour::White, chess_set::Colour::Black] {
+            for piece_type in PIECE_TYPES {
+                let piece_score = evaluate_piece(&piece_type);
 
-            let piece_score = evaluate_piece(piece.get_piece_type());
-            let square_score = evaluate_square(&square);
-            let score = piece_score + square_score;
+                for square in chessboard.get_squares_of(&colour, &piece_type).iter() {
+                    let score = piece_score + evaluate_square(&square);
 
-            match piece.get_colour() == for_colour {
-                true => total_score += score,
-                false => total_score -= score,
+                    match &colour == for_colour {
+                        true => total_score += score,
+                        false => total_score -= score,
+                    }
+                }
             }
         }
 
@@ -60,7 +71,9 @@ fn evaluate_piece(piece_type: &chess_set::PieceType) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::PiecePlacementChessboardEvaluator;
-    use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
+    use crate::domain::chess_set::{
+        BitboardChessboard, Chessboard, Colour, File, Piece, PieceType, Rank, Square,
+    };
     use crate::domain::engine::evaluation::chessboard_evaluator::ChessboardEvaluator;
     use crate::testing::factories;
     use rstest::rstest;
@@ -70,7 +83,7 @@ mod tests {
     #[case::white(Colour::White)]
     #[case::black(Colour::Black)]
     fn initial_position_scores_as_zero_for_both_players(#[case] for_colour: Colour) {
-        let chessboard = factories::chessboard();
+        let chessboard = BitboardChessboard::from_chessboard(&factories::chessboard());
 
         let score = PiecePlacementChessboardEvaluator.evaluate_position(&chessboard, &for_colour);
 
@@ -89,7 +102,7 @@ mod tests {
         let square = Square::new(Rank::Three, File::E);
         starting_position.insert(square, white_queen);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = BitboardChessboard::from_chessboard(&Chessboard::new(starting_position));
 
         let score = PiecePlacementChessboardEvaluator.evaluate_position(&chessboard, &for_colour);
 
@@ -113,7 +126,7 @@ mod tests {
         let square = Square::new(Rank::Three, File::E);
         starting_position.insert(square, white_pawn);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = BitboardChessboard::from_chessboard(&Chessboard::new(starting_position));
 
         let score = PiecePlacementChessboardEvaluator.evaluate_position(&chessboard, &for_colour);
 
@@ -1,12 +1,31 @@
 use super::engine;
 use crate::domain::engine::evaluation;
 use crate::domain::{chess_set, game, rulebook};
-use std::cmp;
+use std::{cmp, time};
 
 /// Classical style chess engine that uses minimax with alpha-beta pruning.
+///
+/// Given a time budget, the engine deepens its search one ply at a time until the
+/// budget runs out, and plays the best move of the deepest search it completed.
 pub struct Minimax {
     evaluator: Box<dyn evaluation::ChessboardEvaluator>,
     max_search_depth: u8,
+    /// The engine resigns rather than move when its best score falls below this threshold.
+    resign_threshold: Option<i32>,
+    search_time_budget: Option<time::Duration>,
+}
+
+/// How deep a search may go, and when it must be abandoned by.
+struct SearchLimits {
+    max_search_depth: u8,
+    deadline: Option<time::Instant>,
+}
+
+impl SearchLimits {
+    fn is_past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| time::Instant::now() >= deadline)
+    }
 }
 
 impl engine::ChessEngine for Minimax {
@@ -18,13 +37,58 @@ impl engine::ChessEngine for Minimax {
             return Err(engine::SuggestNextMoveError::GameHasAlreadyEnded);
         };
 
-        let (_, maybe_move) = self.minimax(&to_play_colour, game.clone(), 0, i32::MIN, i32::MAX);
+        let mut position = game.get_search_position();
+        let rules = game.get_variant().get_rules();
+        let (score, maybe_move) = self.search(rules, &to_play_colour, &mut position);
+
+        if let Some(resign_threshold) = self.resign_threshold {
+            if score < resign_threshold {
+                return Err(engine::SuggestNextMoveError::EngineResigned);
+            }
+        }
 
         match maybe_move {
             Some(chess_move) => Ok(chess_move),
             None => panic!("Minimax di not generate a move!"),
         }
     }
+
+    /// Accept a draw offer unless the search scores the position in the engine's favour.
+    fn should_accept_draw_offer(&self, game: &game::Game) -> bool {
+        let (Some(_), Some(draw_offer)) =
+            (game.get_status().to_play_colour(), game.get_draw_offer())
+        else {
+            return false;
+        };
+
+        let mut position = game.get_search_position();
+        let rules = game.get_variant().get_rules();
+        let responder = draw_offer.offered_by.swap();
+        let limits = SearchLimits {
+            max_search_depth: self.max_search_depth,
+            deadline: None,
+        };
+        let (score, _) = self
+            .minimax(
+                rules,
+                &responder,
+                &mut position,
+                0,
+                i32::MIN,
+                i32::MAX,
+                &limits,
+            )
+            .expect("Searches without a deadline should complete.");
+
+        score <= 0
+    }
+
+    fn set_search_time_budget(&mut self, budget: time::Duration) {
+        self.search_time_budget = Some(match self.search_time_budget {
+            Some(search_time_budget) => search_time_budget.min(budget),
+            None => budget,
+        });
+    }
 }
 
 impl Minimax {
@@ -32,25 +96,95 @@ impl Minimax {
         Self {
             evaluator,
             max_search_depth,
+            resign_threshold: None,
+            search_time_budget: None,
+        }
+    }
+
+    /// Create an engine that resigns when the evaluator scores its position below a threshold.
+    pub fn new_with_resign_threshold(
+        evaluator: Box<dyn evaluation::ChessboardEvaluator>,
+        max_search_depth: u8,
+        resign_threshold: i32,
+    ) -> Self {
+        Self {
+            evaluator,
+            max_search_depth,
+            resign_threshold: Some(resign_threshold),
+            search_time_budget: None,
         }
     }
 
+    /// Search for the best move, within the time budget if there is one.
+    ///
+    /// Without a budget, the search goes straight to the maximum depth. With one, the
+    /// search is deepened a ply at a time, and a search that runs past the deadline is
+    /// abandoned. The shallowest search always completes, so that a move is generated.
+    fn search(
+        &self,
+        rules: &dyn rulebook::Variant,
+        maximizer: &chess_set::Colour,
+        position: &mut rulebook::SearchPosition,
+    ) -> (i32, Option<rulebook::Move>) {
+        let (first_search_depth, deadline) = match self.search_time_budget {
+            Some(budget) => (1, Some(time::Instant::now() + budget)),
+            None => (self.max_search_depth, None),
+        };
+
+        let limits = SearchLimits {
+            max_search_depth: first_search_depth,
+            deadline: None,
+        };
+        let mut result = self
+            .minimax(rules, maximizer, position, 0, i32::MIN, i32::MAX, &limits)
+            .expect("Searches without a deadline should complete.");
+
+        for max_search_depth in first_search_depth + 1..=self.max_search_depth {
+            let limits = SearchLimits {
+                max_search_depth,
+                deadline,
+            };
+            match self.minimax(rules, maximizer, position, 0, i32::MIN, i32::MAX, &limits) {
+                Some(deeper_result) => result = deeper_result,
+                None => break,
+            }
+        }
+        result
+    }
+
+    /// Search the game tree beneath a position.
+    ///
+    /// Moves are made on the position and then unmade, so the position is unchanged
+    /// once the search returns. Moves are generated and outcomes judged by the rules
+    /// of the game's variant. `None` is returned if the search runs past its deadline.
+    #[allow(clippy::too_many_arguments)]
     fn minimax(
         &self,
+        rules: &dyn rulebook::Variant,
         maximizer: &chess_set::Colour,
-        game: game::Game,
+        position: &mut rulebook::SearchPosition,
         current_search_depth: u8,
         mut alpha: i32,
         mut beta: i32,
-    ) -> (i32, Option<rulebook::Move>) {
-        if let Some(terminal_score) =
-            self.evaluate_if_should_stop_searching(maximizer, &game, current_search_depth)
-        {
-            return (terminal_score, None);
+        limits: &SearchLimits,
+    ) -> Option<(i32, Option<rulebook::Move>)> {
+        if limits.is_past_deadline() {
+            return None;
         }
 
-        let to_play_colour = game.get_status().to_play_colour().unwrap();
-        let is_maximizers_move = &to_play_colour == maximizer;
+        let legal_moves = rules.get_search_legal_moves(position);
+        if let Some(terminal_score) = self.evaluate_if_should_stop_searching(
+            rules,
+            maximizer,
+            position,
+            &legal_moves,
+            current_search_depth,
+            limits.max_search_depth,
+        ) {
+            return Some((terminal_score, None));
+        }
+
+        let is_maximizers_move = &position.get_to_play_colour() == maximizer;
 
         let mut current_best_move: Option<rulebook::Move> = None;
         let mut current_best_score = match is_maximizers_move {
@@ -58,17 +192,19 @@ impl Minimax {
             false => i32::MAX,
         };
 
-        for chess_move in rulebook::get_legal_moves(to_play_colour, game.get_chessboard_history()) {
-            let mut deeper_game = game.clone();
-            deeper_game.play_validated_move(&chess_move).unwrap();
-
-            let (maybe_better_score, _) = self.minimax(
+        for chess_move in legal_moves {
+            position.make_move(&chess_move);
+            let maybe_result = self.minimax(
+                rules,
                 maximizer,
-                deeper_game,
+                position,
                 current_search_depth + 1,
                 alpha,
                 beta,
+                limits,
             );
+            position.unmake_move();
+            let (maybe_better_score, _) = maybe_result?;
 
             if is_maximizers_move {
                 if maybe_better_score > current_best_score {
@@ -79,7 +215,7 @@ impl Minimax {
 
                 if alpha >= beta {
                     // Prune.
-                    return (current_best_score, current_best_move);
+                    return Some((current_best_score, current_best_move));
                 }
             } else {
                 if maybe_better_score < current_best_score {
@@ -90,30 +226,28 @@ impl Minimax {
 
                 if beta <= alpha {
                     // Prune.
-                    return (current_best_score, current_best_move);
+                    return Some((current_best_score, current_best_move));
                 }
             }
         }
 
-        (current_best_score, current_best_move)
+        Some((current_best_score, current_best_move))
     }
 
     fn evaluate_if_should_stop_searching(
         &self,
+        rules: &dyn rulebook::Variant,
         maximizer: &chess_set::Colour,
-        game: &game::Game,
+        position: &rulebook::SearchPosition,
+        legal_moves: &[rulebook::Move],
         current_search_depth: u8,
+        max_search_depth: u8,
     ) -> Option<i32> {
-        let game_status = game.get_status();
-
-        // Evaluate a drawn game.
-        if game_status.is_draw() {
-            return Some(0);
-        };
-
-        // Evaluate a won game.
-        match game_status.winner() {
-            Some(colour) => {
+        match rules.get_search_outcome(position, legal_moves) {
+            // Evaluate a drawn game.
+            Some(rulebook::Outcome::Drawn(_)) => return Some(0),
+            // Evaluate a won game.
+            Some(rulebook::Outcome::Won(colour)) => {
                 return match &colour == maximizer {
                     // Current search depth is included to reward a quicker win / slower loss.
                     true => Some(i32::MAX - (current_search_depth as i32)),
@@ -123,11 +257,19 @@ impl Minimax {
             None => {}
         }
 
-        // Evaluate the position if we're at the max search depth.
-        if current_search_depth == self.max_search_depth {
+        // Evaluate a position reached earlier in the game as a draw, to avoid perpetual loops.
+        // The position the search starts from is excluded, so that a move is always generated.
+        if current_search_depth > 0 && position.get_repetition_count() > 1 {
+            return Some(0);
+        };
+
+        // Evaluate the position if we're at the max search depth, including progress towards
+        // any goal the variant adds.
+        if current_search_depth == max_search_depth {
             let score = self
                 .evaluator
-                .evaluate_position(game.current_chessboard(), maximizer);
+                .evaluate_position(position.get_chessboard(), maximizer)
+                + rules.evaluate_search_position(position, maximizer);
             return Some(score);
         }
 
@@ -139,9 +281,12 @@ impl Minimax {
 mod tests {
     use super::Minimax;
     use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
-    use crate::domain::engine::{evaluation, ChessEngine};
-    use crate::domain::game::{Game, GameStatus};
+    use crate::domain::engine::{evaluation, ChessEngine, SuggestNextMoveError};
+    use crate::domain::game::{Game, GameStart, GameStatus, GameVariant};
+    use crate::domain::rulebook;
+    use crate::testing::factories;
     use std::collections::BTreeMap;
+    use std::time;
 
     #[test]
     fn minimax_completes_fools_mate_for_black() {
@@ -204,7 +349,18 @@ mod tests {
         starting_position.insert(black_queen_square, black_queen);
 
         let chessboard = Chessboard::new(starting_position);
-        let mut game = Game::reincarnate(1, GameStatus::ToPlayBlack, vec![chessboard]);
+        let mut game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayBlack,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::Standard,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
 
         let evaluator = evaluation::PiecePlacementChessboardEvaluator;
         // Allow a search depth of 3, so that black can initially see the guaranteed mate.
@@ -240,7 +396,18 @@ mod tests {
         starting_position.insert(white_king_square, white_king);
 
         let chessboard = Chessboard::new(starting_position);
-        let game = Game::reincarnate(1, GameStatus::ToPlayBlack, vec![chessboard]);
+        let game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayBlack,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::Standard,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
 
         let evaluator = evaluation::PiecePlacementChessboardEvaluator;
         let minimax_engine = Minimax::new(Box::new(evaluator), 2);
@@ -250,4 +417,265 @@ mod tests {
         assert_eq!(generated_move.from_square, black_king_square);
         assert_eq!(generated_move.to_square, white_queen_square);
     }
+
+    #[test]
+    fn minimax_steps_onto_the_hill_rather_than_capturing_queen() {
+        let mut starting_position = BTreeMap::new();
+
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        let white_king_square = Square::new(Rank::Three, File::C);
+        starting_position.insert(white_king_square, white_king);
+
+        let white_rook = Piece::new(Colour::White, PieceType::Rook);
+        starting_position.insert(Square::new(Rank::One, File::H), white_rook);
+
+        // Leave the black queen en prise to the white rook.
+        let black_queen = Piece::new(Colour::Black, PieceType::Queen);
+        starting_position.insert(Square::new(Rank::Seven, File::H), black_queen);
+
+        let black_king = Piece::new(Colour::Black, PieceType::King);
+        starting_position.insert(Square::new(Rank::Eight, File::A), black_king);
+
+        let chessboard = Chessboard::new(starting_position);
+        let mut game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayWhite,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::KingOfTheHill,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
+
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let minimax_engine = Minimax::new(Box::new(evaluator), 2);
+
+        let generated_move = minimax_engine.generate_next_move(&game).unwrap();
+
+        assert_eq!(generated_move.from_square, white_king_square);
+        assert_eq!(generated_move.to_square, Square::new(Rank::Four, File::D));
+
+        game.play_validated_move(&generated_move).unwrap();
+
+        assert_eq!(game.get_status(), &GameStatus::WonByWhite);
+    }
+
+    #[test]
+    fn minimax_resigns_when_score_is_below_threshold() {
+        let mut starting_position = BTreeMap::new();
+
+        // Black has a lone king against a white queen.
+        let black_king = Piece::new(Colour::Black, PieceType::King);
+        starting_position.insert(Square::new(Rank::Eight, File::A), black_king);
+
+        let white_queen = Piece::new(Colour::White, PieceType::Queen);
+        starting_position.insert(Square::new(Rank::Four, File::D), white_queen);
+
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        starting_position.insert(Square::new(Rank::One, File::H), white_king);
+
+        let chessboard = Chessboard::new(starting_position);
+        let game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayBlack,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::Standard,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
+
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let minimax_engine = Minimax::new_with_resign_threshold(Box::new(evaluator), 1, -500);
+
+        let result = minimax_engine.generate_next_move(&game);
+
+        assert!(matches!(result, Err(SuggestNextMoveError::EngineResigned)));
+    }
+
+    #[test]
+    fn minimax_does_not_resign_when_score_is_above_threshold() {
+        let game = Game::new(1);
+
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let minimax_engine = Minimax::new_with_resign_threshold(Box::new(evaluator), 1, -500);
+
+        let result = minimax_engine.generate_next_move(&game);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn minimax_accepts_draw_offer_when_losing() {
+        let mut starting_position = BTreeMap::new();
+
+        // Black has a lone king against a white queen, and white offers a draw.
+        let black_king = Piece::new(Colour::Black, PieceType::King);
+        starting_position.insert(Square::new(Rank::Eight, File::A), black_king);
+
+        let white_queen = Piece::new(Colour::White, PieceType::Queen);
+        starting_position.insert(Square::new(Rank::Four, File::D), white_queen);
+
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        starting_position.insert(Square::new(Rank::One, File::H), white_king);
+
+        let chessboard = Chessboard::new(starting_position);
+        let mut game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayBlack,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::Standard,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
+        game.offer_draw(&Colour::White).unwrap();
+
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let minimax_engine = Minimax::new(Box::new(evaluator), 1);
+
+        assert!(minimax_engine.should_accept_draw_offer(&game));
+    }
+
+    #[test]
+    fn minimax_declines_draw_offer_when_winning() {
+        let mut starting_position = BTreeMap::new();
+
+        // White has a queen against a lone king, and black offers a draw.
+        let black_king = Piece::new(Colour::Black, PieceType::King);
+        starting_position.insert(Square::new(Rank::Eight, File::A), black_king);
+
+        let white_queen = Piece::new(Colour::White, PieceType::Queen);
+        starting_position.insert(Square::new(Rank::Four, File::D), white_queen);
+
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        starting_position.insert(Square::new(Rank::One, File::H), white_king);
+
+        let chessboard = Chessboard::new(starting_position);
+        let mut game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayBlack,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::Standard,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
+        game.offer_draw(&Colour::Black).unwrap();
+
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let minimax_engine = Minimax::new(Box::new(evaluator), 1);
+
+        assert!(!minimax_engine.should_accept_draw_offer(&game));
+    }
+
+    #[test]
+    fn minimax_search_stops_once_time_budget_runs_out() {
+        let game = Game::new(1);
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let mut minimax_engine = Minimax::new(Box::new(evaluator), 20);
+
+        minimax_engine.set_search_time_budget(time::Duration::from_millis(50));
+        let started_at = time::Instant::now();
+        let generated_move = minimax_engine.generate_next_move(&game);
+
+        assert!(generated_move.is_ok());
+        assert!(started_at.elapsed() < time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn minimax_with_exhausted_time_budget_still_finds_mate_in_one() {
+        let game = factories::game_from_fen("k7/8/1K6/8/8/8/8/7R w - - 0 1");
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let mut minimax_engine = Minimax::new(Box::new(evaluator), 3);
+
+        minimax_engine.set_search_time_budget(time::Duration::ZERO);
+        let generated_move = minimax_engine.generate_next_move(&game).unwrap();
+
+        assert_eq!(generated_move.to_square, Square::new(Rank::Eight, File::H));
+    }
+
+    #[test]
+    fn search_time_budget_only_ever_shortens() {
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let mut minimax_engine = Minimax::new(Box::new(evaluator), 3);
+
+        minimax_engine.set_search_time_budget(time::Duration::from_millis(50));
+        minimax_engine.set_search_time_budget(time::Duration::from_secs(5));
+
+        assert_eq!(
+            minimax_engine.search_time_budget,
+            Some(time::Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    fn minimax_evaluates_repeated_position_as_draw() {
+        let mut game = Game::new(1);
+
+        // Shuffle the knights out and back, repeating the starting position.
+        for (colour, from_square, to_square) in [
+            (
+                Colour::White,
+                Square::new(Rank::One, File::G),
+                Square::new(Rank::Three, File::F),
+            ),
+            (
+                Colour::Black,
+                Square::new(Rank::Eight, File::G),
+                Square::new(Rank::Six, File::F),
+            ),
+            (
+                Colour::White,
+                Square::new(Rank::Three, File::F),
+                Square::new(Rank::One, File::G),
+            ),
+            (
+                Colour::Black,
+                Square::new(Rank::Six, File::F),
+                Square::new(Rank::Eight, File::G),
+            ),
+        ] {
+            let _ = game.play_move(&colour, &from_square, &to_square).unwrap();
+        }
+
+        let evaluator = evaluation::PiecePlacementChessboardEvaluator;
+        let minimax_engine = Minimax::new(Box::new(evaluator), 3);
+
+        let position = game.get_search_position();
+        let legal_moves = position.get_legal_moves();
+
+        let score = minimax_engine.evaluate_if_should_stop_searching(
+            &rulebook::Standard,
+            &Colour::White,
+            &position,
+            &legal_moves,
+            1,
+            3,
+        );
+        assert_eq!(score, Some(0));
+
+        // The search must still continue from a repeated starting position.
+        let score = minimax_engine.evaluate_if_should_stop_searching(
+            &rulebook::Standard,
+            &Colour::White,
+            &position,
+            &legal_moves,
+            0,
+            3,
+        );
+        assert_eq!(score, None);
+    }
 }
@@ -1,13 +1,13 @@
-use super::{engine, random, ChessEngine};
-use crate::domain::game::GameStatus;
+use super::{engine, ChessEngine};
 use crate::domain::{chess_set, game, rulebook};
+use rand::seq::SliceRandom;
 use std::collections::HashMap;
 use std::time;
 
 const UPPER_CONFIDENCE_BOUND_BIAS: f32 = 1.4;
 
 pub struct MonteCarloTreeSearch {
-    max_search_duration_seconds: u64,
+    max_search_duration: time::Duration,
 }
 
 impl ChessEngine for MonteCarloTreeSearch {
@@ -19,38 +19,52 @@ impl ChessEngine for MonteCarloTreeSearch {
             return Err(engine::SuggestNextMoveError::GameHasAlreadyEnded);
         };
 
-        let mut mcts_tree = MCTSTree::new(to_play_colour, game.clone());
+        let position = game.get_search_position();
+        let rules = game.get_variant().get_rules();
+        let mut mcts_tree = MCTSTree::new(rules, to_play_colour, position);
 
         let started_searching_at = time::Instant::now();
 
-        while started_searching_at.elapsed().as_secs() < self.max_search_duration_seconds {
+        while started_searching_at.elapsed() < self.max_search_duration {
             let selected_node_id = mcts_tree.select(&MCTSTree::root_node_id());
             let selected_node = mcts_tree.get_node(&selected_node_id);
 
             if !selected_node.is_terminal_node() {
                 let child_node_id = mcts_tree.expand(&selected_node_id);
-                let payout = mcts_tree.simulate(child_node_id);
+                let payout = mcts_tree.simulate();
                 mcts_tree.backpropagate(child_node_id, payout);
             } else {
-                let payout = mcts_tree.simulate(selected_node_id);
+                let payout = mcts_tree.simulate();
                 mcts_tree.backpropagate(selected_node_id, payout);
             }
+            mcts_tree.return_to_root();
         }
 
         Ok(mcts_tree.get_best_move())
     }
+
+    fn set_search_time_budget(&mut self, budget: time::Duration) {
+        self.max_search_duration = self.max_search_duration.min(budget);
+    }
 }
 
 impl MonteCarloTreeSearch {
     pub fn new(max_search_duration_seconds: u64) -> Self {
         Self {
-            max_search_duration_seconds,
+            max_search_duration: time::Duration::from_secs(max_search_duration_seconds),
         }
     }
 }
 
+/// The tree of nodes searched so far, and a single position that is moved between them.
+///
+/// Nodes don't store their own position. Instead, moves are made on the tree's position
+/// when descending the tree, and unmade when returning to the root.
 struct MCTSTree {
+    rules: &'static dyn rulebook::Variant,
     to_play_colour: chess_set::Colour,
+    position: rulebook::SearchPosition,
+    moves_made_from_root: u32,
     nodes: HashMap<u32, MCTSNode>,
     max_node_id: u32,
 }
@@ -59,7 +73,6 @@ struct MCTSTree {
 struct MCTSNode {
     id: u32,
     parent_node_id: Option<u32>,
-    game_state_at_node: game::Game,
     child_nodes: HashMap<u32, rulebook::Move>,
     unvisited_legal_moves: Vec<rulebook::Move>,
     // Stats.
@@ -69,15 +82,22 @@ struct MCTSNode {
 
 impl MCTSTree {
     // Factories.
-    fn new(to_play_colour: chess_set::Colour, game: game::Game) -> Self {
+    fn new(
+        rules: &'static dyn rulebook::Variant,
+        to_play_colour: chess_set::Colour,
+        position: rulebook::SearchPosition,
+    ) -> Self {
         let mut nodes = HashMap::new();
 
         let root_node_id = MCTSTree::root_node_id();
-        let root_node = MCTSNode::new(root_node_id, None, game);
+        let root_node = MCTSNode::new(root_node_id, None, rules, &position);
         nodes.insert(root_node_id, root_node);
 
         Self {
+            rules,
             to_play_colour,
+            position,
+            moves_made_from_root: 0,
             nodes,
             max_node_id: root_node_id + 1,
         }
@@ -86,7 +106,9 @@ impl MCTSTree {
     // MCTS algorithm.
 
     /// Select the next node to expand using the UCB formula.
-    fn select(&self, parent_node_id: &u32) -> u32 {
+    ///
+    /// The moves leading to the selected node are made on the tree's position.
+    fn select(&mut self, parent_node_id: &u32) -> u32 {
         let parent_node = self.get_node(parent_node_id);
 
         if parent_node.is_leaf_node() || parent_node.is_terminal_node() {
@@ -106,7 +128,11 @@ impl MCTSTree {
             }
         }
         match child_node_id_with_max_uct {
-            Some(child_node_id) => self.select(&child_node_id),
+            Some(child_node_id) => {
+                let chess_move = parent_node.child_nodes.get(&child_node_id).unwrap().clone();
+                self.make_move(&chess_move);
+                self.select(&child_node_id)
+            }
             None => panic!("No child nodes found!"),
         }
     }
@@ -116,28 +142,35 @@ impl MCTSTree {
         let mut parent_node = self.get_node(parent_node_id).clone();
         let legal_move = parent_node.pop_unvisited_legal_move();
 
-        let mut deeper_game = parent_node.game_state_at_node.clone();
-        deeper_game.play_validated_move(&legal_move).unwrap();
-
-        let child_node_id = self.create_node(parent_node_id, deeper_game);
+        self.make_move(&legal_move);
+        let child_node_id = self.create_node(parent_node_id);
         parent_node.add_child(child_node_id, legal_move);
         self.update_node(parent_node); // Since we had to clone the parent node.
 
         child_node_id
     }
 
-    /// Play random moves from a given node until reaching a conclusion.
-    fn simulate(&mut self, from_node_id: u32) -> f32 {
-        let engine = random::Random::new();
-        let mut simulated_game = self.get_node(&from_node_id).game_state_at_node.clone();
+    /// Play random moves from the tree's position until reaching a conclusion.
+    ///
+    /// The random moves are unmade again before returning.
+    fn simulate(&mut self) -> f32 {
+        let mut rng = rand::thread_rng();
+        let mut simulated_moves = 0;
+
+        let outcome = loop {
+            let legal_moves = self.rules.get_search_legal_moves(&self.position);
+            if let Some(outcome) = self.rules.get_search_outcome(&self.position, &legal_moves) {
+                break outcome;
+            };
+            self.position
+                .make_move(legal_moves.choose(&mut rng).unwrap());
+            simulated_moves += 1;
+        };
 
-        while simulated_game.get_status().to_play_colour().is_some() {
-            let suggested_move = engine.generate_next_move(&simulated_game).unwrap();
-            simulated_game.play_validated_move(&suggested_move).unwrap();
+        for _ in 0..simulated_moves {
+            self.position.unmake_move();
         }
-
-        let outcome = simulated_game.get_status();
-        self.evaluate_simulation_outcome(outcome)
+        self.evaluate_simulation_outcome(&outcome)
     }
 
     /// Backpropagate the result from the simulation to all parent_node nodes.
@@ -154,12 +187,26 @@ impl MCTSTree {
     }
 
     // Mutators.
-    fn create_node(&mut self, parent_node_id: &u32, game_state_at_node: game::Game) -> u32 {
+    fn make_move(&mut self, chess_move: &rulebook::Move) {
+        self.position.make_move(chess_move);
+        self.moves_made_from_root += 1;
+    }
+
+    /// Unmake the moves made since leaving the root node.
+    fn return_to_root(&mut self) {
+        for _ in 0..self.moves_made_from_root {
+            self.position.unmake_move();
+        }
+        self.moves_made_from_root = 0;
+    }
+
+    fn create_node(&mut self, parent_node_id: &u32) -> u32 {
         let child_node_id = self.get_next_node_id();
         let node = MCTSNode::new(
             child_node_id,
-            Some(parent_node_id.clone()),
-            game_state_at_node,
+            Some(*parent_node_id),
+            self.rules,
+            &self.position,
         );
         self.nodes.insert(child_node_id, node);
 
@@ -195,17 +242,14 @@ impl MCTSTree {
         }
     }
 
-    fn evaluate_simulation_outcome(&self, outcome: &GameStatus) -> f32 {
-        if outcome.is_draw() {
-            return 0.;
-        } else if let Some(winner) = outcome.winner() {
-            return match winner == self.to_play_colour {
+    fn evaluate_simulation_outcome(&self, outcome: &rulebook::Outcome) -> f32 {
+        match outcome {
+            rulebook::Outcome::Drawn(_) => 0.,
+            rulebook::Outcome::Won(winner) => match winner == &self.to_play_colour {
                 true => 1.,
                 false => -1.,
-            };
+            },
         }
-
-        panic!("Simulated game has no outcome!")
     }
 
     fn get_node(&self, id: &u32) -> &MCTSNode {
@@ -220,17 +264,19 @@ impl MCTSTree {
 impl MCTSNode {
     // Factories.
 
-    fn new(id: u32, parent_node_id: Option<u32>, game_state_at_node: game::Game) -> Self {
-        let legal_moves = match game_state_at_node.get_status().to_play_colour() {
-            Some(colour) => {
-                rulebook::get_legal_moves(colour, game_state_at_node.get_chessboard_history())
-            }
-            None => vec![],
+    fn new(
+        id: u32,
+        parent_node_id: Option<u32>,
+        rules: &dyn rulebook::Variant,
+        position: &rulebook::SearchPosition,
+    ) -> Self {
+        let mut legal_moves = rules.get_search_legal_moves(position);
+        if rules.get_search_outcome(position, &legal_moves).is_some() {
+            legal_moves.clear();
         };
 
         Self {
             id,
-            game_state_at_node,
             parent_node_id,
             child_nodes: HashMap::new(),
             unvisited_legal_moves: legal_moves,
@@ -281,8 +327,9 @@ impl MCTSNode {
 mod tests {
     use super::{ChessEngine, MonteCarloTreeSearch};
     use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
-    use crate::domain::game::{Game, GameStatus};
+    use crate::domain::game::{Game, GameStart, GameStatus, GameVariant};
     use std::collections::BTreeMap;
+    use std::time;
 
     #[test] // Smokey.
     fn generates_move_for_black() {
@@ -302,7 +349,18 @@ mod tests {
         starting_position.insert(black_rook_square, black_rook);
 
         let chessboard = Chessboard::new(starting_position);
-        let game = Game::reincarnate(1, GameStatus::ToPlayBlack, vec![chessboard]);
+        let game = Game::reincarnate(
+            1,
+            GameStatus::ToPlayBlack,
+            None,
+            None,
+            None,
+            None,
+            GameVariant::Standard,
+            GameStart::default(),
+            vec![],
+            vec![chessboard],
+        );
 
         let max_search_duration_seconds = 1;
         let mcts_engine = MonteCarloTreeSearch::new(max_search_duration_seconds);
@@ -311,4 +369,28 @@ mod tests {
 
         assert!(black_move.is_ok());
     }
+
+    #[test]
+    fn search_time_budget_shortens_search() {
+        let mut mcts_engine = MonteCarloTreeSearch::new(10);
+
+        mcts_engine.set_search_time_budget(time::Duration::from_millis(50));
+
+        assert_eq!(
+            mcts_engine.max_search_duration,
+            time::Duration::from_millis(50)
+        );
+    }
+
+    #[test]
+    fn search_time_budget_does_not_lengthen_search() {
+        let mut mcts_engine = MonteCarloTreeSearch::new(1);
+
+        mcts_engine.set_search_time_budget(time::Duration::from_secs(5));
+
+        assert_eq!(
+            mcts_engine.max_search_duration,
+            time::Duration::from_secs(1)
+        );
+    }
 }
@@ -12,12 +12,11 @@ impl engine::ChessEngine for Random {
         &self,
         game: &game::Game,
     ) -> Result<rulebook::Move, engine::SuggestNextMoveError> {
-        let Some(to_play_colour) = game.get_status().to_play_colour() else {
+        if game.get_status().to_play_colour().is_none() {
             return Err(engine::SuggestNextMoveError::GameHasAlreadyEnded);
         };
 
-        let mut legal_moves =
-            rulebook::get_legal_moves(to_play_colour, game.get_chessboard_history());
+        let mut legal_moves = game.get_legal_moves();
 
         let mut rng = thread_rng();
         let selected_move_index = rng.gen_range(0..legal_moves.len());
@@ -54,7 +53,18 @@ mod tests {
 
     #[test]
     fn returns_error_when_game_has_already_ended() {
-        let game = game::Game::reincarnate(1, game::GameStatus::Drawn, vec![]);
+        let game = game::Game::reincarnate(
+            1,
+            game::GameStatus::Drawn,
+            None,
+            None,
+            None,
+            None,
+            game::GameVariant::Standard,
+            game::GameStart::default(),
+            vec![],
+            vec![],
+        );
         let engine = Random::new();
 
         let suggested_move = engine.generate_next_move(&game);
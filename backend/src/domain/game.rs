@@ -1,4 +1,5 @@
 use crate::domain::chess_set;
+use crate::domain::clock;
 use crate::domain::rulebook;
 use serde;
 
@@ -21,6 +22,39 @@ pub enum GameError {
 
     #[error("{0}")]
     ChessboardActionError(chess_set::ChessboardActionError),
+
+    #[error("There is no draw that can be claimed.")]
+    NoDrawToClaim,
+
+    #[error("'{0}' is not a legal move.")]
+    InvalidSan(String),
+
+    #[error("A draw has already been offered.")]
+    DrawAlreadyOffered,
+
+    #[error("There is no draw offer to respond to.")]
+    NoDrawOffer,
+
+    #[error("{0} player cannot respond to their own draw offer.")]
+    CannotRespondToOwnDrawOffer(chess_set::Colour),
+
+    #[error("The game's clock has already been started.")]
+    ClockAlreadyStarted,
+
+    #[error("A takeback has already been requested.")]
+    TakebackAlreadyRequested,
+
+    #[error("There is no takeback request to respond to.")]
+    NoTakebackRequest,
+
+    #[error("{0} player cannot respond to their own takeback request.")]
+    CannotRespondToOwnTakebackRequest(chess_set::Colour),
+
+    #[error("{0} player has no moves to take back.")]
+    NoMovesToTakeBack(chess_set::Colour),
+
+    #[error("The move is not allowed by the rules of the game's variant.")]
+    MoveNotAllowedByVariant,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
@@ -32,44 +66,198 @@ pub enum GameStatus {
     Drawn,
 }
 
+/// The reason a game ended.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub enum GameTermination {
+    Checkmate,
+    Stalemate,
+    InsufficientMaterial,
+    Repetition,
+    FiftyMoveRule,
+    Resignation,
+    Timeout,
+    Agreement,
+    KingOfTheHill,
+    ThreeChecks,
+}
+
+/// The set of rules a game is played under.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum GameVariant {
+    Standard,
+    /// Fischer random chess, which starts from one of 960 shuffled back ranks.
+    Chess960,
+    /// A king reaching one of the four centre squares wins.
+    KingOfTheHill,
+    /// The player who gives the third check wins.
+    ThreeCheck,
+}
+
+impl GameVariant {
+    /// Get the rules of the variant, which the game and engines play by.
+    pub fn get_rules(&self) -> &'static dyn rulebook::Variant {
+        match self {
+            GameVariant::Standard => &rulebook::Standard,
+            GameVariant::Chess960 => &rulebook::Chess960,
+            GameVariant::KingOfTheHill => &rulebook::KingOfTheHill,
+            GameVariant::ThreeCheck => &rulebook::ThreeCheck,
+        }
+    }
+}
+
+/// A draw offered by a player, which their opponent has yet to accept or decline.
+///
+/// The offer lapses once the opponent plays a move.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+pub struct DrawOffer {
+    pub offered_by: chess_set::Colour,
+    /// The number of plies that had been played when the draw was offered.
+    pub ply: usize,
+}
+
+/// A request by a player to take back their last move, which their opponent has yet
+/// to accept or decline.
+///
+/// The request lapses once another move is played.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+pub struct TakebackRequest {
+    pub requested_by: chess_set::Colour,
+    /// The number of plies to take back, so that the requester is to move again.
+    pub plies: usize,
+}
+
+/// What a game's first chessboard doesn't record about the position the game started from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GameStart {
+    pub to_play_colour: chess_set::Colour,
+    /// The number of half moves since the last pawn move or capture.
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
+    /// The checks given before the game started, which count towards winning Three-check.
+    pub checks_given: rulebook::ChecksGiven,
+}
+
+/// A move played in a game, recorded as it is played so that it never needs re-deriving.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayedMove {
+    pub san: String,
+    pub classification: rulebook::MoveClassification,
+}
+
 /// A single game of chess.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Game {
     id: i32,
     status: GameStatus,
+    termination: Option<GameTermination>,
+    draw_offer: Option<DrawOffer>,
+    takeback_request: Option<TakebackRequest>,
+    clock: Option<clock::Clock>,
+    variant: GameVariant,
+    start: GameStart,
+    // The moves played, each leading to the chessboard after it in the history.
+    moves: Vec<PlayedMove>,
+    // The checks given so far, including those given before the game started.
+    checks_given: rulebook::ChecksGiven,
     chessboard_history: Vec<chess_set::Chessboard>,
 }
 
 // Public interface.
 impl Game {
     pub fn new(id: i32) -> Self {
-        let starting_position = rulebook::get_official_starting_position();
-        let chessboard = chess_set::Chessboard::new(starting_position);
+        let chessboard = rulebook::get_official_starting_chessboard();
 
         Self {
             id: id,
             status: GameStatus::ToPlayWhite,
+            termination: None,
+            draw_offer: None,
+            takeback_request: None,
+            clock: None,
+            variant: GameVariant::Standard,
+            start: GameStart::default(),
+            moves: vec![],
+            checks_given: rulebook::ChecksGiven::default(),
             chessboard_history: vec![chessboard],
         }
     }
 
+    /// Start a game of a variant from the position described by a FEN.
+    ///
+    /// The position is judged by the variant's rules, so it may have already been won.
+    pub fn from_position(id: i32, fen: &rulebook::Fen, variant: GameVariant) -> Self {
+        let checks_given = fen.checks_given.unwrap_or_default();
+        let mut game = Self {
+            id,
+            status: GameStatus::from_to_play_colour(fen.to_play_colour),
+            termination: None,
+            draw_offer: None,
+            takeback_request: None,
+            clock: None,
+            variant,
+            start: GameStart {
+                to_play_colour: fen.to_play_colour,
+                halfmove_clock: fen.halfmove_clock,
+                fullmove_number: fen.fullmove_number,
+                checks_given,
+            },
+            moves: vec![],
+            checks_given,
+            chessboard_history: fen.to_chessboard_history(),
+        };
+        game.update_status_for_player_to_move(fen.to_play_colour);
+        game
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn reincarnate(
         id: i32,
         status: GameStatus,
+        termination: Option<GameTermination>,
+        draw_offer: Option<DrawOffer>,
+        takeback_request: Option<TakebackRequest>,
+        clock: Option<clock::Clock>,
+        variant: GameVariant,
+        start: GameStart,
+        moves: Vec<PlayedMove>,
         chessboard_history: Vec<chess_set::Chessboard>,
     ) -> Game {
         Self {
-            id: id,
-            status: status,
-            chessboard_history: chessboard_history,
+            id,
+            status,
+            termination,
+            draw_offer,
+            takeback_request,
+            clock,
+            variant,
+            start,
+            checks_given: tally_checks_given(&start, &moves),
+            moves,
+            chessboard_history,
         }
     }
 
+    /// Copy the game under another id, such as when persisting a game replayed elsewhere.
+    pub fn with_id(&self, id: i32) -> Self {
+        Self { id, ..self.clone() }
+    }
+
     pub fn play_move(
         &mut self,
         player: &chess_set::Colour,
         from_square: &chess_set::Square,
         to_square: &chess_set::Square,
+    ) -> Result<&GameStatus, GameError> {
+        self.play_move_with_promotion(player, from_square, to_square, None)
+    }
+
+    /// Play a move that may promote a pawn, specifying the piece type to promote to.
+    pub fn play_move_with_promotion(
+        &mut self,
+        player: &chess_set::Colour,
+        from_square: &chess_set::Square,
+        to_square: &chess_set::Square,
+        promote_to: Option<chess_set::PieceType>,
     ) -> Result<&GameStatus, GameError> {
         if let Err(error) = self.check_if_play_is_out_of_turn(player) {
             return Err(error);
@@ -80,17 +268,41 @@ impl Game {
             Err(error) => return Err(error),
         };
 
-        let chess_move = rulebook::Move::new(piece, from_square.clone(), to_square.clone());
+        let chess_move = match promote_to {
+            Some(piece_type) => {
+                rulebook::Move::new_promotion(piece, *from_square, *to_square, piece_type)
+            }
+            None => rulebook::Move::new(piece, *from_square, *to_square),
+        };
 
         match rulebook::would_player_be_left_in_check(&chess_move, &self.chessboard_history) {
             Ok(false) => {}
             Ok(true) => return Err(GameError::MoveWouldLeavePlayerInCheck),
             Err(error) => return Err(GameError::MoveValidationErrorV2(error)),
         };
+        self.check_variant_allows_move(&chess_move)?;
+
+        self.play_validated_move(&chess_move)
+    }
+
+    /// Play a move written in standard algebraic notation, e.g. `Nf3`.
+    pub fn play_san_move(
+        &mut self,
+        player: &chess_set::Colour,
+        san: &str,
+    ) -> Result<&GameStatus, GameError> {
+        self.check_if_play_is_out_of_turn(player)?;
 
+        let Some(chess_move) = rulebook::from_san(san, *player, &self.chessboard_history) else {
+            return Err(GameError::InvalidSan(san.to_string()));
+        };
+        self.check_variant_allows_move(&chess_move)?;
         self.play_validated_move(&chess_move)
     }
 
+    /// Play a move that is known to be legal, stopping the mover's clock if the game is timed.
+    ///
+    /// If the mover has run out of time, the move is not played and the game ends instead.
     pub fn play_validated_move(
         &mut self,
         chess_move: &rulebook::Move,
@@ -99,12 +311,204 @@ impl Game {
             Ok(chessboard) => chessboard,
             Err(error) => return Err(GameError::MoveValidationErrorV2(error)),
         };
+        let classification = match rulebook::classify_move(chess_move, &self.chessboard_history) {
+            Ok(classification) => classification,
+            Err(error) => return Err(GameError::MoveValidationErrorV2(error)),
+        };
+        let played_move = PlayedMove {
+            san: rulebook::to_san(chess_move, &self.chessboard_history),
+            classification,
+        };
+
+        if let Some(clock) = &mut self.clock {
+            if let Err(clock::ClockError::FlagFell(colour)) =
+                clock.punch(*chess_move.piece.get_colour(), clock::now_ms())
+            {
+                self.end_on_timeout(colour);
+                return Ok(&self.status);
+            }
+        }
 
+        if played_move.classification.gives_check {
+            self.checks_given.add_check(chess_move.piece.get_colour());
+        }
+        self.moves.push(played_move);
         self.chessboard_history.push(updated_chessboard);
+        self.lapse_draw_offer(chess_move.piece.get_colour());
+        self.takeback_request = None;
         self.progress_game_status();
         Ok(&self.status)
     }
 
+    /// Claim a draw on behalf of the player to move, when the rules allow it.
+    pub fn claim_draw(&mut self, player: &chess_set::Colour) -> Result<&GameStatus, GameError> {
+        self.check_if_play_is_out_of_turn(player)?;
+
+        let rules = self.variant.get_rules();
+        match rules.get_claimable_draw(*player, self.start.halfmove_clock, &self.chessboard_history)
+        {
+            Some(draw) => {
+                self.status = GameStatus::Drawn;
+                self.termination = Some(GameTermination::from_draw(draw));
+                Ok(&self.status)
+            }
+            None => Err(GameError::NoDrawToClaim),
+        }
+    }
+
+    /// Resign on behalf of a player, awarding the game to their opponent.
+    ///
+    /// A player may resign whether or not it is their turn to move.
+    pub fn resign(&mut self, player: &chess_set::Colour) -> Result<&GameStatus, GameError> {
+        if self.status.to_play_colour().is_none() {
+            return Err(GameError::GameHasAlreadyEnded);
+        }
+
+        self.status = GameStatus::from_winning_colour(player.swap());
+        self.termination = Some(GameTermination::Resignation);
+        self.draw_offer = None;
+        self.takeback_request = None;
+        Ok(&self.status)
+    }
+
+    /// Ask the opponent's consent to take back the player's last move.
+    ///
+    /// If the opponent has replied since, their reply is taken back too.
+    pub fn request_takeback(
+        &mut self,
+        player: &chess_set::Colour,
+    ) -> Result<&GameStatus, GameError> {
+        let Some(to_play_colour) = self.status.to_play_colour() else {
+            return Err(GameError::GameHasAlreadyEnded);
+        };
+        if self.takeback_request.is_some() {
+            return Err(GameError::TakebackAlreadyRequested);
+        }
+
+        let plies = match &to_play_colour == player {
+            true => 2,
+            false => 1,
+        };
+        if plies > self.chessboard_history.len() - 1 {
+            return Err(GameError::NoMovesToTakeBack(*player));
+        }
+
+        self.takeback_request = Some(TakebackRequest {
+            requested_by: *player,
+            plies,
+        });
+        Ok(&self.status)
+    }
+
+    /// Accept the opponent's takeback request on behalf of a player, undoing the moves.
+    pub fn accept_takeback(
+        &mut self,
+        player: &chess_set::Colour,
+    ) -> Result<&GameStatus, GameError> {
+        let takeback_request = self.check_player_can_respond_to_takeback_request(player)?;
+
+        let n_chessboards_kept = self.chessboard_history.len() - takeback_request.plies;
+        self.chessboard_history.truncate(n_chessboards_kept);
+        self.moves.truncate(n_chessboards_kept - 1);
+        self.checks_given = tally_checks_given(&self.start, &self.moves);
+        if let Some(clock) = &mut self.clock {
+            clock.take_back(
+                takeback_request.plies,
+                takeback_request.requested_by,
+                clock::now_ms(),
+            );
+        }
+        self.takeback_request = None;
+        self.draw_offer = None;
+        self.status = GameStatus::from_to_play_colour(takeback_request.requested_by);
+        Ok(&self.status)
+    }
+
+    /// Decline the opponent's takeback request on behalf of a player.
+    pub fn decline_takeback(
+        &mut self,
+        player: &chess_set::Colour,
+    ) -> Result<&GameStatus, GameError> {
+        self.check_player_can_respond_to_takeback_request(player)?;
+
+        self.takeback_request = None;
+        Ok(&self.status)
+    }
+
+    /// Start timing the game, from the current position.
+    pub fn start_clock(&mut self, time_control: clock::TimeControl) -> Result<(), GameError> {
+        if self.status.to_play_colour().is_none() {
+            return Err(GameError::GameHasAlreadyEnded);
+        }
+        if self.clock.is_some() {
+            return Err(GameError::ClockAlreadyStarted);
+        }
+
+        self.clock = Some(clock::Clock::new(time_control, clock::now_ms()));
+        Ok(())
+    }
+
+    /// End the game if the player to move has run out of time.
+    pub fn check_for_timeout(&mut self) -> &GameStatus {
+        let (Some(clock), Some(to_play_colour)) = (&self.clock, self.status.to_play_colour())
+        else {
+            return &self.status;
+        };
+
+        if clock.has_flag_fallen(to_play_colour, clock::now_ms()) {
+            self.end_on_timeout(to_play_colour);
+        }
+        &self.status
+    }
+
+    /// End the game because a player ran out of time on a clock kept elsewhere,
+    /// such as in a game recorded in a PGN file.
+    pub fn forfeit_on_time(
+        &mut self,
+        player: &chess_set::Colour,
+    ) -> Result<&GameStatus, GameError> {
+        if self.status.to_play_colour().is_none() {
+            return Err(GameError::GameHasAlreadyEnded);
+        }
+
+        self.end_on_timeout(*player);
+        Ok(&self.status)
+    }
+
+    /// Offer a draw on behalf of a player, which stands until their opponent moves.
+    pub fn offer_draw(&mut self, player: &chess_set::Colour) -> Result<&GameStatus, GameError> {
+        if self.status.to_play_colour().is_none() {
+            return Err(GameError::GameHasAlreadyEnded);
+        }
+        if self.draw_offer.is_some() {
+            return Err(GameError::DrawAlreadyOffered);
+        }
+
+        self.draw_offer = Some(DrawOffer {
+            offered_by: *player,
+            ply: self.chessboard_history.len() - 1,
+        });
+        Ok(&self.status)
+    }
+
+    /// Accept the opponent's draw offer on behalf of a player, drawing the game.
+    pub fn accept_draw(&mut self, player: &chess_set::Colour) -> Result<&GameStatus, GameError> {
+        self.check_player_can_respond_to_draw_offer(player)?;
+
+        self.draw_offer = None;
+        self.status = GameStatus::Drawn;
+        self.termination = Some(GameTermination::Agreement);
+        Ok(&self.status)
+    }
+
+    /// Decline the opponent's draw offer on behalf of a player.
+    pub fn decline_draw(&mut self, player: &chess_set::Colour) -> Result<&GameStatus, GameError> {
+        self.check_player_can_respond_to_draw_offer(player)?;
+
+        self.draw_offer = None;
+        Ok(&self.status)
+    }
+
     // Queries.
     pub fn get_id(&self) -> &i32 {
         &self.id
@@ -114,6 +518,59 @@ impl Game {
         &self.status
     }
 
+    pub fn get_termination(&self) -> &Option<GameTermination> {
+        &self.termination
+    }
+
+    pub fn get_draw_offer(&self) -> &Option<DrawOffer> {
+        &self.draw_offer
+    }
+
+    pub fn get_takeback_request(&self) -> &Option<TakebackRequest> {
+        &self.takeback_request
+    }
+
+    pub fn get_clock(&self) -> &Option<clock::Clock> {
+        &self.clock
+    }
+
+    pub fn get_variant(&self) -> &GameVariant {
+        &self.variant
+    }
+
+    pub fn get_start(&self) -> &GameStart {
+        &self.start
+    }
+
+    /// How long the player to move can afford to think, if the game is timed.
+    pub fn get_search_time_budget(&self) -> Option<std::time::Duration> {
+        let (Some(clock), Some(to_play_colour)) = (&self.clock, self.status.to_play_colour())
+        else {
+            return None;
+        };
+        Some(clock.get_search_time_budget(to_play_colour, clock::now_ms()))
+    }
+
+    /// Get the checks a player has given so far, which decide Three-check games.
+    pub fn get_checks_given(&self, player: &chess_set::Colour) -> u8 {
+        self.checks_given.get(player)
+    }
+
+    /// Get the moves the player to move can play, under the rules of the game's variant.
+    pub fn get_legal_moves(&self) -> Vec<rulebook::Move> {
+        let Some(to_play_colour) = self.status.to_play_colour() else {
+            return vec![];
+        };
+        self.variant
+            .get_rules()
+            .get_legal_moves(to_play_colour, &self.chessboard_history)
+    }
+
+    /// Get the moves the player to move can play, each with its standard algebraic notation.
+    pub fn get_legal_moves_in_san(&self) -> Vec<(rulebook::Move, String)> {
+        rulebook::write_legal_moves_in_san(&self.get_legal_moves(), &self.chessboard_history)
+    }
+
     pub fn get_chessboard_history(&self) -> &Vec<chess_set::Chessboard> {
         &self.chessboard_history
     }
@@ -121,6 +578,92 @@ impl Game {
     pub fn current_chessboard(&self) -> &chess_set::Chessboard {
         self.chessboard_history.last().unwrap()
     }
+
+    /// The number of half moves since the last pawn move or capture.
+    pub fn get_halfmove_clock(&self) -> u16 {
+        rulebook::get_halfmove_clock(self.start.halfmove_clock, &self.chessboard_history)
+    }
+
+    /// Describe the current position in Forsyth-Edwards Notation.
+    ///
+    /// Three-check positions include the checks given so far.
+    pub fn get_fen(&self) -> rulebook::Fen {
+        let mut fen = rulebook::Fen::from_chessboard_history(
+            self.get_to_play_colour(),
+            self.start.halfmove_clock,
+            self.start.fullmove_number,
+            &self.chessboard_history,
+        );
+        if self.variant == GameVariant::ThreeCheck {
+            fen.checks_given = Some(self.checks_given);
+        };
+        fen
+    }
+
+    /// Describe the position the game started from in Forsyth-Edwards Notation.
+    pub fn get_starting_fen(&self) -> rulebook::Fen {
+        let mut fen = rulebook::Fen::from_chessboard_history(
+            self.start.to_play_colour,
+            self.start.halfmove_clock,
+            self.start.fullmove_number,
+            &self.chessboard_history[..1],
+        );
+        if self.variant == GameVariant::ThreeCheck {
+            fen.checks_given = Some(self.start.checks_given);
+        };
+        fen
+    }
+
+    /// Get the current position, for engines to search from.
+    pub fn get_search_position(&self) -> rulebook::SearchPosition {
+        rulebook::SearchPosition::from_chessboard_history(
+            self.get_to_play_colour(),
+            self.start.halfmove_clock,
+            self.checks_given,
+            &self.chessboard_history,
+        )
+    }
+
+    /// Hash the current position, so that identical positions can be identified.
+    pub fn get_zobrist_hash(&self) -> u64 {
+        rulebook::get_zobrist_hash(self.get_to_play_colour(), &self.chessboard_history)
+    }
+
+    pub fn get_moves(&self) -> &[PlayedMove] {
+        &self.moves
+    }
+
+    /// Get the moves played so far, in standard algebraic notation.
+    pub fn get_moves_in_san(&self) -> Vec<String> {
+        self.moves
+            .iter()
+            .map(|played_move| played_move.san.clone())
+            .collect()
+    }
+
+    /// Get the classification of each move played so far, e.g. whether it was a capture.
+    pub fn get_move_classifications(&self) -> Vec<rulebook::MoveClassification> {
+        self.moves
+            .iter()
+            .map(|played_move| played_move.classification.clone())
+            .collect()
+    }
+
+    /// Get the colour of the player who was to move in the game's starting position.
+    pub fn get_starting_colour(&self) -> chess_set::Colour {
+        self.start.to_play_colour
+    }
+
+    /// Get the colour of the player to move, or who would be to move if the game had not ended.
+    pub fn get_to_play_colour(&self) -> chess_set::Colour {
+        if let Some(colour) = self.status.to_play_colour() {
+            return colour;
+        };
+        match self.chessboard_history.len() % 2 == 1 {
+            true => self.start.to_play_colour,
+            false => self.start.to_play_colour.swap(),
+        }
+    }
 }
 
 // Private interface.
@@ -131,12 +674,46 @@ impl Game {
             panic!("Game should have ended sooner!");
         };
 
-        let to_play_colour = colour.swap();
+        self.update_status_for_player_to_move(colour.swap());
+    }
+
+    /// A player who runs out of time loses, unless their opponent could never win by the variant's rules.
+    fn end_on_timeout(&mut self, flagged_colour: chess_set::Colour) {
+        let opponent = flagged_colour.swap();
+        self.status = match self
+            .variant
+            .get_rules()
+            .can_still_win(opponent, self.current_chessboard())
+        {
+            true => GameStatus::from_winning_colour(opponent),
+            false => GameStatus::Drawn,
+        };
+        self.termination = Some(GameTermination::Timeout);
+        self.draw_offer = None;
+    }
+
+    fn lapse_draw_offer(&mut self, mover: &chess_set::Colour) {
+        if let Some(draw_offer) = self.draw_offer {
+            if !(&draw_offer.offered_by == mover) {
+                self.draw_offer = None;
+            }
+        }
+    }
 
-        if rulebook::is_player_checkmated(to_play_colour, self.get_chessboard_history()) {
-            self.status = GameStatus::from_winning_colour(colour);
-        } else if let Some(_) = rulebook::is_draw(to_play_colour, &self.chessboard_history) {
+    fn update_status_for_player_to_move(&mut self, to_play_colour: chess_set::Colour) {
+        let rules = self.variant.get_rules();
+        if let Some((winner, win)) =
+            rules.get_win(to_play_colour, &self.checks_given, &self.chessboard_history)
+        {
+            self.status = GameStatus::from_winning_colour(winner);
+            self.termination = Some(GameTermination::from_win(win));
+        } else if let Some(draw) = rules.get_draw(
+            to_play_colour,
+            self.start.halfmove_clock,
+            &self.chessboard_history,
+        ) {
             self.status = GameStatus::Drawn;
+            self.termination = Some(GameTermination::from_draw(draw));
         } else {
             self.status = GameStatus::from_to_play_colour(to_play_colour)
         }
@@ -157,6 +734,47 @@ impl Game {
         Ok(())
     }
 
+    fn check_player_can_respond_to_draw_offer(
+        &self,
+        player: &chess_set::Colour,
+    ) -> Result<(), GameError> {
+        if self.status.to_play_colour().is_none() {
+            return Err(GameError::GameHasAlreadyEnded);
+        }
+        let Some(draw_offer) = self.draw_offer else {
+            return Err(GameError::NoDrawOffer);
+        };
+        if &draw_offer.offered_by == player {
+            return Err(GameError::CannotRespondToOwnDrawOffer(*player));
+        }
+        Ok(())
+    }
+
+    fn check_player_can_respond_to_takeback_request(
+        &self,
+        player: &chess_set::Colour,
+    ) -> Result<TakebackRequest, GameError> {
+        if self.status.to_play_colour().is_none() {
+            return Err(GameError::GameHasAlreadyEnded);
+        }
+        let Some(takeback_request) = self.takeback_request else {
+            return Err(GameError::NoTakebackRequest);
+        };
+        if &takeback_request.requested_by == player {
+            return Err(GameError::CannotRespondToOwnTakebackRequest(*player));
+        }
+        Ok(takeback_request)
+    }
+
+    /// Check that a move the standard move rules allow is also legal in the game's variant.
+    fn check_variant_allows_move(&self, chess_move: &rulebook::Move) -> Result<(), GameError> {
+        let rules = self.variant.get_rules();
+        if !rules.allows_move(chess_move, &self.chessboard_history) {
+            return Err(GameError::MoveNotAllowedByVariant);
+        }
+        Ok(())
+    }
+
     fn check_piece_at_square_belongs_to_player(
         &self,
         player: &chess_set::Colour,
@@ -174,6 +792,31 @@ impl Game {
     }
 }
 
+/// Tally the checks given by the start of a game and over the moves played since.
+fn tally_checks_given(start: &GameStart, moves: &[PlayedMove]) -> rulebook::ChecksGiven {
+    let mut checks_given = start.checks_given;
+    let mut mover = start.to_play_colour;
+    for played_move in moves {
+        if played_move.classification.gives_check {
+            checks_given.add_check(&mover);
+        }
+        mover = mover.swap();
+    }
+    checks_given
+}
+
+impl Default for GameStart {
+    /// The start of a game from the official starting position, with white to move.
+    fn default() -> Self {
+        Self {
+            to_play_colour: chess_set::Colour::White,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            checks_given: rulebook::ChecksGiven::default(),
+        }
+    }
+}
+
 impl GameStatus {
     pub fn to_play_colour(&self) -> Option<chess_set::Colour> {
         match self {
@@ -210,6 +853,26 @@ impl GameStatus {
     }
 }
 
+impl GameTermination {
+    fn from_win(win: rulebook::Win) -> Self {
+        match win {
+            rulebook::Win::Checkmate => GameTermination::Checkmate,
+            rulebook::Win::KingOfTheHill => GameTermination::KingOfTheHill,
+            rulebook::Win::ThreeChecks => GameTermination::ThreeChecks,
+        }
+    }
+
+    fn from_draw(draw: rulebook::Draw) -> Self {
+        match draw {
+            rulebook::Draw::InsufficientMaterial => GameTermination::InsufficientMaterial,
+            rulebook::Draw::Stalemate => GameTermination::Stalemate,
+            rulebook::Draw::FiftyMoveRule => GameTermination::FiftyMoveRule,
+            rulebook::Draw::SeventyFiveMoveRule => GameTermination::FiftyMoveRule,
+            rulebook::Draw::Repetition => GameTermination::Repetition,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(test)]
@@ -312,4 +975,958 @@ mod tests {
             assert_eq!(game.get_piece_at_square(&to_square), None);
         }
     }
+
+    #[cfg(test)]
+    mod play_promotion_move_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+        use std::collections::BTreeMap;
+
+        fn game_with_white_pawn_on_seventh_rank() -> Game {
+            let mut starting_position = BTreeMap::new();
+
+            let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+            starting_position.insert(Square::new(Rank::Seven, File::A), white_pawn);
+
+            let white_king = Piece::new(Colour::White, PieceType::King);
+            starting_position.insert(Square::new(Rank::One, File::E), white_king);
+
+            let black_king = Piece::new(Colour::Black, PieceType::King);
+            starting_position.insert(Square::new(Rank::Five, File::H), black_king);
+
+            let chessboard = chess_set::Chessboard::new(starting_position);
+            Game::reincarnate(
+                1,
+                GameStatus::ToPlayWhite,
+                None,
+                None,
+                None,
+                None,
+                GameVariant::Standard,
+                GameStart::default(),
+                vec![],
+                vec![chessboard],
+            )
+        }
+
+        #[test]
+        fn can_promote_pawn_to_knight() {
+            let mut game = game_with_white_pawn_on_seventh_rank();
+
+            let from_square = Square::new(Rank::Seven, File::A);
+            let to_square = Square::new(Rank::Eight, File::A);
+
+            let result = game.play_move_with_promotion(
+                &Colour::White,
+                &from_square,
+                &to_square,
+                Some(PieceType::Knight),
+            );
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayBlack));
+            assert_eq!(game.get_piece_at_square(&from_square), None);
+            let white_knight = Piece::new(Colour::White, PieceType::Knight);
+            assert_eq!(game.get_piece_at_square(&to_square), Some(white_knight));
+        }
+
+        #[test]
+        fn errors_when_pawn_reaches_furthest_rank_without_promotion() {
+            let mut game = game_with_white_pawn_on_seventh_rank();
+
+            let from_square = Square::new(Rank::Seven, File::A);
+            let to_square = Square::new(Rank::Eight, File::A);
+
+            let result = game.play_move(&Colour::White, &from_square, &to_square);
+
+            let expected_error =
+                GameError::MoveValidationErrorV2(rulebook::MoveValidationError::PawnMustBePromoted);
+            assert_eq!(result, Err(expected_error));
+            assert_ne!(game.get_piece_at_square(&from_square), None);
+        }
+
+        #[test]
+        fn errors_when_promoting_piece_other_than_pawn() {
+            let mut game = game_with_white_pawn_on_seventh_rank();
+
+            let from_square = Square::new(Rank::One, File::E);
+            let to_square = Square::new(Rank::Two, File::E);
+
+            let result = game.play_move_with_promotion(
+                &Colour::White,
+                &from_square,
+                &to_square,
+                Some(PieceType::Queen),
+            );
+
+            let expected_error = GameError::MoveValidationErrorV2(
+                rulebook::MoveValidationError::MoveIsNotLegalForPiece,
+            );
+            assert_eq!(result, Err(expected_error));
+        }
+    }
+
+    #[cfg(test)]
+    mod fifty_move_rule_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+        use crate::testing::factories;
+
+        #[test]
+        fn player_to_move_can_claim_fifty_move_draw() {
+            let mut game = factories::game_from_fen("7k/8/8/8/8/8/R7/7K b - - 100 60");
+
+            let result = game.claim_draw(&Colour::Black);
+
+            assert_eq!(result, Ok(&GameStatus::Drawn));
+            assert_eq!(
+                game.get_termination(),
+                &Some(GameTermination::FiftyMoveRule)
+            );
+            assert_eq!(game.get_halfmove_clock(), 100);
+        }
+
+        #[test]
+        fn player_not_to_move_cannot_claim_fifty_move_draw() {
+            let mut game = factories::game_from_fen("7k/8/8/8/8/8/R7/7K b - - 100 60");
+
+            let result = game.claim_draw(&Colour::White);
+
+            assert_eq!(result, Err(GameError::PlayIsOutOfTurn(Colour::White)));
+            assert_eq!(game.get_status(), &GameStatus::ToPlayBlack);
+        }
+
+        #[test]
+        fn cannot_claim_draw_before_fifty_moves() {
+            let mut game = factories::game_from_fen("7k/8/8/8/8/8/R7/7K w - - 99 60");
+
+            let result = game.claim_draw(&Colour::White);
+
+            assert_eq!(result, Err(GameError::NoDrawToClaim));
+            assert_eq!(game.get_status(), &GameStatus::ToPlayWhite);
+        }
+
+        #[test]
+        fn counts_halfmoves_played_before_game_started_from_fen() {
+            let mut game = factories::game_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 40");
+
+            game.play_san_move(&Colour::White, "Ra2").unwrap();
+
+            assert_eq!(game.get_halfmove_clock(), 100);
+            assert_eq!(
+                game.get_fen().to_string(),
+                "4k3/8/8/8/8/8/R7/4K3 b - - 100 40"
+            );
+            let result = game.claim_draw(&Colour::Black);
+            assert_eq!(result, Ok(&GameStatus::Drawn));
+        }
+
+        #[test]
+        fn game_is_drawn_automatically_after_seventy_five_moves() {
+            let mut game = factories::game_from_fen("7k/8/8/8/8/8/R7/7K w - - 149 85");
+
+            let from_square = Square::new(Rank::One, File::H);
+            let to_square = Square::new(Rank::Two, File::H);
+            let result = game.play_move(&Colour::White, &from_square, &to_square);
+
+            assert_eq!(result, Ok(&GameStatus::Drawn));
+            assert_eq!(
+                game.get_termination(),
+                &Some(GameTermination::FiftyMoveRule)
+            );
+            assert_eq!(game.get_halfmove_clock(), 150);
+        }
+    }
+
+    #[cfg(test)]
+    mod repetition_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+
+        fn shuffle_knights(game: &mut Game) {
+            let g1 = Square::new(Rank::One, File::G);
+            let f3 = Square::new(Rank::Three, File::F);
+            let g8 = Square::new(Rank::Eight, File::G);
+            let f6 = Square::new(Rank::Six, File::F);
+
+            let _ = game.play_move(&Colour::White, &g1, &f3).unwrap();
+            let _ = game.play_move(&Colour::Black, &g8, &f6).unwrap();
+            let _ = game.play_move(&Colour::White, &f3, &g1).unwrap();
+            let _ = game.play_move(&Colour::Black, &f6, &g8).unwrap();
+        }
+
+        #[test]
+        fn player_to_move_can_claim_threefold_repetition() {
+            let mut game = Game::new(1);
+            shuffle_knights(&mut game);
+            shuffle_knights(&mut game);
+
+            let result = game.claim_draw(&Colour::White);
+
+            assert_eq!(result, Ok(&GameStatus::Drawn));
+            assert_eq!(game.get_termination(), &Some(GameTermination::Repetition));
+        }
+
+        #[test]
+        fn cannot_claim_twofold_repetition() {
+            let mut game = Game::new(1);
+            shuffle_knights(&mut game);
+
+            let result = game.claim_draw(&Colour::White);
+
+            assert_eq!(result, Err(GameError::NoDrawToClaim));
+        }
+
+        #[test]
+        fn game_is_drawn_automatically_after_fivefold_repetition() {
+            let mut game = Game::new(1);
+            for _ in 0..3 {
+                shuffle_knights(&mut game);
+            }
+            assert_eq!(game.get_status(), &GameStatus::ToPlayWhite);
+
+            shuffle_knights(&mut game);
+
+            assert_eq!(game.get_status(), &GameStatus::Drawn);
+            assert_eq!(game.get_termination(), &Some(GameTermination::Repetition));
+        }
+    }
+
+    #[cfg(test)]
+    mod resignation_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+        use rstest::rstest;
+
+        #[rstest]
+        #[case::white_resigns(Colour::White, GameStatus::WonByBlack)]
+        #[case::black_resigns(Colour::Black, GameStatus::WonByWhite)]
+        fn resigning_awards_game_to_opponent(
+            #[case] player: Colour,
+            #[case] expected_status: GameStatus,
+        ) {
+            let mut game = Game::new(1);
+
+            let result = game.resign(&player);
+
+            assert_eq!(result, Ok(&expected_status));
+            assert_eq!(game.get_termination(), &Some(GameTermination::Resignation));
+        }
+
+        #[test]
+        fn resigning_clears_draw_offer_and_takeback_request() {
+            let mut game = Game::new(1);
+            let e2 = Square::new(Rank::Two, File::E);
+            let e4 = Square::new(Rank::Four, File::E);
+            game.play_move(&Colour::White, &e2, &e4).unwrap();
+            game.offer_draw(&Colour::White).unwrap();
+            game.request_takeback(&Colour::White).unwrap();
+
+            game.resign(&Colour::Black).unwrap();
+
+            assert_eq!(game.get_draw_offer(), &None);
+            assert_eq!(game.get_takeback_request(), &None);
+        }
+
+        #[test]
+        fn cannot_resign_once_game_has_ended() {
+            let mut game = Game::reincarnate(
+                1,
+                GameStatus::Drawn,
+                Some(GameTermination::Stalemate),
+                None,
+                None,
+                None,
+                GameVariant::Standard,
+                GameStart::default(),
+                vec![],
+                vec![],
+            );
+
+            let result = game.resign(&Colour::White);
+
+            assert_eq!(result, Err(GameError::GameHasAlreadyEnded));
+            assert_eq!(game.get_status(), &GameStatus::Drawn);
+            assert_eq!(game.get_termination(), &Some(GameTermination::Stalemate));
+        }
+    }
+
+    #[cfg(test)]
+    mod draw_offer_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+
+        fn play_knight_out(game: &mut Game, player: Colour) {
+            let (from_square, to_square) = match player {
+                Colour::White => (
+                    Square::new(Rank::One, File::G),
+                    Square::new(Rank::Three, File::F),
+                ),
+                Colour::Black => (
+                    Square::new(Rank::Eight, File::G),
+                    Square::new(Rank::Six, File::F),
+                ),
+            };
+            let _ = game.play_move(&player, &from_square, &to_square).unwrap();
+        }
+
+        #[test]
+        fn opponent_can_accept_draw_offer() {
+            let mut game = Game::new(1);
+            play_knight_out(&mut game, Colour::White);
+            game.offer_draw(&Colour::White).unwrap();
+
+            let expected_offer = DrawOffer {
+                offered_by: Colour::White,
+                ply: 1,
+            };
+            assert_eq!(game.get_draw_offer(), &Some(expected_offer));
+
+            let result = game.accept_draw(&Colour::Black);
+
+            assert_eq!(result, Ok(&GameStatus::Drawn));
+            assert_eq!(game.get_termination(), &Some(GameTermination::Agreement));
+            assert_eq!(game.get_draw_offer(), &None);
+        }
+
+        #[test]
+        fn opponent_can_decline_draw_offer() {
+            let mut game = Game::new(1);
+            game.offer_draw(&Colour::White).unwrap();
+
+            let result = game.decline_draw(&Colour::Black);
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayWhite));
+            assert_eq!(game.get_draw_offer(), &None);
+        }
+
+        #[test]
+        fn player_cannot_accept_own_draw_offer() {
+            let mut game = Game::new(1);
+            game.offer_draw(&Colour::White).unwrap();
+
+            let result = game.accept_draw(&Colour::White);
+
+            assert_eq!(
+                result,
+                Err(GameError::CannotRespondToOwnDrawOffer(Colour::White))
+            );
+            assert_eq!(game.get_status(), &GameStatus::ToPlayWhite);
+        }
+
+        #[test]
+        fn cannot_respond_when_no_draw_is_offered() {
+            let mut game = Game::new(1);
+
+            assert_eq!(
+                game.accept_draw(&Colour::Black),
+                Err(GameError::NoDrawOffer)
+            );
+            assert_eq!(
+                game.decline_draw(&Colour::Black),
+                Err(GameError::NoDrawOffer)
+            );
+        }
+
+        #[test]
+        fn cannot_offer_draw_twice() {
+            let mut game = Game::new(1);
+            game.offer_draw(&Colour::White).unwrap();
+
+            let result = game.offer_draw(&Colour::Black);
+
+            assert_eq!(result, Err(GameError::DrawAlreadyOffered));
+        }
+
+        #[test]
+        fn draw_offer_stands_while_offerer_moves() {
+            let mut game = Game::new(1);
+            game.offer_draw(&Colour::White).unwrap();
+
+            play_knight_out(&mut game, Colour::White);
+
+            let result = game.accept_draw(&Colour::Black);
+
+            assert_eq!(result, Ok(&GameStatus::Drawn));
+        }
+
+        #[test]
+        fn draw_offer_lapses_once_opponent_moves() {
+            let mut game = Game::new(1);
+            play_knight_out(&mut game, Colour::White);
+            game.offer_draw(&Colour::White).unwrap();
+
+            play_knight_out(&mut game, Colour::Black);
+
+            assert_eq!(game.get_draw_offer(), &None);
+            assert_eq!(
+                game.accept_draw(&Colour::Black),
+                Err(GameError::NoDrawOffer)
+            );
+        }
+
+        #[test]
+        fn cannot_offer_draw_once_game_has_ended() {
+            let mut game = Game::new(1);
+            game.resign(&Colour::White).unwrap();
+
+            let result = game.offer_draw(&Colour::Black);
+
+            assert_eq!(result, Err(GameError::GameHasAlreadyEnded));
+        }
+    }
+
+    #[cfg(test)]
+    mod takeback_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+
+        fn play_opening_moves(game: &mut Game) {
+            let e2 = Square::new(Rank::Two, File::E);
+            let e4 = Square::new(Rank::Four, File::E);
+            let e7 = Square::new(Rank::Seven, File::E);
+            let e5 = Square::new(Rank::Five, File::E);
+
+            let _ = game.play_move(&Colour::White, &e2, &e4).unwrap();
+            let _ = game.play_move(&Colour::Black, &e7, &e5).unwrap();
+        }
+
+        #[test]
+        fn takes_back_own_move_when_opponent_has_not_replied() {
+            let mut game = Game::new(1);
+            play_opening_moves(&mut game);
+            let expected_history = game.get_chessboard_history()[..2].to_vec();
+
+            game.request_takeback(&Colour::Black).unwrap();
+            let result = game.accept_takeback(&Colour::White);
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayBlack));
+            assert_eq!(game.get_chessboard_history(), &expected_history);
+            assert_eq!(game.get_moves_in_san(), vec!["e4"]);
+            assert_eq!(game.get_takeback_request(), &None);
+        }
+
+        #[test]
+        fn takes_back_opponents_reply_too() {
+            let mut game = Game::new(1);
+            play_opening_moves(&mut game);
+
+            game.request_takeback(&Colour::White).unwrap();
+            let expected_request = TakebackRequest {
+                requested_by: Colour::White,
+                plies: 2,
+            };
+            assert_eq!(game.get_takeback_request(), &Some(expected_request));
+
+            let result = game.accept_takeback(&Colour::Black);
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayWhite));
+            assert_eq!(game.get_chessboard_history().len(), 1);
+            assert_eq!(game.get_moves_in_san(), Vec::<String>::new());
+        }
+
+        #[test]
+        fn declined_takeback_keeps_moves() {
+            let mut game = Game::new(1);
+            play_opening_moves(&mut game);
+            game.request_takeback(&Colour::Black).unwrap();
+
+            let result = game.decline_takeback(&Colour::White);
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayWhite));
+            assert_eq!(game.get_chessboard_history().len(), 3);
+            assert_eq!(game.get_takeback_request(), &None);
+        }
+
+        #[test]
+        fn player_cannot_accept_own_takeback_request() {
+            let mut game = Game::new(1);
+            play_opening_moves(&mut game);
+            game.request_takeback(&Colour::Black).unwrap();
+
+            let result = game.accept_takeback(&Colour::Black);
+
+            assert_eq!(
+                result,
+                Err(GameError::CannotRespondToOwnTakebackRequest(Colour::Black))
+            );
+            assert_eq!(game.get_chessboard_history().len(), 3);
+        }
+
+        #[test]
+        fn cannot_take_back_before_any_moves() {
+            let mut game = Game::new(1);
+
+            let result = game.request_takeback(&Colour::White);
+
+            assert_eq!(result, Err(GameError::NoMovesToTakeBack(Colour::White)));
+        }
+
+        #[test]
+        fn takeback_request_lapses_once_a_move_is_played() {
+            let mut game = Game::new(1);
+            play_opening_moves(&mut game);
+            game.request_takeback(&Colour::Black).unwrap();
+
+            let g1 = Square::new(Rank::One, File::G);
+            let f3 = Square::new(Rank::Three, File::F);
+            let _ = game.play_move(&Colour::White, &g1, &f3).unwrap();
+
+            assert_eq!(game.get_takeback_request(), &None);
+            assert_eq!(
+                game.accept_takeback(&Colour::White),
+                Err(GameError::NoTakebackRequest)
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod clock_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+        use crate::domain::rulebook::Fen;
+
+        const TIME_CONTROL: clock::TimeControl = clock::TimeControl::Fischer {
+            base_ms: 60_000,
+            increment_ms: 1_000,
+        };
+
+        /// A clock whose current turn started at the Unix epoch, so the player to move has
+        /// long since run out of time.
+        fn expired_clock() -> clock::Clock {
+            clock::Clock::reincarnate(TIME_CONTROL, 60_000, 60_000, 0, vec![])
+        }
+
+        fn game_from_fen(fen: &str, game_clock: clock::Clock, variant: GameVariant) -> Game {
+            let fen = Fen::parse(fen).unwrap();
+            let status = GameStatus::from_to_play_colour(fen.to_play_colour);
+            Game::reincarnate(
+                1,
+                status,
+                None,
+                None,
+                None,
+                Some(game_clock),
+                variant,
+                GameStart::default(),
+                vec![],
+                fen.to_chessboard_history(),
+            )
+        }
+
+        #[test]
+        fn playing_move_records_remaining_time() {
+            let mut game = Game::new(1);
+            game.start_clock(TIME_CONTROL).unwrap();
+
+            let from_square = Square::new(Rank::Two, File::E);
+            let to_square = Square::new(Rank::Four, File::E);
+            game.play_move(&Colour::White, &from_square, &to_square)
+                .unwrap();
+
+            let game_clock = game.get_clock().as_ref().unwrap();
+            assert_eq!(game_clock.get_move_remaining_ms().len(), 1);
+            assert!(game_clock.get_remaining_ms(Colour::White) > 60_000);
+            assert_eq!(game_clock.get_remaining_ms(Colour::Black), 60_000);
+        }
+
+        #[test]
+        fn cannot_start_clock_twice() {
+            let mut game = Game::new(1);
+            game.start_clock(TIME_CONTROL).unwrap();
+
+            let result = game.start_clock(TIME_CONTROL);
+
+            assert_eq!(result, Err(GameError::ClockAlreadyStarted));
+        }
+
+        #[test]
+        fn player_loses_when_flag_falls_during_move() {
+            let mut game = game_from_fen(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                expired_clock(),
+                GameVariant::Standard,
+            );
+
+            let from_square = Square::new(Rank::Two, File::E);
+            let to_square = Square::new(Rank::Four, File::E);
+            let result = game.play_move(&Colour::White, &from_square, &to_square);
+
+            assert_eq!(result, Ok(&GameStatus::WonByBlack));
+            assert_eq!(game.get_termination(), &Some(GameTermination::Timeout));
+            assert_eq!(game.get_chessboard_history().len(), 1);
+        }
+
+        #[test]
+        fn timeout_against_insufficient_mating_material_is_drawn() {
+            let mut game = game_from_fen(
+                "4k3/8/8/8/8/8/8/2n1K3 w - - 0 1",
+                expired_clock(),
+                GameVariant::Standard,
+            );
+
+            let result = game.check_for_timeout();
+
+            assert_eq!(result, &GameStatus::Drawn);
+            assert_eq!(game.get_termination(), &Some(GameTermination::Timeout));
+        }
+
+        #[test]
+        fn timeout_against_knight_is_lost_when_own_pawn_could_hem_king_in() {
+            let mut game = game_from_fen(
+                "4k3/8/8/8/8/8/4P3/2n1K3 w - - 0 1",
+                expired_clock(),
+                GameVariant::Standard,
+            );
+
+            let result = game.check_for_timeout();
+
+            assert_eq!(result, &GameStatus::WonByBlack);
+            assert_eq!(game.get_termination(), &Some(GameTermination::Timeout));
+        }
+
+        #[test]
+        fn timeout_against_lone_king_is_lost_in_king_of_the_hill() {
+            let mut game = game_from_fen(
+                "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+                expired_clock(),
+                GameVariant::KingOfTheHill,
+            );
+
+            let result = game.check_for_timeout();
+
+            assert_eq!(result, &GameStatus::WonByBlack);
+            assert_eq!(game.get_termination(), &Some(GameTermination::Timeout));
+        }
+
+        #[test]
+        fn game_continues_while_player_has_time() {
+            let mut game = Game::new(1);
+            game.start_clock(TIME_CONTROL).unwrap();
+
+            let result = game.check_for_timeout();
+
+            assert_eq!(result, &GameStatus::ToPlayWhite);
+            assert_eq!(game.get_termination(), &None);
+        }
+
+        #[test]
+        fn player_can_forfeit_on_time_without_clock() {
+            let mut game = Game::new(1);
+
+            let result = game.forfeit_on_time(&Colour::White);
+
+            assert_eq!(result, Ok(&GameStatus::WonByBlack));
+            assert_eq!(game.get_termination(), &Some(GameTermination::Timeout));
+        }
+
+        #[test]
+        fn cannot_forfeit_on_time_once_game_has_ended() {
+            let mut game = Game::new(1);
+            game.resign(&Colour::Black).unwrap();
+
+            let result = game.forfeit_on_time(&Colour::White);
+
+            assert_eq!(result, Err(GameError::GameHasAlreadyEnded));
+        }
+    }
+
+    #[cfg(test)]
+    mod fen_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+        use crate::testing::factories;
+
+        #[test]
+        fn new_game_has_starting_fen() {
+            let game = Game::new(1);
+
+            assert_eq!(
+                game.get_fen().to_string(),
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+            );
+        }
+
+        #[test]
+        fn can_capture_en_passant_in_game_started_from_fen() {
+            let mut game = factories::game_from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+
+            let from_square = Square::new(Rank::Five, File::E);
+            let to_square = Square::new(Rank::Six, File::D);
+            let result = game.play_move(&Colour::White, &from_square, &to_square);
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayBlack));
+            let captured_pawn_square = Square::new(Rank::Five, File::D);
+            assert_eq!(game.get_piece_at_square(&captured_pawn_square), None);
+        }
+
+        #[test]
+        fn drawn_game_started_with_black_to_move_knows_who_is_to_move() {
+            let mut game = factories::game_from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 0 1");
+            game.play_san_move(&Colour::Black, "Kd7").unwrap();
+            game.offer_draw(&Colour::Black).unwrap();
+            game.accept_draw(&Colour::White).unwrap();
+
+            assert_eq!(game.get_starting_colour(), Colour::Black);
+            assert_eq!(game.get_to_play_colour(), Colour::White);
+            assert_eq!(game.get_fen().to_string(), "8/3k4/8/8/8/8/8/R3K3 w - - 1 2");
+            assert_eq!(game.get_moves_in_san(), vec!["Kd7"]);
+        }
+
+        #[test]
+        fn game_started_from_stalemate_is_drawn() {
+            let game = factories::game_from_fen("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1");
+
+            assert_eq!(game.get_status(), &GameStatus::Drawn);
+            assert_eq!(game.get_termination(), &Some(GameTermination::Stalemate));
+        }
+    }
+
+    #[cfg(test)]
+    mod variant_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+        use crate::testing::factories;
+        use rstest::rstest;
+
+        #[test]
+        fn new_game_is_standard_chess() {
+            let game = Game::new(1);
+
+            assert_eq!(game.get_variant(), &GameVariant::Standard);
+            assert_eq!(game.get_legal_moves().len(), 20);
+        }
+
+        #[test]
+        fn each_variant_starts_from_its_own_starting_positions() {
+            let standard_rules = GameVariant::Standard.get_rules();
+            let chess960_rules = GameVariant::Chess960.get_rules();
+
+            assert_eq!(standard_rules.get_starting_position_count(), 1);
+            assert_eq!(chess960_rules.get_starting_position_count(), 960);
+        }
+
+        #[test]
+        fn chess960_game_can_castle_onto_adjacent_rook() {
+            let mut game = factories::variant_game_from_fen(
+                "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1",
+                GameVariant::Chess960,
+            );
+
+            let f1 = Square::new(Rank::One, File::F);
+            let g1 = Square::new(Rank::One, File::G);
+            let result = game.play_move(&Colour::White, &f1, &g1);
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayBlack));
+            assert_eq!(game.get_moves_in_san(), vec!["O-O+"]);
+        }
+
+        #[test]
+        fn king_of_the_hill_game_is_won_by_reaching_the_centre() {
+            let mut game = factories::variant_game_from_fen(
+                "4k3/7p/8/8/8/4K3/7P/8 w - - 0 1",
+                GameVariant::KingOfTheHill,
+            );
+
+            let e3 = Square::new(Rank::Three, File::E);
+            let e4 = Square::new(Rank::Four, File::E);
+            let result = game.play_move(&Colour::White, &e3, &e4);
+
+            assert_eq!(result, Ok(&GameStatus::WonByWhite));
+            assert_eq!(
+                game.get_termination(),
+                &Some(GameTermination::KingOfTheHill)
+            );
+        }
+
+        #[rstest]
+        #[case::king_of_the_hill(
+            "4k3/8/8/8/4K3/8/8/8 b - - 0 1",
+            GameVariant::KingOfTheHill,
+            GameTermination::KingOfTheHill
+        )]
+        #[case::three_check(
+            "4k3/8/8/8/8/8/8/4K3 b - - 0 1 +3+0",
+            GameVariant::ThreeCheck,
+            GameTermination::ThreeChecks
+        )]
+        fn game_started_from_won_position_of_variant_has_ended(
+            #[case] fen: &str,
+            #[case] variant: GameVariant,
+            #[case] expected_termination: GameTermination,
+        ) {
+            let game = factories::variant_game_from_fen(fen, variant);
+
+            assert_eq!(game.get_status(), &GameStatus::WonByWhite);
+            assert_eq!(game.get_termination(), &Some(expected_termination));
+        }
+
+        #[test]
+        fn three_check_game_is_won_by_the_third_check() {
+            let mut game = factories::variant_game_from_fen(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                GameVariant::ThreeCheck,
+            );
+
+            let moves = [
+                (Colour::White, "e4"),
+                (Colour::Black, "e5"),
+                (Colour::White, "Bc4"),
+                (Colour::Black, "a6"),
+                (Colour::White, "Bxf7+"),
+                (Colour::Black, "Kxf7"),
+                (Colour::White, "Qh5+"),
+                (Colour::Black, "g6"),
+            ];
+            for (player, san) in moves {
+                game.play_san_move(&player, san).unwrap();
+            }
+            assert_eq!(game.get_checks_given(&Colour::White), 2);
+            assert_eq!(game.get_checks_given(&Colour::Black), 0);
+
+            let result = game.play_san_move(&Colour::White, "Qf3+");
+
+            assert_eq!(result, Ok(&GameStatus::WonByWhite));
+            assert_eq!(game.get_termination(), &Some(GameTermination::ThreeChecks));
+            assert_eq!(game.get_checks_given(&Colour::White), 3);
+        }
+
+        #[test]
+        fn checks_given_before_three_check_game_started_count() {
+            let fen = rulebook::Fen::parse("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +2+1").unwrap();
+            let mut game = Game::from_position(1, &fen, GameVariant::ThreeCheck);
+
+            let result = game.play_san_move(&Colour::White, "Ra8+");
+
+            assert_eq!(result, Ok(&GameStatus::WonByWhite));
+            assert_eq!(game.get_termination(), &Some(GameTermination::ThreeChecks));
+            assert_eq!(
+                game.get_fen().to_string(),
+                "R3k3/8/8/8/8/8/8/4K3 b - - 1 1 +3+1"
+            );
+        }
+
+        #[test]
+        fn taken_back_checks_are_no_longer_counted() {
+            let fen = rulebook::Fen::parse("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +1+0").unwrap();
+            let mut game = Game::from_position(1, &fen, GameVariant::ThreeCheck);
+            game.play_san_move(&Colour::White, "Ra8+").unwrap();
+            assert_eq!(game.get_checks_given(&Colour::White), 2);
+
+            game.request_takeback(&Colour::White).unwrap();
+            game.accept_takeback(&Colour::Black).unwrap();
+
+            assert_eq!(game.get_checks_given(&Colour::White), 1);
+        }
+
+        #[test]
+        fn gets_legal_moves_in_san_under_the_variant() {
+            let game = factories::variant_game_from_fen(
+                "4k3/8/8/8/8/8/5K2/R6R w - - 0 1",
+                GameVariant::KingOfTheHill,
+            );
+
+            let legal_moves = game.get_legal_moves_in_san();
+
+            assert_eq!(legal_moves.len(), game.get_legal_moves().len());
+            let sans: Vec<&str> = legal_moves.iter().map(|(_, san)| san.as_str()).collect();
+            assert!(sans.contains(&"Rad1"));
+            assert!(sans.contains(&"Rhe1+"));
+        }
+
+        #[test]
+        fn ended_game_has_no_legal_moves() {
+            let game = factories::game_from_fen("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1");
+
+            assert!(game.get_legal_moves().is_empty());
+        }
+    }
+
+    #[cfg(test)]
+    mod san_tests {
+        use super::super::*;
+        use crate::domain::chess_set::{Colour, File, Rank, Square};
+        use crate::testing::factories;
+
+        #[test]
+        fn can_play_move_in_san() {
+            let mut game = Game::new(1);
+
+            let result = game.play_san_move(&Colour::White, "Nf3");
+
+            assert_eq!(result, Ok(&GameStatus::ToPlayBlack));
+            let knight_square = Square::new(Rank::Three, File::F);
+            assert!(game.get_piece_at_square(&knight_square).is_some());
+        }
+
+        #[test]
+        fn errors_for_illegal_move_in_san() {
+            let mut game = Game::new(1);
+
+            let result = game.play_san_move(&Colour::White, "Nf4");
+
+            assert_eq!(result, Err(GameError::InvalidSan("Nf4".to_string())));
+        }
+
+        #[test]
+        fn errors_for_move_in_san_played_out_of_turn() {
+            let mut game = Game::new(1);
+
+            let result = game.play_san_move(&Colour::Black, "e5");
+
+            assert_eq!(result, Err(GameError::PlayIsOutOfTurn(Colour::Black)));
+        }
+
+        #[test]
+        fn gets_moves_played_in_san() {
+            let mut game = Game::new(1);
+            for (player, san) in [
+                (Colour::White, "f3"),
+                (Colour::Black, "e5"),
+                (Colour::White, "g4"),
+                (Colour::Black, "Qh4"),
+            ] {
+                game.play_san_move(&player, san).unwrap();
+            }
+
+            assert_eq!(game.get_moves_in_san(), vec!["f3", "e5", "g4", "Qh4#"]);
+        }
+
+        #[test]
+        fn gets_moves_in_san_for_game_started_with_black_to_move() {
+            let mut game = factories::game_from_fen("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1");
+            game.play_san_move(&Colour::Black, "Kd7").unwrap();
+
+            assert_eq!(game.get_starting_colour(), Colour::Black);
+            assert_eq!(game.get_moves_in_san(), vec!["Kd7"]);
+        }
+
+        #[test]
+        fn gets_classifications_of_moves_played() {
+            let mut game = Game::new(1);
+            for (player, san) in [
+                (Colour::White, "f3"),
+                (Colour::Black, "e5"),
+                (Colour::White, "g4"),
+                (Colour::Black, "Qh4"),
+            ] {
+                game.play_san_move(&player, san).unwrap();
+            }
+
+            let kinds: Vec<rulebook::MoveKind> = game
+                .get_move_classifications()
+                .iter()
+                .map(|classification| classification.kind)
+                .collect();
+            assert_eq!(
+                kinds,
+                vec![
+                    rulebook::MoveKind::Quiet,
+                    rulebook::MoveKind::DoublePush,
+                    rulebook::MoveKind::DoublePush,
+                    rulebook::MoveKind::Quiet,
+                ]
+            );
+            assert!(game.get_move_classifications()[3].gives_checkmate);
+        }
+    }
 }
@@ -1,4 +1,6 @@
 pub mod chess_set;
+pub mod clock;
 pub mod engine;
 pub mod game;
+pub mod pgn;
 pub mod rulebook;
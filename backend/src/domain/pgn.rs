@@ -0,0 +1,676 @@
+use crate::domain::{chess_set, game, rulebook};
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+use thiserror;
+
+/// The recommended maximum length of a line of movetext.
+const MAX_LINE_LENGTH: usize = 80;
+
+/// The tokens that end the movetext of a game.
+const RESULT_TOKENS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+/// The variants that are recorded with a `Variant` tag.
+const NAMED_VARIANTS: [game::GameVariant; 3] = [
+    game::GameVariant::Chess960,
+    game::GameVariant::KingOfTheHill,
+    game::GameVariant::ThreeCheck,
+];
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum PgnError {
+    #[error("'{0}' is not a valid tag pair.")]
+    InvalidTag(String),
+
+    #[error("A comment was not closed.")]
+    UnterminatedComment,
+
+    #[error("A variation was not closed.")]
+    UnterminatedVariation,
+
+    #[error("The movetext does not end with a result.")]
+    MissingResult,
+
+    #[error("{0}")]
+    InvalidFen(rulebook::FenError),
+}
+
+/// A game record in Portable Game Notation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pgn {
+    pub tags: Vec<(String, String)>,
+    pub starting_colour: chess_set::Colour,
+    pub starting_fullmove_number: u16,
+    pub moves: Vec<String>,
+    pub result: String,
+}
+
+impl Pgn {
+    // Factories.
+
+    /// Record a game, with its moves in standard algebraic notation.
+    ///
+    /// Games that did not start from the official starting position include
+    /// the `SetUp` and `FEN` tags, and games of other variants include the `Variant` tag.
+    pub fn from_game(game: &game::Game) -> Self {
+        let chessboard_history = game.get_chessboard_history();
+        let starting_colour = game.get_starting_colour();
+        let result = get_result(game.get_status());
+
+        let mut tags = vec![
+            ("Event".to_string(), "?".to_string()),
+            ("Site".to_string(), "?".to_string()),
+            ("Date".to_string(), "????.??.??".to_string()),
+            ("Round".to_string(), "?".to_string()),
+            ("White".to_string(), "?".to_string()),
+            ("Black".to_string(), "?".to_string()),
+            ("Result".to_string(), result.clone()),
+        ];
+        if let Some(variant_name) = get_variant_name(game.get_variant()) {
+            tags.push(("Variant".to_string(), variant_name.to_string()));
+        };
+        let starting_chessboard = &chessboard_history[0];
+        if !(starting_chessboard == &rulebook::get_official_starting_chessboard()) {
+            let fen = game.get_starting_fen();
+            tags.push(("SetUp".to_string(), "1".to_string()));
+            tags.push(("FEN".to_string(), fen.to_string()));
+        };
+
+        let moves = game.get_moves_in_san();
+
+        Self {
+            tags,
+            starting_colour,
+            starting_fullmove_number: game.get_start().fullmove_number,
+            moves,
+            result,
+        }
+    }
+
+    /// Parse every game in a PGN file.
+    ///
+    /// Each game is parsed independently, so that one invalid game doesn't prevent
+    /// the others from being read. Comments, NAGs and variations are skipped.
+    pub fn parse_all(text: &str) -> Vec<Result<Self, PgnError>> {
+        let mut games = vec![];
+        let mut builder = PgnBuilder::default();
+
+        for token in tokenize(text) {
+            match token {
+                Ok(Token::TagPair(name, value)) => {
+                    if !builder.moves.is_empty() {
+                        games.push(Err(PgnError::MissingResult));
+                        builder = PgnBuilder::default();
+                    };
+                    builder.tags.push((name, value));
+                }
+                Ok(Token::Move(san)) => builder.moves.push(san),
+                Ok(Token::Result(result)) => {
+                    games.push(builder.build(result));
+                    builder = PgnBuilder::default();
+                }
+                Err(error) => {
+                    builder.error.get_or_insert(error);
+                }
+            }
+        }
+
+        if !builder.is_empty() {
+            games.push(Err(builder.error.unwrap_or(PgnError::MissingResult)));
+        };
+        games
+    }
+
+    // Queries.
+
+    /// Get the position the game started from, if not the official starting position.
+    pub fn get_setup(&self) -> Result<Option<rulebook::Fen>, PgnError> {
+        let Some((_, fen)) = self.tags.iter().find(|(name, _)| name == "FEN") else {
+            return Ok(None);
+        };
+        match rulebook::Fen::parse(fen) {
+            Ok(fen) => Ok(Some(fen)),
+            Err(error) => Err(PgnError::InvalidFen(error)),
+        }
+    }
+
+    /// Get how the game ended, such as "Time forfeit", from its `Termination` tag.
+    pub fn get_termination(&self) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(name, _)| name == "Termination")
+            .map(|(_, termination)| termination.as_str())
+    }
+
+    /// Get the rules the game was played under, from its `Variant` tag.
+    pub fn get_variant(&self) -> game::GameVariant {
+        let Some((_, variant_name)) = self.tags.iter().find(|(name, _)| name == "Variant") else {
+            return game::GameVariant::Standard;
+        };
+        NAMED_VARIANTS
+            .into_iter()
+            .find(|variant| {
+                get_variant_name(variant)
+                    .is_some_and(|name| name.eq_ignore_ascii_case(variant_name))
+            })
+            .unwrap_or(game::GameVariant::Standard)
+    }
+
+    /// Write the movetext, numbering each of white's moves.
+    fn get_movetext_tokens(&self) -> Vec<String> {
+        let mut tokens = vec![];
+        let mut player = self.starting_colour;
+        let mut move_number = self.starting_fullmove_number;
+        for (index, san) in self.moves.iter().enumerate() {
+            match player {
+                chess_set::Colour::White => tokens.push(format!("{}.", move_number)),
+                chess_set::Colour::Black if index == 0 => {
+                    tokens.push(format!("{}...", move_number))
+                }
+                chess_set::Colour::Black => {}
+            };
+            tokens.push(san.clone());
+
+            if player == chess_set::Colour::Black {
+                move_number += 1;
+            };
+            player = player.swap();
+        }
+        tokens.push(self.result.clone());
+        tokens
+    }
+}
+
+/// The parts of a game collected while its tokens are being read.
+#[derive(Default)]
+struct PgnBuilder {
+    tags: Vec<(String, String)>,
+    moves: Vec<String>,
+    error: Option<PgnError>,
+}
+
+impl PgnBuilder {
+    fn build(self, result: String) -> Result<Pgn, PgnError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        };
+
+        let mut pgn = Pgn {
+            tags: self.tags,
+            starting_colour: chess_set::Colour::White,
+            starting_fullmove_number: 1,
+            moves: self.moves,
+            result,
+        };
+        if let Some(fen) = pgn.get_setup()? {
+            pgn.starting_colour = fen.to_play_colour;
+            pgn.starting_fullmove_number = fen.fullmove_number;
+        };
+        Ok(pgn)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.moves.is_empty() && self.error.is_none()
+    }
+}
+
+enum Token {
+    TagPair(String, String),
+    Move(String),
+    Result(String),
+}
+
+/// Split a PGN file into tag pairs, moves and results.
+fn tokenize(text: &str) -> Vec<Result<Token, PgnError>> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    let mut is_line_start = true;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => {
+                is_line_start = true;
+                continue;
+            }
+            // Lines starting with `%` are reserved for use by other software.
+            '%' if is_line_start => skip_line(&mut chars),
+            ';' => skip_line(&mut chars),
+            '{' => {
+                if !skip_comment(&mut chars) {
+                    tokens.push(Err(PgnError::UnterminatedComment));
+                };
+            }
+            '(' => {
+                if !skip_variation(&mut chars) {
+                    tokens.push(Err(PgnError::UnterminatedVariation));
+                };
+            }
+            '[' => tokens.push(read_tag_pair(&mut chars)),
+            '$' => {
+                read_symbol(&mut chars, c);
+            }
+            c if c.is_whitespace() => {}
+            c => {
+                let symbol = read_symbol(&mut chars, c);
+                if let Some(token) = get_movetext_token(&symbol) {
+                    tokens.push(Ok(token));
+                };
+            }
+        };
+        is_line_start = false;
+    }
+    tokens
+}
+
+/// Interpret a symbol in the movetext, discarding move numbers and annotations.
+fn get_movetext_token(symbol: &str) -> Option<Token> {
+    if RESULT_TOKENS.contains(&symbol) {
+        return Some(Token::Result(symbol.to_string()));
+    };
+
+    // Move numbers may be written without a space before the move, e.g. `1.e4`.
+    let san = match symbol.rfind('.') {
+        Some(index)
+            if symbol[..index]
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == '.') =>
+        {
+            &symbol[index + 1..]
+        }
+        _ => symbol,
+    };
+    let san = san.trim_end_matches(['!', '?']);
+    match san.is_empty() {
+        true => None,
+        false => Some(Token::Move(san.to_string())),
+    }
+}
+
+fn read_symbol(chars: &mut Peekable<Chars>, first_char: char) -> String {
+    let mut symbol = first_char.to_string();
+    while let Some(c) = chars.peek() {
+        if c.is_whitespace() || "[]{}();$".contains(*c) {
+            break;
+        };
+        symbol.push(*c);
+        chars.next();
+    }
+    symbol
+}
+
+fn read_tag_pair(chars: &mut Peekable<Chars>) -> Result<Token, PgnError> {
+    let mut raw_tag = String::new();
+    let mut is_in_value = false;
+    let mut is_escaped = false;
+    for c in chars.by_ref() {
+        if c == ']' && !is_in_value {
+            break;
+        };
+        if c == '\n' {
+            return Err(PgnError::InvalidTag(raw_tag));
+        };
+        raw_tag.push(c);
+        if is_escaped {
+            is_escaped = false;
+        } else if c == '\\' && is_in_value {
+            is_escaped = true;
+        } else if c == '"' {
+            is_in_value = !is_in_value;
+        };
+    }
+
+    let Some((name, value)) = raw_tag.trim().split_once(char::is_whitespace) else {
+        return Err(PgnError::InvalidTag(raw_tag));
+    };
+    let value = value.trim();
+    if name.is_empty()
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        || value.len() < 2
+        || !value.starts_with('"')
+        || !value.ends_with('"')
+    {
+        return Err(PgnError::InvalidTag(raw_tag));
+    };
+
+    let value = value[1..value.len() - 1]
+        .replace("\\\"", "\"")
+        .replace("\\\\", "\\");
+    Ok(Token::TagPair(name.to_string(), value))
+}
+
+fn skip_line(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.peek() {
+        if c == &'\n' {
+            break;
+        };
+        chars.next();
+    }
+}
+
+/// Skip the rest of a `{...}` comment, returning whether the comment was closed.
+fn skip_comment(chars: &mut Peekable<Chars>) -> bool {
+    chars.any(|c| c == '}')
+}
+
+/// Skip the rest of a (possibly nested) variation, returning whether the variation was closed.
+fn skip_variation(chars: &mut Peekable<Chars>) -> bool {
+    let mut depth = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '{' => {
+                let is_closed = skip_comment(chars);
+                if !is_closed {
+                    return false;
+                };
+            }
+            ';' => skip_line(chars),
+            _ => {}
+        };
+        if depth == 0 {
+            return true;
+        };
+    }
+    false
+}
+
+/// Get the result token that records a game's status.
+pub fn get_result(status: &game::GameStatus) -> String {
+    match status {
+        game::GameStatus::WonByWhite => "1-0".to_string(),
+        game::GameStatus::WonByBlack => "0-1".to_string(),
+        game::GameStatus::Drawn => "1/2-1/2".to_string(),
+        game::GameStatus::ToPlayWhite | game::GameStatus::ToPlayBlack => "*".to_string(),
+    }
+}
+
+/// Get the value of a variant's `Variant` tag, using the names common to other chess software.
+fn get_variant_name(variant: &game::GameVariant) -> Option<&'static str> {
+    match variant {
+        game::GameVariant::Standard => None,
+        game::GameVariant::Chess960 => Some("Chess960"),
+        game::GameVariant::KingOfTheHill => Some("King of the Hill"),
+        game::GameVariant::ThreeCheck => Some("Three-check"),
+    }
+}
+
+// Trait implementations.
+
+impl fmt::Display for Pgn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, value) in self.tags.iter() {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(f, "[{} \"{}\"]", name, value)?;
+        }
+        writeln!(f)?;
+
+        let mut line = String::new();
+        for token in self.get_movetext_tokens() {
+            if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_LENGTH {
+                writeln!(f, "{}", line)?;
+                line.clear();
+            };
+            if !line.is_empty() {
+                line.push(' ');
+            };
+            line.push_str(&token);
+        }
+        writeln!(f, "{}", line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Rank, Square};
+    use crate::testing::factories;
+    use rstest::rstest;
+
+    fn play_moves(game: &mut game::Game, moves: Vec<(&str, &str)>) {
+        let mut player = Colour::White;
+        for (from_square, to_square) in moves {
+            let from_square = Square::from_algebraic(from_square).unwrap();
+            let to_square = Square::from_algebraic(to_square).unwrap();
+            game.play_move(&player, &from_square, &to_square).unwrap();
+            player = player.swap();
+        }
+    }
+
+    #[test]
+    fn writes_ongoing_game() {
+        let mut game = game::Game::new(1);
+        play_moves(&mut game, vec![("e2", "e4"), ("e7", "e5"), ("g1", "f3")]);
+
+        let pgn = Pgn::from_game(&game);
+
+        assert_eq!(
+            pgn.to_string(),
+            "[Event \"?\"]
+[Site \"?\"]
+[Date \"????.??.??\"]
+[Round \"?\"]
+[White \"?\"]
+[Black \"?\"]
+[Result \"*\"]
+
+1. e4 e5 2. Nf3 *
+"
+        );
+    }
+
+    #[test]
+    fn writes_result_of_finished_game() {
+        let mut game = game::Game::new(1);
+        play_moves(
+            &mut game,
+            vec![("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")],
+        );
+
+        let pgn = Pgn::from_game(&game);
+
+        assert_eq!(pgn.result, "0-1");
+        assert_eq!(pgn.moves, vec!["f3", "e5", "g4", "Qh4#"]);
+        assert!(pgn.to_string().ends_with("1. f3 e5 2. g4 Qh4# 0-1\n"));
+    }
+
+    #[test]
+    fn writes_setup_tags_for_game_started_from_position() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1";
+        let mut game = factories::game_from_fen(fen);
+        let e8 = Square::new(Rank::Eight, File::E);
+        let d8 = Square::new(Rank::Eight, File::D);
+        game.play_move(&Colour::Black, &e8, &d8).unwrap();
+
+        let pgn = Pgn::from_game(&game);
+
+        assert!(pgn.tags.contains(&("SetUp".to_string(), "1".to_string())));
+        assert!(pgn.tags.contains(&("FEN".to_string(), fen.to_string())));
+        assert!(pgn.to_string().ends_with("1... Kd8 *\n"));
+    }
+
+    #[test]
+    fn writes_drawn_game_started_with_black_to_move() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1";
+        let mut game = factories::game_from_fen(fen);
+        game.play_san_move(&Colour::Black, "Kd8").unwrap();
+        game.offer_draw(&Colour::White).unwrap();
+        game.accept_draw(&Colour::Black).unwrap();
+
+        let pgn = Pgn::from_game(&game);
+
+        assert!(pgn.tags.contains(&("FEN".to_string(), fen.to_string())));
+        assert!(pgn.to_string().ends_with("1... Kd8 1/2-1/2\n"));
+    }
+
+    #[test]
+    fn numbers_moves_from_fullmove_number_of_starting_position() {
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 57 40";
+        let mut game = factories::game_from_fen(fen);
+        game.play_san_move(&Colour::Black, "Kd8").unwrap();
+        game.play_san_move(&Colour::White, "Kd2").unwrap();
+
+        let pgn = Pgn::from_game(&game);
+
+        assert!(pgn.tags.contains(&("FEN".to_string(), fen.to_string())));
+        assert!(pgn.to_string().ends_with("40... Kd8 41. Kd2 *\n"));
+        let parsed_pgn = Pgn::parse_all(&pgn.to_string()).remove(0).unwrap();
+        assert_eq!(parsed_pgn, pgn);
+    }
+
+    #[test]
+    fn writes_variant_tag_for_chess960_game() {
+        let game = factories::variant_game_from_fen(
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1",
+            game::GameVariant::Chess960,
+        );
+
+        let pgn = Pgn::from_game(&game);
+
+        assert!(pgn
+            .tags
+            .contains(&("Variant".to_string(), "Chess960".to_string())));
+        assert_eq!(pgn.get_variant(), game::GameVariant::Chess960);
+    }
+
+    #[rstest]
+    #[case::king_of_the_hill("King of the Hill", game::GameVariant::KingOfTheHill)]
+    #[case::three_check("three-check", game::GameVariant::ThreeCheck)]
+    #[case::unknown("Crazyhouse", game::GameVariant::Standard)]
+    fn gets_variant_from_tag(
+        #[case] variant_name: &str,
+        #[case] expected_variant: game::GameVariant,
+    ) {
+        let pgn = Pgn {
+            tags: vec![("Variant".to_string(), variant_name.to_string())],
+            starting_colour: Colour::White,
+            starting_fullmove_number: 1,
+            moves: vec![],
+            result: "*".to_string(),
+        };
+
+        assert_eq!(pgn.get_variant(), expected_variant);
+    }
+
+    #[test]
+    fn wraps_long_movetext() {
+        let pgn = Pgn {
+            tags: vec![],
+            starting_colour: Colour::White,
+            starting_fullmove_number: 1,
+            moves: vec!["Nf3".to_string(); 40],
+            result: "*".to_string(),
+        };
+
+        let written = pgn.to_string();
+
+        assert!(written.lines().count() > 2);
+        assert!(written.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert!(written.starts_with("\n1. Nf3 Nf3 2. Nf3 Nf3"));
+    }
+
+    #[test]
+    fn parses_tags_and_moves() {
+        let text = "[Event \"Casual \\\"blitz\\\" game\"]
+[Result \"1-0\"]
+
+1. e4 e5 2.Nf3 Nc6 3. Bb5 a6 1-0
+";
+
+        let games = Pgn::parse_all(text);
+
+        assert_eq!(games.len(), 1);
+        let pgn = games[0].as_ref().unwrap();
+        assert_eq!(
+            pgn.tags,
+            vec![
+                ("Event".to_string(), "Casual \"blitz\" game".to_string()),
+                ("Result".to_string(), "1-0".to_string())
+            ]
+        );
+        assert_eq!(pgn.moves, vec!["e4", "e5", "Nf3", "Nc6", "Bb5", "a6"]);
+        assert_eq!(pgn.result, "1-0");
+    }
+
+    #[test]
+    fn skips_comments_nags_and_variations() {
+        let text = "% Escaped line
+1. e4 {Best by test} e5 $1 2. Nf3!? (2. f4 exf4 (2... d5) {Gambit}) Nc6 ; The classical reply
+3. O-O-O?? 0-0 *";
+
+        let games = Pgn::parse_all(text);
+
+        let pgn = games[0].as_ref().unwrap();
+        assert_eq!(pgn.moves, vec!["e4", "e5", "Nf3", "Nc6", "O-O-O", "0-0"]);
+        assert_eq!(pgn.result, "*");
+    }
+
+    #[test]
+    fn parses_multiple_games() {
+        let text = "[Event \"First\"]
+
+1. e4 e5 1/2-1/2
+
+[Event \"Second\"]
+[SetUp \"1\"]
+[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"]
+
+1... Kd8 *
+";
+
+        let games = Pgn::parse_all(text);
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].as_ref().unwrap().result, "1/2-1/2");
+        let second_game = games[1].as_ref().unwrap();
+        assert_eq!(second_game.starting_colour, Colour::Black);
+        assert_eq!(second_game.moves, vec!["Kd8"]);
+        assert!(second_game.get_setup().unwrap().is_some());
+    }
+
+    #[test]
+    fn invalid_game_does_not_prevent_parsing_other_games() {
+        let text = "[Event Unquoted]
+1. e4 e5 *
+[Event \"Second\"]
+1. d4 *
+";
+
+        let games = Pgn::parse_all(text);
+
+        assert_eq!(games.len(), 2);
+        assert_eq!(
+            games[0],
+            Err(PgnError::InvalidTag("Event Unquoted".to_string()))
+        );
+        assert_eq!(games[1].as_ref().unwrap().moves, vec!["d4"]);
+    }
+
+    #[rstest]
+    #[case::missing_result("1. e4 e5", PgnError::MissingResult)]
+    #[case::unterminated_comment("1. e4 {e5 *", PgnError::UnterminatedComment)]
+    #[case::unterminated_variation("1. e4 (1. d4 *", PgnError::UnterminatedVariation)]
+    #[case::invalid_fen(
+        "[FEN \"not a fen\"]\n*",
+        PgnError::InvalidFen(rulebook::FenError::WrongNumberOfFields(3))
+    )]
+    fn errors_for_invalid_game(#[case] text: &str, #[case] expected_error: PgnError) {
+        let games = Pgn::parse_all(text);
+
+        assert_eq!(games, vec![Err(expected_error)]);
+    }
+
+    #[test]
+    fn parses_written_pgn() {
+        let mut game = game::Game::new(1);
+        play_moves(
+            &mut game,
+            vec![("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")],
+        );
+        let pgn = Pgn::from_game(&game);
+
+        let games = Pgn::parse_all(&pgn.to_string());
+
+        assert_eq!(games, vec![Ok(pgn)]);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::domain::chess_set;
+use std::collections::BTreeMap;
+
+/// Get every square attacked by a player's pieces.
+///
+/// Squares occupied by the player's own pieces are included, since those pieces are defended.
+pub fn get_attacked_squares(
+    colour: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> Vec<chess_set::Square> {
+    chess_set::BitboardChessboard::from_chessboard(chessboard)
+        .get_attacked_squares(&colour)
+        .iter()
+        .collect()
+}
+
+/// Get the squares of a player's pieces that attack a square.
+pub fn get_attackers(
+    square: &chess_set::Square,
+    by_colour: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> Vec<chess_set::Square> {
+    chess_set::BitboardChessboard::from_chessboard(chessboard)
+        .get_attackers(square, &by_colour)
+        .iter()
+        .collect()
+}
+
+/// Get the squares of the pieces defending the piece on a square.
+///
+/// An empty square has no defenders.
+pub fn get_defenders(
+    square: &chess_set::Square,
+    chessboard: &chess_set::Chessboard,
+) -> Vec<chess_set::Square> {
+    match chessboard.get_piece(square) {
+        Some(piece) => get_attackers(square, *piece.get_colour(), chessboard),
+        None => vec![],
+    }
+}
+
+/// Count the number of a player's pieces attacking each square they attack.
+pub fn get_attack_counts(
+    colour: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> BTreeMap<chess_set::Square, usize> {
+    let bitboard_chessboard = chess_set::BitboardChessboard::from_chessboard(chessboard);
+
+    bitboard_chessboard
+        .get_attacked_squares(&colour)
+        .iter()
+        .map(|square| {
+            let attacker_count = bitboard_chessboard.get_attackers(&square, &colour).count();
+            (square, attacker_count as usize)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, Square};
+    use crate::domain::rulebook::Fen;
+    use crate::testing::factories;
+
+    fn square(notation: &str) -> Square {
+        Square::from_algebraic(notation).unwrap()
+    }
+
+    #[test]
+    fn gets_squares_attacked_from_starting_position() {
+        let chessboard = factories::chessboard();
+
+        let attacked_squares = get_attacked_squares(Colour::Black, &chessboard);
+
+        assert_eq!(attacked_squares.len(), 22);
+        assert!(attacked_squares.contains(&square("h6")));
+        assert!(!attacked_squares.contains(&square("h5")));
+    }
+
+    #[test]
+    fn gets_attackers_and_defenders_of_piece() {
+        // The black knight on d5 is attacked by the pawn and the rook, and defended by the pawn.
+        let fen = Fen::parse("4k3/8/4p3/3n4/4P3/8/8/3RK3 w - - 0 1").unwrap();
+        let chessboard = fen.to_chessboard_history().pop().unwrap();
+
+        let attackers = get_attackers(&square("d5"), Colour::White, &chessboard);
+        let defenders = get_defenders(&square("d5"), &chessboard);
+
+        assert_eq!(attackers, vec![square("d1"), square("e4")]);
+        assert_eq!(defenders, vec![square("e6")]);
+    }
+
+    #[test]
+    fn empty_square_has_no_defenders() {
+        let chessboard = factories::chessboard();
+
+        let defenders = get_defenders(&square("e4"), &chessboard);
+
+        assert_eq!(defenders, vec![]);
+    }
+
+    #[test]
+    fn counts_attackers_per_square() {
+        let chessboard = factories::chessboard();
+
+        let attack_counts = get_attack_counts(Colour::White, &chessboard);
+
+        assert_eq!(attack_counts.len(), 22);
+        assert_eq!(attack_counts.get(&square("d2")), Some(&4));
+        assert_eq!(attack_counts.get(&square("f3")), Some(&3));
+        assert_eq!(attack_counts.get(&square("a3")), Some(&2));
+        assert_eq!(attack_counts.get(&square("a4")), None);
+    }
+}
@@ -9,7 +9,7 @@ use crate::domain::chess_set;
 /// * Test whether any of the opponent's pieces can attack that square
 pub fn would_player_be_left_in_check(
     chess_move: &chess_move::Move,
-    chessboard_history: &Vec<chess_set::Chessboard>,
+    chessboard_history: &[chess_set::Chessboard],
 ) -> Result<bool, chess_move::MoveValidationError> {
     let trial_chessboard = match chess_move.apply_if_valid(chessboard_history) {
         Ok(chessboard) => chessboard,
@@ -18,29 +18,46 @@ pub fn would_player_be_left_in_check(
 
     Ok(is_player_in_check(
         &chess_move.piece.get_colour(),
-        trial_chessboard,
+        &trial_chessboard,
     ))
 }
 
-pub fn is_player_in_check(player: &chess_set::Colour, chessboard: chess_set::Chessboard) -> bool {
-    // Locate the king on the _trial_ chessboard, in case the king has moved.
-    let king_location = chessboard.get_square_king_is_on(player);
-    let opponent_player = player.swap();
-
-    for (from_square, opponent_piece) in chessboard.get_pieces(opponent_player) {
-        let attack_king_move = chess_move::Move::new(opponent_piece, from_square, king_location);
-
-        // The full chessboard history isn't needed here, so we just supply the current state.
-        match attack_king_move.validate(&vec![chessboard.clone()]) {
-            Err(error) => match error {
-                chess_move::MoveValidationError::CannotCaptureOpponentKing => return true,
-                _ => continue,
-            },
-            Ok(_) => panic!("Should not be allowed to captured opponent king."),
-        };
+pub fn is_player_in_check(player: &chess_set::Colour, chessboard: &chess_set::Chessboard) -> bool {
+    chess_set::BitboardChessboard::from_chessboard(chessboard).is_player_in_check(player)
+}
+
+/// The checks each player has given, which decide Three-check games.
+///
+/// Checks are counted as they are given, since they can't be recounted from a game's
+/// chessboard history when the game started from a position with checks already given.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChecksGiven {
+    pub white: u8,
+    pub black: u8,
+}
+
+impl ChecksGiven {
+    pub fn new(white: u8, black: u8) -> Self {
+        Self { white, black }
+    }
+
+    // Mutators.
+
+    pub fn add_check(&mut self, player: &chess_set::Colour) {
+        match player {
+            chess_set::Colour::White => self.white += 1,
+            chess_set::Colour::Black => self.black += 1,
+        }
     }
 
-    false
+    // Queries.
+
+    pub fn get(&self, player: &chess_set::Colour) -> u8 {
+        match player {
+            chess_set::Colour::White => self.white,
+            chess_set::Colour::Black => self.black,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -69,7 +86,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(black_king, king_from_square, king_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -90,7 +107,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -115,7 +132,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(shield_piece, shield_from_square, shield_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -140,7 +157,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(shield_piece, shield_from_square, shield_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -162,7 +179,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -185,7 +202,7 @@ mod tests {
         let chess_move =
             chess_move::Move::new(black_king, black_king_from_square, black_king_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -212,7 +229,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(white_pawn, from_square, to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(true));
     }
@@ -235,7 +252,7 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(black_king, king_from_square, king_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(false));
     }
@@ -264,11 +281,22 @@ mod tests {
         let chessboard = Chessboard::new(position);
         let chess_move = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[chessboard]);
 
         assert_eq!(result, Ok(false));
     }
 
+    #[test]
+    fn adds_checks_given_by_each_player() {
+        let mut checks_given = ChecksGiven::new(1, 0);
+
+        checks_given.add_check(&Colour::White);
+        checks_given.add_check(&Colour::Black);
+
+        assert_eq!(checks_given.get(&Colour::White), 2);
+        assert_eq!(checks_given.get(&Colour::Black), 1);
+    }
+
     // Invalid board state scenarios.
     #[test]
     fn error_if_chessboard_action_not_valid() {
@@ -279,7 +307,7 @@ mod tests {
         let to_square = factories::some_other_square();
         let chess_move = chess_move::Move::new(factories::some_piece(), from_square, to_square);
 
-        let result = would_player_be_left_in_check(&chess_move, &vec![empty_chessboard]);
+        let result = would_player_be_left_in_check(&chess_move, &[empty_chessboard]);
 
         let expected_error = Err(chess_move::MoveValidationError::PieceIsNotAtFromSquare);
         assert_eq!(result, expected_error);
@@ -1,14 +1,14 @@
 use super::check;
-use super::moves::chess_move;
+use super::moves::{chess_move, generation};
 use crate::domain::chess_set;
 
 pub fn is_player_checkmated(
     player: chess_set::Colour,
-    chessboard_history: &Vec<chess_set::Chessboard>,
+    chessboard_history: &[chess_set::Chessboard],
 ) -> bool {
     let chessboard = chessboard_history.last().unwrap();
 
-    if !check::is_player_in_check(&player, chessboard.clone()) {
+    if !check::is_player_in_check(&player, chessboard) {
         return false;
     }
 
@@ -23,36 +23,27 @@ pub fn is_player_checkmated(
 /// * Generating moves
 pub fn get_legal_moves(
     player: chess_set::Colour,
-    chessboard_history: &Vec<chess_set::Chessboard>,
+    chessboard_history: &[chess_set::Chessboard],
 ) -> Vec<chess_move::Move> {
-    let chessboard = chessboard_history.last().unwrap();
-
-    let mut legal_moves = vec![];
-    for (from_square, maybe_piece) in chessboard.position.clone().into_iter() {
-        let Some(moved_piece) = maybe_piece else {
-            continue;
-        };
-        if !(moved_piece.get_colour() == &player) {
-            continue;
-        };
-
-        for (to_square, _) in chessboard.position.clone().into_iter() {
-            let chess_move = chess_move::Move::new(moved_piece, from_square, to_square);
-
-            match check::would_player_be_left_in_check(&chess_move, chessboard_history) {
-                Ok(false) => {}
-                Ok(true) => continue,
-                Err(_) => continue,
-            }
-
-            match chess_move.validate(chessboard_history) {
-                Ok(_) => legal_moves.push(chess_move),
-                Err(_) => continue,
-            }
-        }
-    }
+    generation::generate_legal_moves(player, chessboard_history)
+}
 
-    legal_moves
+/// Get the legal move that was played to reach a chessboard from the latest chessboard in a history.
+///
+/// Only where the pieces are is compared, so this also works out the move for a chessboard
+/// whose castling rights and en passant square aren't known.
+pub fn get_played_move(
+    player: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+    chessboard: &chess_set::Chessboard,
+) -> Option<chess_move::Move> {
+    get_legal_moves(player, chessboard_history)
+        .into_iter()
+        .find(|chess_move| {
+            chess_move
+                .apply_if_valid(chessboard_history)
+                .is_ok_and(|played_chessboard| played_chessboard.position == chessboard.position)
+        })
 }
 
 #[cfg(test)]
@@ -81,7 +72,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -109,7 +100,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -131,7 +122,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -156,7 +147,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     // Not checkmate scenarios.
@@ -171,7 +162,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(!is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(!is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -188,7 +179,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(!is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(!is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -205,7 +196,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(!is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(!is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -231,7 +222,7 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(!is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(!is_player_checkmated(Colour::Black, &[chessboard]));
     }
 
     #[test]
@@ -257,22 +248,100 @@ mod checkmate_tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(!is_player_checkmated(Colour::Black, &vec![chessboard]));
+        assert!(!is_player_checkmated(Colour::Black, &[chessboard]));
     }
 }
 
 #[cfg(test)]
 mod get_legal_moves_tests {
-    use super::get_legal_moves;
-    use crate::domain::chess_set::Colour;
+    use super::{get_legal_moves, get_played_move};
+    use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
     use crate::testing::factories;
+    use std::collections::BTreeMap;
 
     #[test]
     fn there_are_twenty_legal_opening_moves() {
         let chessboard = factories::chessboard();
 
-        let legal_moves = get_legal_moves(Colour::White, &vec![chessboard]);
+        let legal_moves = get_legal_moves(Colour::White, &[chessboard]);
 
         assert_eq!(legal_moves.len(), 20);
     }
+
+    #[test]
+    fn includes_one_move_per_promotion_piece_type() {
+        let mut starting_position = BTreeMap::new();
+
+        let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+        let white_pawn_square = Square::new(Rank::Seven, File::C);
+        starting_position.insert(white_pawn_square, white_pawn);
+
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        let white_king_square = Square::new(Rank::One, File::A);
+        starting_position.insert(white_king_square, white_king);
+
+        let black_king = Piece::new(Colour::Black, PieceType::King);
+        let black_king_square = Square::new(Rank::Eight, File::H);
+        starting_position.insert(black_king_square, black_king);
+
+        let chessboard = Chessboard::new(starting_position);
+
+        let legal_moves = get_legal_moves(Colour::White, &[chessboard]);
+
+        let promotions: Vec<PieceType> = legal_moves
+            .iter()
+            .filter(|chess_move| chess_move.from_square == white_pawn_square)
+            .map(|chess_move| chess_move.promote_to.unwrap())
+            .collect();
+        assert_eq!(
+            promotions,
+            vec![
+                PieceType::Queen,
+                PieceType::Rook,
+                PieceType::Bishop,
+                PieceType::Knight
+            ]
+        );
+    }
+
+    #[test]
+    fn gets_move_played_between_chessboards() {
+        let chessboard_history = vec![factories::chessboard()];
+        let mut chessboard = factories::chessboard();
+        let g1 = Square::new(Rank::One, File::G);
+        let f3 = Square::new(Rank::Three, File::F);
+        let knight = chessboard.get_piece(&g1);
+        chessboard.update_position(BTreeMap::from([(g1, None), (f3, knight)]));
+
+        let chess_move = get_played_move(Colour::White, &chessboard_history, &chessboard).unwrap();
+
+        assert_eq!(chess_move.from_square, g1);
+        assert_eq!(chess_move.to_square, f3);
+    }
+
+    #[test]
+    fn gets_move_played_to_chessboard_without_castling_rights() {
+        let chessboard_history = vec![factories::chessboard()];
+        let mut chessboard = factories::chessboard();
+        let e2 = Square::new(Rank::Two, File::E);
+        let e4 = Square::new(Rank::Four, File::E);
+        let pawn = chessboard.get_piece(&e2);
+        chessboard.update_position(BTreeMap::from([(e2, None), (e4, pawn)]));
+        chessboard.castling_rights = vec![];
+
+        let chess_move = get_played_move(Colour::White, &chessboard_history, &chessboard).unwrap();
+
+        assert_eq!(chess_move.from_square, e2);
+        assert_eq!(chess_move.to_square, e4);
+    }
+
+    #[test]
+    fn no_move_is_played_between_identical_chessboards() {
+        let chessboard_history = vec![factories::chessboard()];
+
+        let chess_move =
+            get_played_move(Colour::White, &chessboard_history, &factories::chessboard());
+
+        assert!(chess_move.is_none());
+    }
 }
@@ -0,0 +1,202 @@
+use super::check;
+use super::checkmate;
+use super::moves::{chess_move, pieces};
+use crate::domain::chess_set;
+
+/// The kind of a move, which determines how it changes the chessboard.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize)]
+pub enum MoveKind {
+    Quiet,
+    Capture,
+    DoublePush,
+    EnPassant,
+    CastleKingside,
+    CastleQueenside,
+    Promotion,
+}
+
+/// Everything that can be said about a move once it has been played.
+///
+/// Promotions that capture a piece have the `Promotion` kind, with the captured piece set.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct MoveClassification {
+    pub kind: MoveKind,
+    pub captured_piece: Option<chess_set::Piece>,
+    pub gives_check: bool,
+    pub gives_checkmate: bool,
+}
+
+/// Classify a move, without the caller needing to compare the chessboards before and after it.
+///
+/// The chessboard history should be the history before the move is played.
+pub fn classify_move(
+    chess_move: &chess_move::Move,
+    chessboard_history: &[chess_set::Chessboard],
+) -> Result<MoveClassification, chess_move::MoveValidationError> {
+    let updated_chessboard = chess_move.apply_if_valid(chessboard_history)?;
+    let chessboard = chessboard_history.last().unwrap();
+
+    let kind = get_move_kind(chess_move, chessboard);
+    let captured_piece = match kind {
+        MoveKind::EnPassant => Some(chess_set::Piece::new(
+            chess_move.piece.get_colour().swap(),
+            chess_set::PieceType::Pawn,
+        )),
+        MoveKind::CastleKingside | MoveKind::CastleQueenside => None,
+        _ => chessboard.get_piece(&chess_move.to_square),
+    };
+
+    let opponent = chess_move.piece.get_colour().swap();
+    let gives_check = check::is_player_in_check(&opponent, &updated_chessboard);
+    let gives_checkmate = gives_check && {
+        let mut updated_history = chessboard_history.to_vec();
+        updated_history.push(updated_chessboard);
+        checkmate::is_player_checkmated(opponent, &updated_history)
+    };
+
+    Ok(MoveClassification {
+        kind,
+        captured_piece,
+        gives_check,
+        gives_checkmate,
+    })
+}
+
+fn get_move_kind(chess_move: &chess_move::Move, chessboard: &chess_set::Chessboard) -> MoveKind {
+    let file_distance =
+        chess_move.to_square.get_file().index() - chess_move.from_square.get_file().index();
+    let rank_distance =
+        chess_move.to_square.get_rank().index() - chess_move.from_square.get_rank().index();
+
+    let castling_rights = chess_set::Bitboard::from_squares(&chessboard.castling_rights);
+    let is_castling = pieces::get_castling_rook_square(chess_move, castling_rights).is_some();
+
+    match chess_move.piece.get_piece_type() {
+        // Castles are written as the king moving towards its rook.
+        chess_set::PieceType::King if is_castling && file_distance > 0 => MoveKind::CastleKingside,
+        chess_set::PieceType::King if is_castling => MoveKind::CastleQueenside,
+        chess_set::PieceType::Pawn if chess_move.promote_to.is_some() => MoveKind::Promotion,
+        chess_set::PieceType::Pawn if rank_distance.abs() == 2 => MoveKind::DoublePush,
+        // A pawn can only move diagonally onto an empty square by capturing en passant.
+        chess_set::PieceType::Pawn
+            if file_distance != 0 && !chessboard.is_square_occupied(&chess_move.to_square) =>
+        {
+            MoveKind::EnPassant
+        }
+        _ if chessboard.is_square_occupied(&chess_move.to_square) => MoveKind::Capture,
+        _ => MoveKind::Quiet,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, Piece, PieceType};
+    use crate::domain::rulebook::{from_uci, Fen};
+    use rstest::rstest;
+
+    fn classify(fen: &str, uci: &str) -> MoveClassification {
+        let fen = Fen::parse(fen).unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+        let chess_move = from_uci(uci, chessboard_history.last().unwrap()).unwrap();
+
+        classify_move(&chess_move, &chessboard_history).unwrap()
+    }
+
+    #[rstest]
+    #[case::quiet(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "g1f3",
+        MoveKind::Quiet
+    )]
+    #[case::double_push(
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "e2e4",
+        MoveKind::DoublePush
+    )]
+    #[case::capture("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", "e4d5", MoveKind::Capture)]
+    #[case::en_passant("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6", MoveKind::EnPassant)]
+    #[case::castle_kingside(
+        "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+        "e1g1",
+        MoveKind::CastleKingside
+    )]
+    #[case::castle_queenside(
+        "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1",
+        "e8c8",
+        MoveKind::CastleQueenside
+    )]
+    #[case::chess960_castle_kingside(
+        "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1",
+        "f1g1",
+        MoveKind::CastleKingside
+    )]
+    #[case::chess960_castle_queenside(
+        "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1",
+        "f1b1",
+        MoveKind::CastleQueenside
+    )]
+    #[case::promotion("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8n", MoveKind::Promotion)]
+    fn classifies_kind_of_move(
+        #[case] fen: &str,
+        #[case] uci: &str,
+        #[case] expected_kind: MoveKind,
+    ) {
+        let classification = classify(fen, uci);
+
+        assert_eq!(classification.kind, expected_kind);
+    }
+
+    #[rstest]
+    #[case::quiet("4k3/8/8/8/8/8/8/4K2R w - - 0 1", "h1h2", None)]
+    #[case::capture("4k3/8/8/3n4/4P3/8/8/4K3 w - - 0 1", "e4d5", Some(PieceType::Knight))]
+    #[case::en_passant("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6", Some(PieceType::Pawn))]
+    #[case::capturing_promotion("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7b8q", Some(PieceType::Rook))]
+    fn gets_captured_piece(
+        #[case] fen: &str,
+        #[case] uci: &str,
+        #[case] expected_piece_type: Option<PieceType>,
+    ) {
+        let classification = classify(fen, uci);
+
+        let expected_piece =
+            expected_piece_type.map(|piece_type| Piece::new(Colour::Black, piece_type));
+        assert_eq!(classification.captured_piece, expected_piece);
+    }
+
+    #[test]
+    fn classifies_check_without_mate() {
+        let classification = classify("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8");
+
+        assert!(classification.gives_check);
+        assert!(!classification.gives_checkmate);
+    }
+
+    #[test]
+    fn classifies_checkmate() {
+        let classification = classify("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1", "a1a8");
+
+        assert_eq!(classification.kind, MoveKind::Quiet);
+        assert!(classification.gives_check);
+        assert!(classification.gives_checkmate);
+    }
+
+    #[test]
+    fn errors_for_illegal_move() {
+        let fen = Fen::parse("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+        let king = Piece::new(Colour::White, PieceType::King);
+        let chess_move = chess_move::Move::new(
+            king,
+            chess_set::Square::from_algebraic("e1").unwrap(),
+            chess_set::Square::from_algebraic("e3").unwrap(),
+        );
+
+        let result = classify_move(&chess_move, &chessboard_history);
+
+        assert_eq!(
+            result,
+            Err(chess_move::MoveValidationError::MoveIsNotLegalForPiece)
+        );
+    }
+}
@@ -1,22 +1,64 @@
 use crate::domain::chess_set;
 
-use super::{insufficient_material, stalemate};
+use super::{fifty_move_rule, insufficient_material, repetition, stalemate};
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum Draw {
     InsufficientMaterial,
     Stalemate,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    Repetition,
 }
 
+/// Test whether the game is automatically drawn.
+///
+/// The starting halfmove clock is the halfmove clock of the earliest chessboard in
+/// the history, e.g. as given by the FEN a game started from.
 pub fn is_draw(
     to_play_colour: chess_set::Colour,
-    chessboard_history: &Vec<chess_set::Chessboard>,
+    starting_halfmove_clock: u16,
+    chessboard_history: &[chess_set::Chessboard],
 ) -> Option<Draw> {
     if insufficient_material::is_insufficient_material_draw(chessboard_history.last().unwrap()) {
         return Some(Draw::InsufficientMaterial);
     };
+    is_draw_regardless_of_material(to_play_colour, starting_halfmove_clock, chessboard_history)
+}
+
+/// Test whether the game is automatically drawn, other than by insufficient material.
+///
+/// This is for variants that can be won without checkmating, e.g. King of the Hill.
+pub fn is_draw_regardless_of_material(
+    to_play_colour: chess_set::Colour,
+    starting_halfmove_clock: u16,
+    chessboard_history: &[chess_set::Chessboard],
+) -> Option<Draw> {
     if stalemate::is_stalemate(to_play_colour, chessboard_history) {
         return Some(Draw::Stalemate);
     };
+    if fifty_move_rule::is_seventy_five_move_draw(starting_halfmove_clock, chessboard_history) {
+        return Some(Draw::SeventyFiveMoveRule);
+    };
+    if repetition::is_fivefold_repetition(to_play_colour, chessboard_history) {
+        return Some(Draw::Repetition);
+    };
+
+    None
+}
+
+/// Test whether the player to move can claim a draw.
+pub fn get_claimable_draw(
+    to_play_colour: chess_set::Colour,
+    starting_halfmove_clock: u16,
+    chessboard_history: &[chess_set::Chessboard],
+) -> Option<Draw> {
+    if fifty_move_rule::is_fifty_move_draw_claimable(starting_halfmove_clock, chessboard_history) {
+        return Some(Draw::FiftyMoveRule);
+    };
+    if repetition::is_threefold_repetition_claimable(to_play_colour, chessboard_history) {
+        return Some(Draw::Repetition);
+    };
 
     None
 }
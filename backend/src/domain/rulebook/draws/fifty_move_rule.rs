@@ -0,0 +1,174 @@
+use crate::domain::chess_set;
+
+/// The number of half moves (plies) after which a draw can be claimed.
+const FIFTY_MOVE_RULE_HALFMOVES: u16 = 100;
+
+/// The number of half moves (plies) after which the game is automatically drawn.
+pub(crate) const SEVENTY_FIVE_MOVE_RULE_HALFMOVES: u16 = 150;
+
+pub fn is_fifty_move_draw_claimable(
+    starting_halfmove_clock: u16,
+    chessboard_history: &[chess_set::Chessboard],
+) -> bool {
+    get_halfmove_clock(starting_halfmove_clock, chessboard_history) >= FIFTY_MOVE_RULE_HALFMOVES
+}
+
+pub fn is_seventy_five_move_draw(
+    starting_halfmove_clock: u16,
+    chessboard_history: &[chess_set::Chessboard],
+) -> bool {
+    get_halfmove_clock(starting_halfmove_clock, chessboard_history)
+        >= SEVENTY_FIVE_MOVE_RULE_HALFMOVES
+}
+
+/// Count the half moves played since the last pawn move or capture.
+///
+/// This is derived by walking backwards through the chessboard history. If there has
+/// been no pawn move or capture since the earliest chessboard, the count carries on from
+/// the halfmove clock of that chessboard, e.g. as given by the FEN a game started from.
+pub fn get_halfmove_clock(
+    starting_halfmove_clock: u16,
+    chessboard_history: &[chess_set::Chessboard],
+) -> u16 {
+    let mut halfmove_clock = 0;
+
+    for pair in chessboard_history.windows(2).rev() {
+        let (previous_state, current_state) = (&pair[0], &pair[1]);
+        if is_capture(previous_state, current_state) || is_pawn_move(previous_state, current_state)
+        {
+            return halfmove_clock;
+        }
+        halfmove_clock += 1;
+    }
+
+    starting_halfmove_clock + halfmove_clock
+}
+
+fn is_capture(
+    previous_state: &chess_set::Chessboard,
+    current_state: &chess_set::Chessboard,
+) -> bool {
+    count_pieces(current_state) < count_pieces(previous_state)
+}
+
+fn is_pawn_move(
+    previous_state: &chess_set::Chessboard,
+    current_state: &chess_set::Chessboard,
+) -> bool {
+    for (square, maybe_piece) in previous_state.position.iter() {
+        let was_pawn = is_pawn(maybe_piece);
+        let is_still_pawn = is_pawn(current_state.position.get(square).unwrap());
+        if was_pawn != is_still_pawn {
+            return true;
+        }
+    }
+    false
+}
+
+fn is_pawn(maybe_piece: &Option<chess_set::Piece>) -> bool {
+    match maybe_piece {
+        Some(piece) => piece.get_piece_type() == &chess_set::PieceType::Pawn,
+        None => false,
+    }
+}
+
+fn count_pieces(chessboard: &chess_set::Chessboard) -> usize {
+    chessboard
+        .position
+        .values()
+        .filter(|maybe_piece| maybe_piece.is_some())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+    use crate::testing::factories;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn halfmove_clock_is_zero_for_new_game() {
+        let chessboard = factories::chessboard();
+
+        assert_eq!(get_halfmove_clock(0, &[chessboard]), 0);
+    }
+
+    #[test]
+    fn halfmove_clock_counts_moves_without_pawn_move_or_capture() {
+        let chessboard_history = factories::chessboard_history_without_progress(8);
+
+        assert_eq!(get_halfmove_clock(0, &chessboard_history), 7);
+    }
+
+    #[test]
+    fn halfmove_clock_carries_on_from_starting_halfmove_clock() {
+        let chessboard_history = factories::chessboard_history_without_progress(8);
+
+        assert_eq!(get_halfmove_clock(57, &chessboard_history), 64);
+    }
+
+    #[test]
+    fn halfmove_clock_is_reset_by_pawn_move() {
+        let mut chessboard_history = factories::chessboard_history_without_progress(8);
+
+        let mut chessboard = chessboard_history.last().unwrap().clone();
+        let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+        let mut updates = BTreeMap::new();
+        updates.insert(Square::new(Rank::Six, File::H), Some(white_pawn));
+        chessboard.update_position(updates.clone());
+        chessboard_history.push(chessboard.clone());
+
+        // Advance the pawn by one square.
+        updates.insert(Square::new(Rank::Six, File::H), None);
+        updates.insert(Square::new(Rank::Seven, File::H), Some(white_pawn));
+        chessboard.update_position(updates);
+        chessboard_history.push(chessboard);
+
+        assert_eq!(get_halfmove_clock(57, &chessboard_history), 0);
+    }
+
+    #[test]
+    fn halfmove_clock_is_reset_by_capture() {
+        let mut chessboard_history = factories::chessboard_history_without_progress(8);
+
+        let mut chessboard = chessboard_history.last().unwrap().clone();
+        // Remove the black rook, as if it has been captured.
+        let mut updates = BTreeMap::new();
+        updates.insert(Square::new(Rank::Seven, File::A), None);
+        chessboard.update_position(updates);
+        chessboard_history.push(chessboard);
+
+        assert_eq!(get_halfmove_clock(0, &chessboard_history), 0);
+    }
+
+    #[test]
+    fn fifty_move_draw_is_claimable_after_one_hundred_halfmoves() {
+        let chessboard_history = factories::chessboard_history_without_progress(101);
+
+        assert!(is_fifty_move_draw_claimable(0, &chessboard_history));
+        assert!(!is_seventy_five_move_draw(0, &chessboard_history));
+    }
+
+    #[test]
+    fn fifty_move_draw_is_not_claimable_before_one_hundred_halfmoves() {
+        let chessboard_history = factories::chessboard_history_without_progress(100);
+
+        assert!(!is_fifty_move_draw_claimable(0, &chessboard_history));
+    }
+
+    #[test]
+    fn fifty_move_draw_counts_halfmoves_before_the_chessboard_history() {
+        let chessboard_history = factories::chessboard_history_without_progress(2);
+
+        assert!(is_fifty_move_draw_claimable(99, &chessboard_history));
+        assert!(!is_fifty_move_draw_claimable(98, &chessboard_history));
+    }
+
+    #[test]
+    fn seventy_five_move_draw_after_one_hundred_and_fifty_halfmoves() {
+        let chessboard_history = factories::chessboard_history_without_progress(151);
+
+        assert!(is_seventy_five_move_draw(0, &chessboard_history));
+    }
+}
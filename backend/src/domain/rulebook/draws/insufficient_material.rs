@@ -1,19 +1,94 @@
 use crate::domain::chess_set;
 
 pub fn is_insufficient_material_draw(chessboard: &chess_set::Chessboard) -> bool {
-    let n_white_pieces = chessboard.get_pieces(chess_set::Colour::White).len();
-    let n_black_pieces = chessboard.get_pieces(chess_set::Colour::Black).len();
+    let bitboard_chessboard = chess_set::BitboardChessboard::from_chessboard(chessboard);
+    is_insufficient_material(&bitboard_chessboard)
+}
+
+/// Whether neither player has any material left besides their king.
+///
+/// This is shared with searches, so that they draw the same positions as games do.
+pub(crate) fn is_insufficient_material(
+    bitboard_chessboard: &chess_set::BitboardChessboard,
+) -> bool {
+    let n_white_pieces = bitboard_chessboard
+        .get_occupied_squares(&chess_set::Colour::White)
+        .count();
+    let n_black_pieces = bitboard_chessboard
+        .get_occupied_squares(&chess_set::Colour::Black)
+        .count();
 
     n_white_pieces == 1 && n_black_pieces == 1
 }
 
+/// Whether a player could checkmate their opponent by any sequence of legal moves.
+///
+/// This considers both players' material, since the opponent's own pieces can hem their
+/// king in. A king with knights can mate any opponent with something besides queens to
+/// block with, and a king with bishops of a single square colour can only mate an
+/// opponent with a knight, a pawn or a bishop of the other square colour.
+pub fn has_sufficient_mating_material(
+    colour: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> bool {
+    let bitboard_chessboard = chess_set::BitboardChessboard::from_chessboard(chessboard);
+    let get_squares = |colour: &chess_set::Colour, piece_type: chess_set::PieceType| {
+        bitboard_chessboard.get_squares_of(colour, &piece_type)
+    };
+    let opponent = colour.swap();
+
+    let n_major_pieces_or_pawns = get_squares(&colour, chess_set::PieceType::Pawn).count()
+        + get_squares(&colour, chess_set::PieceType::Rook).count()
+        + get_squares(&colour, chess_set::PieceType::Queen).count();
+    if n_major_pieces_or_pawns > 0 {
+        return true;
+    }
+
+    let n_knights = get_squares(&colour, chess_set::PieceType::Knight).count();
+    let bishops = get_squares(&colour, chess_set::PieceType::Bishop);
+    let n_opponent_pieces = bitboard_chessboard.get_occupied_squares(&opponent).count();
+    let n_opponent_kings_and_queens =
+        1 + get_squares(&opponent, chess_set::PieceType::Queen).count();
+
+    match (n_knights, bishops.count()) {
+        (0, 0) => false,
+        (1, 0) => n_opponent_pieces > n_opponent_kings_and_queens,
+        (_, 0) => true,
+        (0, _) => {
+            let mut bishop_square_colours = bishops.iter().map(|square| is_light_square(&square));
+            let first_square_colour = bishop_square_colours.next();
+            if bishop_square_colours.any(|square_colour| Some(square_colour) != first_square_colour)
+            {
+                return true;
+            }
+
+            let opponent_has_knights_or_pawns =
+                get_squares(&opponent, chess_set::PieceType::Knight).count()
+                    + get_squares(&opponent, chess_set::PieceType::Pawn).count()
+                    > 0;
+            let opponent_has_bishops_of_other_square_colour =
+                get_squares(&opponent, chess_set::PieceType::Bishop)
+                    .iter()
+                    .any(|square| Some(is_light_square(&square)) != first_square_colour);
+            opponent_has_knights_or_pawns || opponent_has_bishops_of_other_square_colour
+        }
+        _ => true,
+    }
+}
+
+fn is_light_square(square: &chess_set::Square) -> bool {
+    (square.get_rank().index() + square.get_file().index()) % 2 == 1
+}
+
 #[cfg(test)]
 mod tests {
     use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
     use std::collections::BTreeMap;
 
-    use super::is_insufficient_material_draw;
+    use super::{has_sufficient_mating_material, is_insufficient_material_draw};
+    use crate::domain::rulebook::Fen;
     use crate::testing::factories;
+    use rstest::rstest;
 
     #[test]
     fn draw_when_one_player_has_insufficient_material() {
@@ -38,4 +113,34 @@ mod tests {
 
         assert!(!is_insufficient_material_draw(&chessboard));
     }
+
+    #[rstest]
+    #[case::lone_king("4k3/8/8/8/8/8/8/4K3 w - - 0 1", false)]
+    #[case::single_bishop("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", false)]
+    #[case::single_knight("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1", false)]
+    #[case::two_minor_pieces("4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1", true)]
+    #[case::pawn("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", true)]
+    #[case::rook("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", true)]
+    #[case::two_knights("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", true)]
+    #[case::knight_against_rook("4k2r/8/8/8/8/8/8/1N2K3 w - - 0 1", true)]
+    #[case::knight_against_queen("4k3/8/8/8/8/8/8/1N1qK3 w - - 0 1", false)]
+    #[case::bishop_against_pawn("4k3/4p3/8/8/8/8/8/2B1K3 w - - 0 1", true)]
+    #[case::bishop_against_knight("4kn2/8/8/8/8/8/8/2B1K3 w - - 0 1", true)]
+    #[case::bishop_against_bishop_of_same_colour("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1", false)]
+    #[case::bishop_against_bishop_of_other_colour("2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1", true)]
+    #[case::bishop_against_rook("r3k3/8/8/8/8/8/8/2B1K3 w - - 0 1", false)]
+    #[case::bishops_of_same_colour("4k3/8/8/8/8/8/8/B1B1K3 w - - 0 1", false)]
+    #[case::bishops_of_both_colours("4k3/8/8/8/8/8/8/2BBK3 w - - 0 1", true)]
+    fn determines_whether_player_has_mating_material(#[case] fen: &str, #[case] expected: bool) {
+        let chessboard = Fen::parse(fen)
+            .unwrap()
+            .to_chessboard_history()
+            .pop()
+            .unwrap();
+
+        assert_eq!(
+            has_sufficient_mating_material(Colour::White, &chessboard),
+            expected
+        );
+    }
 }
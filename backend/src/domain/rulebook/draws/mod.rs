@@ -1,5 +1,13 @@
 mod all_draws;
+mod fifty_move_rule;
 mod insufficient_material;
+mod repetition;
 mod stalemate;
 
-pub use all_draws::{is_draw, Draw};
+pub use all_draws::{get_claimable_draw, is_draw, is_draw_regardless_of_material, Draw};
+pub use fifty_move_rule::get_halfmove_clock;
+pub(crate) use fifty_move_rule::SEVENTY_FIVE_MOVE_RULE_HALFMOVES;
+pub use insufficient_material::has_sufficient_mating_material;
+pub(crate) use insufficient_material::is_insufficient_material;
+pub use repetition::get_repetition_count;
+pub(crate) use repetition::FIVEFOLD_REPETITION_COUNT;
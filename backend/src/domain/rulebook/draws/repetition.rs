@@ -0,0 +1,246 @@
+use super::fifty_move_rule;
+use crate::domain::chess_set;
+use crate::domain::rulebook::moves::generation;
+use std::collections::BTreeMap;
+
+/// The number of times a position must occur before a draw can be claimed.
+const THREEFOLD_REPETITION_COUNT: usize = 3;
+
+/// The number of times a position must occur before the game is automatically drawn.
+pub(crate) const FIVEFOLD_REPETITION_COUNT: usize = 5;
+
+/// Everything that must match for two positions to count as the same position.
+///
+/// The player to move is not stored, since only positions with the same player
+/// to move are ever compared.
+#[derive(PartialEq)]
+struct Position<'a> {
+    position: &'a BTreeMap<chess_set::Square, Option<chess_set::Piece>>,
+    castling_rights: &'a Vec<chess_set::Square>,
+    en_passant_square: Option<chess_set::Square>,
+}
+
+pub fn is_threefold_repetition_claimable(
+    to_play_colour: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> bool {
+    get_repetition_count(to_play_colour, chessboard_history) >= THREEFOLD_REPETITION_COUNT
+}
+
+pub fn is_fivefold_repetition(
+    to_play_colour: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> bool {
+    get_repetition_count(to_play_colour, chessboard_history) >= FIVEFOLD_REPETITION_COUNT
+}
+
+/// Count the number of times the latest position has occurred, including the latest position.
+///
+/// Only positions since the last pawn move or capture are searched, since those
+/// moves can never be undone.
+pub fn get_repetition_count(
+    to_play_colour: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> usize {
+    let latest_index = chessboard_history.len() - 1;
+    // Only positions in the chessboard history can be compared, so any halfmove clock
+    // the history started with is ignored.
+    let halfmove_clock = fifty_move_rule::get_halfmove_clock(0, chessboard_history) as usize;
+    let latest_position = get_position(to_play_colour, &chessboard_history[latest_index]);
+
+    let mut repetition_count = 1;
+    for index in (latest_index - halfmove_clock..=latest_index)
+        .rev()
+        .step_by(2)
+        .skip(1)
+    {
+        // Compare the piece placement first, since it is by far the cheapest check.
+        if !(&chessboard_history[index].position == latest_position.position) {
+            continue;
+        };
+        let position = get_position(to_play_colour, &chessboard_history[index]);
+        if position == latest_position {
+            repetition_count += 1;
+        };
+    }
+
+    repetition_count
+}
+
+fn get_position(
+    to_play_colour: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> Position<'_> {
+    Position {
+        position: &chessboard.position,
+        castling_rights: &chessboard.castling_rights,
+        // An en passant square only distinguishes positions if it can actually be captured onto.
+        en_passant_square: generation::get_legal_en_passant_square(
+            to_play_colour,
+            &chess_set::BitboardChessboard::from_chessboard(chessboard),
+            chessboard.en_passant_square,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
+    use crate::domain::rulebook::moves::chess_move::Move;
+    use crate::domain::rulebook::moves::pieces;
+    use crate::testing::factories;
+
+    fn play_move(chessboard_history: &mut Vec<Chessboard>, from_square: Square, to_square: Square) {
+        let mut chessboard = chessboard_history.last().unwrap().clone();
+        let piece = chessboard.get_piece(&from_square);
+
+        let mut updates = BTreeMap::new();
+        updates.insert(from_square, None);
+        updates.insert(to_square, piece);
+        chessboard.update_position(updates);
+
+        let chess_move = Move::new(piece.unwrap(), from_square, to_square);
+        chessboard.castling_rights =
+            pieces::get_castling_rights_after_move(&chessboard.castling_rights, &chess_move);
+        chessboard.en_passant_square = pieces::get_en_passant_square_after_move(&chess_move);
+
+        chessboard_history.push(chessboard);
+    }
+
+    fn shuffle_knights(chessboard_history: &mut Vec<Chessboard>) {
+        let g1 = Square::new(Rank::One, File::G);
+        let f3 = Square::new(Rank::Three, File::F);
+        let g8 = Square::new(Rank::Eight, File::G);
+        let f6 = Square::new(Rank::Six, File::F);
+
+        play_move(chessboard_history, g1, f3);
+        play_move(chessboard_history, g8, f6);
+        play_move(chessboard_history, f3, g1);
+        play_move(chessboard_history, f6, g8);
+    }
+
+    #[test]
+    fn counts_single_occurrence_of_starting_position() {
+        let chessboard_history = vec![factories::chessboard()];
+
+        let repetition_count = get_repetition_count(Colour::White, &chessboard_history);
+
+        assert_eq!(repetition_count, 1);
+    }
+
+    #[test]
+    fn threefold_repetition_is_claimable_after_knights_shuffle_twice() {
+        let mut chessboard_history = vec![factories::chessboard()];
+        shuffle_knights(&mut chessboard_history);
+
+        assert_eq!(get_repetition_count(Colour::White, &chessboard_history), 2);
+        assert!(!is_threefold_repetition_claimable(
+            Colour::White,
+            &chessboard_history
+        ));
+
+        shuffle_knights(&mut chessboard_history);
+
+        assert!(is_threefold_repetition_claimable(
+            Colour::White,
+            &chessboard_history
+        ));
+        assert!(!is_fivefold_repetition(Colour::White, &chessboard_history));
+    }
+
+    #[test]
+    fn fivefold_repetition_after_knights_shuffle_four_times() {
+        let mut chessboard_history = vec![factories::chessboard()];
+        for _ in 0..4 {
+            shuffle_knights(&mut chessboard_history);
+        }
+
+        assert!(is_fivefold_repetition(Colour::White, &chessboard_history));
+    }
+
+    #[test]
+    fn positions_with_different_player_to_move_are_not_repetitions() {
+        let mut starting_position = BTreeMap::new();
+        let a1 = Square::new(Rank::One, File::A);
+        let h1 = Square::new(Rank::One, File::H);
+        let h8 = Square::new(Rank::Eight, File::H);
+        starting_position.insert(a1, Piece::new(Colour::White, PieceType::Rook));
+        starting_position.insert(h1, Piece::new(Colour::White, PieceType::King));
+        starting_position.insert(h8, Piece::new(Colour::Black, PieceType::King));
+        let mut chessboard_history = vec![Chessboard::new(starting_position)];
+
+        // The rook returns to A1 in three moves, so the placement recurs with black to move.
+        let a2 = Square::new(Rank::Two, File::A);
+        let a3 = Square::new(Rank::Three, File::A);
+        let g8 = Square::new(Rank::Eight, File::G);
+        play_move(&mut chessboard_history, a1, a2);
+        play_move(&mut chessboard_history, h8, g8);
+        play_move(&mut chessboard_history, a2, a3);
+        play_move(&mut chessboard_history, g8, h8);
+        play_move(&mut chessboard_history, a3, a1);
+
+        assert_eq!(chessboard_history[0], chessboard_history[5]);
+        assert_eq!(get_repetition_count(Colour::Black, &chessboard_history), 1);
+    }
+
+    #[test]
+    fn positions_with_different_castling_rights_are_not_repetitions() {
+        let mut chessboard_history = vec![factories::chessboard()];
+        let e2 = Square::new(Rank::Two, File::E);
+        let e4 = Square::new(Rank::Four, File::E);
+        let e7 = Square::new(Rank::Seven, File::E);
+        let e5 = Square::new(Rank::Five, File::E);
+        let e1 = Square::new(Rank::One, File::E);
+        let e8 = Square::new(Rank::Eight, File::E);
+
+        play_move(&mut chessboard_history, e2, e4);
+        play_move(&mut chessboard_history, e7, e5);
+
+        // The kings walk out and back, so lose the right to castle.
+        for _ in 0..2 {
+            play_move(&mut chessboard_history, e1, e2);
+            play_move(&mut chessboard_history, e8, e7);
+            play_move(&mut chessboard_history, e2, e1);
+            play_move(&mut chessboard_history, e7, e8);
+        }
+
+        assert_eq!(
+            chessboard_history[2].position,
+            chessboard_history[10].position
+        );
+        assert_eq!(get_repetition_count(Colour::White, &chessboard_history), 2);
+    }
+
+    #[test]
+    fn positions_with_different_en_passant_availability_are_not_repetitions() {
+        let mut chessboard_history = vec![factories::chessboard()];
+        let e2 = Square::new(Rank::Two, File::E);
+        let e4 = Square::new(Rank::Four, File::E);
+        let e5 = Square::new(Rank::Five, File::E);
+        let d7 = Square::new(Rank::Seven, File::D);
+        let d5 = Square::new(Rank::Five, File::D);
+        let g8 = Square::new(Rank::Eight, File::G);
+        let f6 = Square::new(Rank::Six, File::F);
+
+        play_move(&mut chessboard_history, e2, e4);
+        play_move(&mut chessboard_history, g8, f6);
+        play_move(&mut chessboard_history, e4, e5);
+        play_move(&mut chessboard_history, d7, d5);
+
+        // White could now capture en passant on d6, but not after the knights shuffle.
+        let first_occurrence = chessboard_history.last().unwrap().clone();
+        let g1 = Square::new(Rank::One, File::G);
+        let f3 = Square::new(Rank::Three, File::F);
+        play_move(&mut chessboard_history, g1, f3);
+        play_move(&mut chessboard_history, f6, g8);
+        play_move(&mut chessboard_history, f3, g1);
+        play_move(&mut chessboard_history, g8, f6);
+
+        assert_eq!(
+            chessboard_history.last().unwrap().position,
+            first_occurrence.position
+        );
+        assert_eq!(get_repetition_count(Colour::White, &chessboard_history), 1);
+    }
+}
@@ -3,7 +3,7 @@ use crate::domain::rulebook;
 
 pub fn is_stalemate(
     to_play_colour: chess_set::Colour,
-    chessboard_history: &Vec<chess_set::Chessboard>,
+    chessboard_history: &[chess_set::Chessboard],
 ) -> bool {
     rulebook::get_legal_moves(to_play_colour, chessboard_history).len() == 0
 }
@@ -38,15 +38,21 @@ mod tests {
 
         let chessboard = Chessboard::new(starting_position);
 
-        assert!(is_stalemate(Colour::White, &vec![chessboard.clone()]));
-        assert!(!is_stalemate(Colour::Black, &vec![chessboard]))
+        assert!(is_stalemate(
+            Colour::White,
+            std::slice::from_ref(&chessboard)
+        ));
+        assert!(!is_stalemate(Colour::Black, &[chessboard]))
     }
 
     #[test]
     fn is_not_stalemate_when_player_can_move() {
         let chessboard = factories::chessboard();
 
-        assert!(!is_stalemate(Colour::White, &vec![chessboard.clone()]));
-        assert!(!is_stalemate(Colour::Black, &vec![chessboard]))
+        assert!(!is_stalemate(
+            Colour::White,
+            std::slice::from_ref(&chessboard)
+        ));
+        assert!(!is_stalemate(Colour::Black, &[chessboard]))
     }
 }
@@ -0,0 +1,747 @@
+use super::check;
+use super::draws;
+use super::moves::pieces;
+use crate::domain::chess_set;
+use std::collections::BTreeMap;
+use std::fmt;
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FenError {
+    #[error("A FEN should have 6 fields, or 7 with the checks given, but {0} were given.")]
+    WrongNumberOfFields(usize),
+
+    #[error("'{0}' is not a valid piece placement.")]
+    InvalidPiecePlacement(String),
+
+    #[error("'{0}' is not a valid active colour.")]
+    InvalidActiveColour(String),
+
+    #[error("'{0}' is not a valid set of castling rights.")]
+    InvalidCastlingRights(String),
+
+    #[error("'{0}' is not a valid en passant square.")]
+    InvalidEnPassantSquare(String),
+
+    #[error("'{0}' is not a valid move counter.")]
+    InvalidMoveCounter(String),
+
+    #[error("'{0}' is not a valid count of checks given.")]
+    InvalidChecksGiven(String),
+
+    #[error("Illegal position: {0}")]
+    IllegalPosition(String),
+}
+
+/// A position in Forsyth-Edwards Notation.
+///
+/// The castling rights are represented by the squares of the rooks that can still
+/// (at some point) be castled with. Castling rights are read and written in X-FEN, so
+/// Chess960 positions round-trip: `K` and `Q` refer to the outermost rook on each side
+/// of the king, and any other rook is given by the letter of its file.
+///
+/// Three-check positions have a seventh field with the checks each player has given,
+/// e.g. `+1+0` once White has given a check.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fen {
+    pub chessboard: chess_set::Chessboard,
+    pub to_play_colour: chess_set::Colour,
+    pub castling_rights: Vec<chess_set::Square>,
+    pub en_passant_square: Option<chess_set::Square>,
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
+    pub checks_given: Option<check::ChecksGiven>,
+}
+
+impl Fen {
+    // Factories.
+
+    /// Parse a FEN, e.g. `rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1`.
+    pub fn parse(fen: &str) -> Result<Self, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if !(fields.len() == 6 || fields.len() == 7) {
+            return Err(FenError::WrongNumberOfFields(fields.len()));
+        };
+
+        let chessboard = parse_piece_placement(fields[0])?;
+        let castling_rights = parse_castling_rights(fields[2], &chessboard)?;
+        let parsed_fen = Self {
+            chessboard,
+            to_play_colour: parse_active_colour(fields[1])?,
+            castling_rights,
+            en_passant_square: parse_en_passant_square(fields[3])?,
+            halfmove_clock: parse_move_counter(fields[4])?,
+            fullmove_number: parse_move_counter(fields[5])?,
+            checks_given: match fields.get(6) {
+                Some(value) => Some(parse_checks_given(value)?),
+                None => None,
+            },
+        };
+        if parsed_fen.fullmove_number == 0 {
+            return Err(FenError::InvalidMoveCounter(fields[5].to_string()));
+        };
+
+        parsed_fen.validate()?;
+        Ok(parsed_fen)
+    }
+
+    /// Describe the latest position in a chessboard history.
+    ///
+    /// The move counters carry on from those of the earliest chessboard in the history,
+    /// e.g. as given by the FEN a game started from.
+    pub fn from_chessboard_history(
+        to_play_colour: chess_set::Colour,
+        starting_halfmove_clock: u16,
+        starting_fullmove_number: u16,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Self {
+        let plies_played = chessboard_history.len() as u16 - 1;
+        let first_to_play_colour = match plies_played.is_multiple_of(2) {
+            true => to_play_colour,
+            false => to_play_colour.swap(),
+        };
+        let offset = match first_to_play_colour {
+            chess_set::Colour::White => 0,
+            chess_set::Colour::Black => 1,
+        };
+
+        let chessboard = chessboard_history.last().unwrap();
+
+        Self {
+            chessboard: chess_set::Chessboard {
+                castling_rights: vec![],
+                en_passant_square: None,
+                ..chessboard.clone()
+            },
+            to_play_colour,
+            castling_rights: chessboard.castling_rights.clone(),
+            en_passant_square: chessboard.en_passant_square,
+            halfmove_clock: draws::get_halfmove_clock(starting_halfmove_clock, chessboard_history),
+            fullmove_number: starting_fullmove_number + (plies_played + offset) / 2,
+            checks_given: None,
+        }
+    }
+
+    // Queries.
+
+    /// Get a chessboard history that starts from the position.
+    ///
+    /// Chessboards don't record the move counters, so they must be kept alongside the
+    /// history, e.g. by the game played from the position.
+    pub fn to_chessboard_history(&self) -> Vec<chess_set::Chessboard> {
+        let mut chessboard = self.chessboard.clone();
+        chessboard.castling_rights = self.castling_rights.clone();
+        chessboard.en_passant_square = self.en_passant_square;
+        vec![chessboard]
+    }
+
+    fn validate(&self) -> Result<(), FenError> {
+        for colour in [chess_set::Colour::White, chess_set::Colour::Black] {
+            let king = chess_set::Piece::new(colour, chess_set::PieceType::King);
+            let king_count = self
+                .chessboard
+                .position
+                .values()
+                .filter(|maybe_piece| maybe_piece == &&Some(king))
+                .count();
+            if king_count != 1 {
+                let reason = format!("{:?} should have exactly one king.", colour);
+                return Err(FenError::IllegalPosition(reason));
+            };
+        }
+
+        for (square, maybe_piece) in self.chessboard.position.iter() {
+            let Some(piece) = maybe_piece else {
+                continue;
+            };
+            let is_pawn = piece.get_piece_type() == &chess_set::PieceType::Pawn;
+            let is_back_rank =
+                [chess_set::Rank::One, chess_set::Rank::Eight].contains(square.get_rank());
+            if is_pawn && is_back_rank {
+                let reason = format!("there is a pawn on {}.", square.to_algebraic());
+                return Err(FenError::IllegalPosition(reason));
+            };
+        }
+
+        let opponent_colour = self.to_play_colour.swap();
+        if check::is_player_in_check(&opponent_colour, &self.chessboard) {
+            let reason = format!("{:?} is in check but not to move.", opponent_colour);
+            return Err(FenError::IllegalPosition(reason));
+        };
+
+        let possible_castling_rights = pieces::get_possible_castling_rights(&self.chessboard);
+        for rook_square in self.castling_rights.iter() {
+            if !possible_castling_rights.contains(rook_square) {
+                let reason = format!(
+                    "castling with the rook on {} requires the king and rook on their back rank.",
+                    rook_square.to_algebraic()
+                );
+                return Err(FenError::IllegalPosition(reason));
+            };
+        }
+
+        if let Some(en_passant_square) = self.en_passant_square {
+            if !self.is_en_passant_square_consistent(&en_passant_square) {
+                let reason = format!(
+                    "no pawn can have just advanced over {}.",
+                    en_passant_square.to_algebraic()
+                );
+                return Err(FenError::IllegalPosition(reason));
+            };
+        };
+
+        Ok(())
+    }
+
+    fn is_en_passant_square_consistent(&self, en_passant_square: &chess_set::Square) -> bool {
+        let expected_rank = match self.to_play_colour {
+            chess_set::Colour::White => &chess_set::Rank::Six,
+            chess_set::Colour::Black => &chess_set::Rank::Three,
+        };
+        if en_passant_square.get_rank() != expected_rank {
+            return false;
+        };
+
+        let (from_square, to_square) =
+            pieces::get_double_advancement_squares(self.to_play_colour, en_passant_square);
+        let opponent_pawn =
+            chess_set::Piece::new(self.to_play_colour.swap(), chess_set::PieceType::Pawn);
+
+        self.chessboard.get_piece(&to_square) == Some(opponent_pawn)
+            && !self.chessboard.is_square_occupied(en_passant_square)
+            && !self.chessboard.is_square_occupied(&from_square)
+    }
+}
+
+impl fmt::Display for Fen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let active_colour = match self.to_play_colour {
+            chess_set::Colour::White => "w",
+            chess_set::Colour::Black => "b",
+        };
+        let en_passant_square = match self.en_passant_square {
+            Some(square) => square.to_algebraic(),
+            None => "-".to_string(),
+        };
+
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            write_piece_placement(&self.chessboard),
+            active_colour,
+            write_castling_rights(&self.castling_rights, &self.chessboard),
+            en_passant_square,
+            self.halfmove_clock,
+            self.fullmove_number
+        )?;
+        if let Some(checks_given) = self.checks_given {
+            write!(f, " +{}+{}", checks_given.white, checks_given.black)?;
+        };
+        Ok(())
+    }
+}
+
+// Parsing.
+
+fn parse_piece_placement(value: &str) -> Result<chess_set::Chessboard, FenError> {
+    let invalid = || FenError::InvalidPiecePlacement(value.to_string());
+
+    let ranks: Vec<&str> = value.split('/').collect();
+    if ranks.len() != 8 {
+        return Err(invalid());
+    };
+
+    let mut position = BTreeMap::new();
+    for (rank, rank_value) in chess_set::Rank::iter().rev().zip(ranks) {
+        let mut file_index = 1;
+        for symbol in rank_value.chars() {
+            if let Some(empty_squares) = symbol.to_digit(10) {
+                if !(1..=8).contains(&empty_squares) {
+                    return Err(invalid());
+                };
+                file_index += empty_squares as i8;
+                continue;
+            };
+
+            let Some(piece) = parse_piece(symbol) else {
+                return Err(invalid());
+            };
+            if file_index > 8 {
+                return Err(invalid());
+            };
+            let square = chess_set::Square::new(rank, chess_set::File::from_index(file_index));
+            position.insert(square, piece);
+            file_index += 1;
+        }
+
+        if file_index != 9 {
+            return Err(invalid());
+        };
+    }
+
+    Ok(chess_set::Chessboard::new(position))
+}
+
+fn parse_piece(symbol: char) -> Option<chess_set::Piece> {
+    let colour = match symbol.is_ascii_uppercase() {
+        true => chess_set::Colour::White,
+        false => chess_set::Colour::Black,
+    };
+    let piece_type = chess_set::PieceType::from_notation(symbol.to_ascii_uppercase())?;
+    Some(chess_set::Piece::new(colour, piece_type))
+}
+
+fn parse_active_colour(value: &str) -> Result<chess_set::Colour, FenError> {
+    match value {
+        "w" => Ok(chess_set::Colour::White),
+        "b" => Ok(chess_set::Colour::Black),
+        _ => Err(FenError::InvalidActiveColour(value.to_string())),
+    }
+}
+
+/// Parse castling rights in X-FEN, which also accepts Shredder-FEN's file letters.
+///
+/// `K` and `Q` are read as the outermost rook on that side of the king. When there is
+/// no such rook, the corner square is used, so that the position is reported as illegal.
+fn parse_castling_rights(
+    value: &str,
+    chessboard: &chess_set::Chessboard,
+) -> Result<Vec<chess_set::Square>, FenError> {
+    if value == "-" {
+        return Ok(vec![]);
+    };
+
+    let mut castling_rights = vec![];
+    for symbol in value.chars() {
+        let (colour, rank) = match symbol.is_ascii_uppercase() {
+            true => (chess_set::Colour::White, chess_set::Rank::One),
+            false => (chess_set::Colour::Black, chess_set::Rank::Eight),
+        };
+        let rook_square = match symbol.to_ascii_uppercase() {
+            'K' => get_outermost_rook_square(colour, true, chessboard)
+                .unwrap_or(chess_set::Square::new(rank, chess_set::File::H)),
+            'Q' => get_outermost_rook_square(colour, false, chessboard)
+                .unwrap_or(chess_set::Square::new(rank, chess_set::File::A)),
+            letter @ 'A'..='H' => {
+                chess_set::Square::new(rank, chess_set::File::from_letter(letter))
+            }
+            _ => return Err(FenError::InvalidCastlingRights(value.to_string())),
+        };
+        if castling_rights.contains(&rook_square) {
+            return Err(FenError::InvalidCastlingRights(value.to_string()));
+        };
+        castling_rights.push(rook_square);
+    }
+
+    castling_rights.sort();
+    Ok(castling_rights)
+}
+
+fn parse_en_passant_square(value: &str) -> Result<Option<chess_set::Square>, FenError> {
+    if value == "-" {
+        return Ok(None);
+    };
+    match chess_set::Square::from_algebraic(value) {
+        Some(square) => Ok(Some(square)),
+        None => Err(FenError::InvalidEnPassantSquare(value.to_string())),
+    }
+}
+
+fn parse_move_counter(value: &str) -> Result<u16, FenError> {
+    match value.parse::<u16>() {
+        Ok(counter) => Ok(counter),
+        Err(_) => Err(FenError::InvalidMoveCounter(value.to_string())),
+    }
+}
+
+/// Parse the checks each player has given, e.g. `+1+0`.
+fn parse_checks_given(value: &str) -> Result<check::ChecksGiven, FenError> {
+    let invalid = || FenError::InvalidChecksGiven(value.to_string());
+
+    let Some((white, black)) = value
+        .strip_prefix('+')
+        .and_then(|counts| counts.split_once('+'))
+    else {
+        return Err(invalid());
+    };
+    match (white.parse::<u8>(), black.parse::<u8>()) {
+        (Ok(white), Ok(black)) => Ok(check::ChecksGiven::new(white, black)),
+        _ => Err(invalid()),
+    }
+}
+
+// Writing.
+
+fn write_piece_placement(chessboard: &chess_set::Chessboard) -> String {
+    let mut ranks = vec![];
+
+    for rank in chess_set::Rank::iter().rev() {
+        let mut rank_value = String::new();
+        let mut empty_squares = 0;
+
+        for file in chess_set::File::iter() {
+            let square = chess_set::Square::new(rank, file);
+            let Some(piece) = chessboard.get_piece(&square) else {
+                empty_squares += 1;
+                continue;
+            };
+
+            if empty_squares > 0 {
+                rank_value.push_str(&empty_squares.to_string());
+                empty_squares = 0;
+            };
+            rank_value.push(write_piece(&piece));
+        }
+
+        if empty_squares > 0 {
+            rank_value.push_str(&empty_squares.to_string());
+        };
+        ranks.push(rank_value);
+    }
+
+    ranks.join("/")
+}
+
+fn write_piece(piece: &chess_set::Piece) -> char {
+    let letter = piece.get_piece_type().to_string().chars().next().unwrap();
+    match piece.get_colour() {
+        chess_set::Colour::White => letter,
+        chess_set::Colour::Black => letter.to_ascii_lowercase(),
+    }
+}
+
+/// Write castling rights in X-FEN, with each player's kingside rights first.
+///
+/// The outermost rook on each side of the king is written as `K` or `Q`, and any
+/// other rook by the letter of its file.
+fn write_castling_rights(
+    castling_rights: &[chess_set::Square],
+    chessboard: &chess_set::Chessboard,
+) -> String {
+    let mut value = String::new();
+
+    for (colour, rank) in [
+        (chess_set::Colour::White, chess_set::Rank::One),
+        (chess_set::Colour::Black, chess_set::Rank::Eight),
+    ] {
+        let mut rook_squares: Vec<&chess_set::Square> = castling_rights
+            .iter()
+            .filter(|rook_square| rook_square.get_rank() == &rank)
+            .collect();
+        rook_squares.sort_by_key(|rook_square| std::cmp::Reverse(*rook_square.get_file()));
+
+        let king_file = get_back_rank_king_file(colour, chessboard);
+        for rook_square in rook_squares {
+            let is_kingside = king_file.is_none_or(|file| rook_square.get_file() > &file);
+            let symbol = match get_outermost_rook_square(colour, is_kingside, chessboard) {
+                Some(outermost_square) if &outermost_square == rook_square => match is_kingside {
+                    true => 'K',
+                    false => 'Q',
+                },
+                _ => rook_square.get_file().to_string().chars().next().unwrap(),
+            };
+            match colour {
+                chess_set::Colour::White => value.push(symbol),
+                chess_set::Colour::Black => value.push(symbol.to_ascii_lowercase()),
+            };
+        }
+    }
+
+    match value.is_empty() {
+        true => "-".to_string(),
+        false => value,
+    }
+}
+
+// Castling rights.
+
+fn get_back_rank_king_file(
+    colour: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> Option<chess_set::File> {
+    let rank = get_back_rank(colour);
+    let king = chess_set::Piece::new(colour, chess_set::PieceType::King);
+    chess_set::File::iter()
+        .find(|file| chessboard.get_piece(&chess_set::Square::new(rank, *file)) == Some(king))
+}
+
+/// Find the rook furthest from the king on one side of it, on the player's back rank.
+fn get_outermost_rook_square(
+    colour: chess_set::Colour,
+    is_kingside: bool,
+    chessboard: &chess_set::Chessboard,
+) -> Option<chess_set::Square> {
+    let king_file = get_back_rank_king_file(colour, chessboard)?;
+    let rank = get_back_rank(colour);
+    let rook = chess_set::Piece::new(colour, chess_set::PieceType::Rook);
+
+    let mut files: Vec<chess_set::File> = match is_kingside {
+        true => chess_set::File::iter()
+            .filter(|file| file > &king_file)
+            .collect(),
+        false => chess_set::File::iter()
+            .filter(|file| file < &king_file)
+            .collect(),
+    };
+    if is_kingside {
+        files.reverse();
+    };
+    files
+        .into_iter()
+        .map(|file| chess_set::Square::new(rank, file))
+        .find(|square| chessboard.get_piece(square) == Some(rook))
+}
+
+fn get_back_rank(colour: chess_set::Colour) -> chess_set::Rank {
+    match colour {
+        chess_set::Colour::White => chess_set::Rank::One,
+        chess_set::Colour::Black => chess_set::Rank::Eight,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+    use crate::testing::factories;
+    use rstest::rstest;
+
+    const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    #[cfg(test)]
+    mod parse_tests {
+        use super::*;
+
+        #[test]
+        fn parses_starting_position() {
+            let fen = Fen::parse(STARTING_FEN).unwrap();
+
+            assert_eq!(fen.chessboard.position, factories::chessboard().position);
+            assert_eq!(fen.to_play_colour, Colour::White);
+            assert_eq!(fen.castling_rights.len(), 4);
+            assert_eq!(fen.en_passant_square, None);
+            assert_eq!(fen.halfmove_clock, 0);
+            assert_eq!(fen.fullmove_number, 1);
+        }
+
+        #[test]
+        fn parses_en_passant_square_and_move_counters() {
+            let fen = Fen::parse("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 32").unwrap();
+
+            let black_pawn = Piece::new(Colour::Black, PieceType::Pawn);
+            let d4 = Square::new(Rank::Four, File::D);
+            assert_eq!(fen.chessboard.get_piece(&d4), Some(black_pawn));
+            assert_eq!(fen.to_play_colour, Colour::Black);
+            assert_eq!(fen.castling_rights, vec![]);
+            assert_eq!(
+                fen.en_passant_square,
+                Some(Square::new(Rank::Three, File::E))
+            );
+            assert_eq!(fen.fullmove_number, 32);
+        }
+
+        #[test]
+        fn parses_checks_given() {
+            let fen = Fen::parse("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +2+1").unwrap();
+
+            assert_eq!(fen.checks_given, Some(check::ChecksGiven::new(2, 1)));
+            assert_eq!(Fen::parse(STARTING_FEN).unwrap().checks_given, None);
+        }
+
+        #[rstest]
+        #[case::x_fen("1r2k1rr/8/8/8/8/8/8/1R2K1RR w GQkq - 0 1")]
+        #[case::shredder_fen("1r2k1rr/8/8/8/8/8/8/1R2K1RR w GBhb - 0 1")]
+        fn parses_chess960_castling_rights(#[case] fen: &str) {
+            let fen = Fen::parse(fen).unwrap();
+
+            let expected_castling_rights = vec![
+                Square::new(Rank::One, File::B),
+                Square::new(Rank::One, File::G),
+                Square::new(Rank::Eight, File::B),
+                Square::new(Rank::Eight, File::H),
+            ];
+            assert_eq!(fen.castling_rights, expected_castling_rights);
+        }
+
+        #[rstest]
+        #[case::missing_fields("8/8/8/8/8/8/8/8 w", FenError::WrongNumberOfFields(2))]
+        #[case::too_few_ranks(
+            "4k3/8/8/8/8/8/4K3 w - - 0 1",
+            FenError::InvalidPiecePlacement("4k3/8/8/8/8/8/4K3".to_string())
+        )]
+        #[case::too_many_files(
+            "4k4/8/8/8/8/8/8/4K3 w - - 0 1",
+            FenError::InvalidPiecePlacement("4k4/8/8/8/8/8/8/4K3".to_string())
+        )]
+        #[case::unknown_piece(
+            "4k3/8/8/8/8/8/8/4X3 w - - 0 1",
+            FenError::InvalidPiecePlacement("4k3/8/8/8/8/8/8/4X3".to_string())
+        )]
+        #[case::active_colour(
+            "4k3/8/8/8/8/8/8/4K3 x - - 0 1",
+            FenError::InvalidActiveColour("x".to_string())
+        )]
+        #[case::castling_rights(
+            "4k3/8/8/8/8/8/8/4K3 w KX - 0 1",
+            FenError::InvalidCastlingRights("KX".to_string())
+        )]
+        #[case::en_passant_square(
+            "4k3/8/8/8/8/8/8/4K3 w - e9 0 1",
+            FenError::InvalidEnPassantSquare("e9".to_string())
+        )]
+        #[case::move_counter(
+            "4k3/8/8/8/8/8/8/4K3 w - - x 1",
+            FenError::InvalidMoveCounter("x".to_string())
+        )]
+        #[case::fullmove_number_zero(
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 0",
+            FenError::InvalidMoveCounter("0".to_string())
+        )]
+        #[case::checks_given(
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1 3+3",
+            FenError::InvalidChecksGiven("3+3".to_string())
+        )]
+        fn errors_for_invalid_fen(#[case] fen: &str, #[case] expected_error: FenError) {
+            assert_eq!(Fen::parse(fen), Err(expected_error));
+        }
+
+        #[rstest]
+        #[case::missing_king("8/8/8/8/8/8/8/4K3 w - - 0 1")]
+        #[case::pawn_on_back_rank("4k2P/8/8/8/8/8/8/4K3 w - - 0 1")]
+        #[case::opponent_in_check("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1")]
+        #[case::castling_without_rook("4k3/8/8/8/8/8/8/4K3 w K - 0 1")]
+        #[case::en_passant_without_pawn("4k3/8/8/8/8/8/8/4K3 b - e3 0 1")]
+        fn errors_for_illegal_position(#[case] fen: &str) {
+            let result = Fen::parse(fen);
+
+            assert!(matches!(result, Err(FenError::IllegalPosition(_))));
+        }
+    }
+
+    #[cfg(test)]
+    mod write_tests {
+        use super::*;
+
+        #[rstest]
+        #[case::starting_position(STARTING_FEN)]
+        #[case::kiwipete("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")]
+        #[case::en_passant("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 32")]
+        #[case::partial_castling_rights("r3k3/8/8/8/8/8/8/4K2R w Kq - 5 40")]
+        #[case::chess960("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9")]
+        #[case::chess960_inner_rook("rr2k3/8/8/8/8/8/8/RR2K3 w Bb - 0 1")]
+        #[case::chess960_both_kinds("1r2k1rr/8/8/8/8/8/8/1R2K1RR w GQkq - 0 1")]
+        #[case::checks_given("4k3/8/8/8/8/8/8/R3K3 b - - 3 12 +2+0")]
+        fn fen_round_trips(#[case] fen: &str) {
+            let parsed_fen = Fen::parse(fen).unwrap();
+
+            assert_eq!(parsed_fen.to_string(), fen);
+        }
+
+        #[test]
+        fn describes_chessboard_history_after_double_pawn_advancement() {
+            let mut chessboard = factories::chessboard();
+            let mut updates = BTreeMap::new();
+            updates.insert(Square::new(Rank::Two, File::E), None);
+            updates.insert(
+                Square::new(Rank::Four, File::E),
+                Some(Piece::new(Colour::White, PieceType::Pawn)),
+            );
+            chessboard.update_position(updates);
+            chessboard.en_passant_square = Some(Square::new(Rank::Three, File::E));
+            let chessboard_history = vec![factories::chessboard(), chessboard];
+
+            let fen = Fen::from_chessboard_history(Colour::Black, 0, 1, &chessboard_history);
+
+            assert_eq!(
+                fen.to_string(),
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+            );
+        }
+
+        #[test]
+        fn counts_fullmove_number_from_chessboard_history() {
+            let chessboard_history = factories::chessboard_history_without_progress(4);
+
+            let fen = Fen::from_chessboard_history(Colour::Black, 0, 1, &chessboard_history);
+
+            assert_eq!(fen.halfmove_clock, 3);
+            assert_eq!(fen.fullmove_number, 2);
+        }
+
+        #[test]
+        fn carries_on_from_starting_move_counters() {
+            let chessboard_history = factories::chessboard_history_without_progress(4);
+
+            let fen = Fen::from_chessboard_history(Colour::Black, 57, 40, &chessboard_history);
+
+            assert_eq!(fen.halfmove_clock, 60);
+            assert_eq!(fen.fullmove_number, 41);
+        }
+    }
+
+    #[cfg(test)]
+    mod to_chessboard_history_tests {
+        use super::*;
+
+        #[test]
+        fn en_passant_square_is_set_on_chessboard() {
+            let fen = Fen::parse("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+
+            let chessboard_history = fen.to_chessboard_history();
+
+            assert_eq!(chessboard_history.len(), 1);
+            assert_eq!(
+                chessboard_history[0].en_passant_square,
+                Some(Square::new(Rank::Three, File::E))
+            );
+
+            let round_trip = Fen::from_chessboard_history(Colour::Black, 0, 1, &chessboard_history);
+            assert_eq!(round_trip, fen);
+        }
+
+        #[test]
+        fn lost_castling_rights_are_set_on_chessboard() {
+            let fen = Fen::parse("r3k2r/8/8/8/8/8/8/R3K2R w Kk - 0 1").unwrap();
+
+            let chessboard_history = fen.to_chessboard_history();
+
+            assert_eq!(
+                chessboard_history[0].castling_rights,
+                vec![
+                    Square::new(Rank::One, File::H),
+                    Square::new(Rank::Eight, File::H)
+                ]
+            );
+
+            let round_trip = Fen::from_chessboard_history(Colour::White, 0, 1, &chessboard_history);
+            assert_eq!(round_trip, fen);
+        }
+
+        #[test]
+        fn move_counters_round_trip_alongside_chessboard_history() {
+            let fen = Fen::parse("4k3/8/8/8/8/8/8/R3K3 w - - 57 40").unwrap();
+
+            let chessboard_history = fen.to_chessboard_history();
+
+            let round_trip = Fen::from_chessboard_history(
+                fen.to_play_colour,
+                fen.halfmove_clock,
+                fen.fullmove_number,
+                &chessboard_history,
+            );
+            assert_eq!(round_trip, fen);
+            assert_eq!(round_trip.to_string(), "4k3/8/8/8/8/8/8/R3K3 w - - 57 40");
+        }
+
+        #[test]
+        fn single_chessboard_reproduces_position_without_en_passant() {
+            let fen = Fen::parse(STARTING_FEN).unwrap();
+
+            let chessboard_history = fen.to_chessboard_history();
+
+            assert_eq!(chessboard_history.len(), 1);
+            assert_eq!(chessboard_history[0].position, fen.chessboard.position);
+            assert_eq!(chessboard_history[0].en_passant_square, None);
+        }
+    }
+}
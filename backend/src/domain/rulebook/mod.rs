@@ -1,11 +1,36 @@
+mod attacks;
 mod check;
 mod checkmate;
+mod classification;
 mod draws;
+mod fen;
 mod moves;
+mod perft;
+mod san;
+mod search_position;
 mod starting_position;
+mod tactics;
+mod uci;
+mod variants;
 
-pub use check::would_player_be_left_in_check;
-pub use checkmate::{get_legal_moves, is_player_checkmated};
-pub use draws::{is_draw, Draw};
+pub use attacks::{get_attack_counts, get_attacked_squares, get_attackers, get_defenders};
+pub use check::{would_player_be_left_in_check, ChecksGiven};
+pub use checkmate::{get_legal_moves, get_played_move, is_player_checkmated};
+pub use classification::{classify_move, MoveClassification, MoveKind};
+pub use draws::{get_claimable_draw, get_halfmove_clock, get_repetition_count, is_draw, Draw};
+pub use fen::{Fen, FenError};
 pub use moves::chess_move::{Move, MoveRule, MoveValidationError};
-pub use starting_position::get_official_starting_position;
+pub use moves::pieces::{get_possible_castling_rights, get_promotion_piece_types};
+pub use perft::{divide, perft};
+pub use san::{from_san, to_san, write_legal_moves_in_san};
+pub use search_position::{get_zobrist_hash, Outcome, SearchPosition};
+pub use starting_position::{
+    get_chess960_starting_chessboard, get_chess960_starting_position,
+    get_official_starting_chessboard, get_official_starting_position,
+};
+pub use tactics::{
+    get_discovered_attacks, get_discovered_checks, get_pins, get_skewers, DiscoveredAttack, Pin,
+    Skewer,
+};
+pub use uci::{from_uci, parse_uci, to_uci, UciError, UciMove};
+pub use variants::{Chess960, KingOfTheHill, Standard, ThreeCheck, Variant, Win};
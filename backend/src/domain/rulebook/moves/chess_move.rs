@@ -15,6 +15,9 @@ pub enum MoveValidationError {
     MoveIsNotLegalForPiece,
     CannotCaptureOwnPiece,
     CannotCaptureOpponentKing,
+
+    // Promotion.
+    PawnMustBePromoted,
 }
 
 #[derive(Clone)]
@@ -23,17 +26,14 @@ pub struct Move {
     pub from_square: chess_set::Square,
     pub to_square: chess_set::Square,
     pub translation: translation::Translation,
+    pub promote_to: Option<chess_set::PieceType>,
 }
 
 pub trait MoveRule {
     /// Test whether a certain move is valid, according to this rule.
-    fn allows_move(
-        &self,
-        chess_move: &Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
-    ) -> bool;
+    fn allows_move(&self, chess_move: &Move, chessboard_history: &[chess_set::Chessboard]) -> bool;
 
-    /// Return the outcome of playing a valid move, according to this rule.
+    /// Return the outcome of playing a valid move on a chessboard, according to this rule.
     ///
     /// Note that special rules must override this method. For example:
     /// * "en passant" involves capturing the piece at a square different to `to_square`
@@ -41,13 +41,23 @@ pub trait MoveRule {
     fn get_move_outcome(
         &self,
         chess_move: &Move,
+        chessboard: &chess_set::Chessboard,
     ) -> BTreeMap<chess_set::Square, Option<chess_set::Piece>> {
+        let _ = chessboard;
+
         let mut outcome = BTreeMap::new();
         outcome.insert(chess_move.from_square, None);
         outcome.insert(chess_move.to_square, Some(chess_move.piece));
 
         outcome
     }
+
+    /// Test whether the move may finish on a square occupied by one of the player's own pieces.
+    ///
+    /// Only castling in Chess960 allows this, since the king moves onto its rook's square.
+    fn allows_own_piece_at_to_square(&self) -> bool {
+        false
+    }
 }
 
 impl Move {
@@ -64,12 +74,24 @@ impl Move {
             from_square: from_square,
             to_square: to_square,
             translation: translation,
+            promote_to: None,
         }
     }
 
+    pub fn new_promotion(
+        piece: chess_set::Piece,
+        from_square: chess_set::Square,
+        to_square: chess_set::Square,
+        promote_to: chess_set::PieceType,
+    ) -> Self {
+        let mut chess_move = Self::new(piece, from_square, to_square);
+        chess_move.promote_to = Some(promote_to);
+        chess_move
+    }
+
     pub fn apply_if_valid(
         &self,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> Result<chess_set::Chessboard, MoveValidationError> {
         let allowing_rule = match self.validate(chessboard_history) {
             Ok(rule) => rule,
@@ -77,15 +99,18 @@ impl Move {
         };
 
         let mut chessboard = chessboard_history.last().unwrap().clone();
-        let move_outcome = allowing_rule.get_move_outcome(self);
+        let move_outcome = allowing_rule.get_move_outcome(self, &chessboard);
         chessboard.update_position(move_outcome);
+        chessboard.castling_rights =
+            pieces::get_castling_rights_after_move(&chessboard.castling_rights, self);
+        chessboard.en_passant_square = pieces::get_en_passant_square_after_move(self);
         Ok(chessboard)
     }
 
     // Queries.
     pub fn validate(
         &self,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> Result<Box<dyn MoveRule>, MoveValidationError> {
         let chessboard = chessboard_history.last().unwrap();
 
@@ -98,13 +123,28 @@ impl Move {
             return Err(MoveValidationError::PieceIsNotAtFromSquare);
         }
 
-        if let Err(error) = self.validate_occupant_of_to_square(chessboard) {
-            return Err(error);
+        if !rule.allows_own_piece_at_to_square() {
+            self.validate_occupant_of_to_square(chessboard)?;
         };
 
+        self.validate_promotion()?;
+
         Ok(rule)
     }
 
+    /// Test whether the move takes a pawn to the furthest rank, where it must be promoted.
+    pub fn is_to_promotion_rank(&self) -> bool {
+        if !(self.piece.get_piece_type() == &chess_set::PieceType::Pawn) {
+            return false;
+        };
+
+        let promotion_rank = match self.piece.get_colour() {
+            chess_set::Colour::White => &chess_set::Rank::Eight,
+            chess_set::Colour::Black => &chess_set::Rank::One,
+        };
+        self.to_square.get_rank() == promotion_rank
+    }
+
     pub fn is_obstructed(&self, chessboard: &chess_set::Chessboard) -> bool {
         if !self.translation.vector.is_straight_line() {
             return true;
@@ -126,7 +166,7 @@ impl Move {
 
     fn get_rule_that_allows_move(
         &self,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> Result<Box<dyn MoveRule>, MoveValidationError> {
         let rules = pieces::get_move_rules_for_piece(self.piece.get_piece_type());
         for rule in rules {
@@ -155,6 +195,16 @@ impl Move {
 
         Ok(())
     }
+
+    fn validate_promotion(&self) -> Result<(), MoveValidationError> {
+        let is_pawn = self.piece.get_piece_type() == &chess_set::PieceType::Pawn;
+
+        match self.promote_to {
+            Some(_) if !is_pawn => Err(MoveValidationError::MoveIsNotLegalForPiece),
+            None if self.is_to_promotion_rank() => Err(MoveValidationError::PawnMustBePromoted),
+            _ => Ok(()),
+        }
+    }
 }
 
 // Trait implementations.
@@ -183,7 +233,7 @@ mod tests {
 
             let chess_move = Move::new(piece, from_square, to_square);
 
-            let result = chess_move.validate(&vec![chessboard]);
+            let result = chess_move.validate(&[chessboard]);
 
             assert!(result.is_ok());
         }
@@ -195,7 +245,7 @@ mod tests {
             let piece = chessboard.get_piece(&square).unwrap();
             let chess_move = Move::new(piece, square.clone(), square);
 
-            let result = chess_move.validate(&vec![chessboard]);
+            let result = chess_move.validate(&[chessboard]);
 
             let expected_error = MoveValidationError::MoveIsNotLegalForPiece;
             match result {
@@ -219,7 +269,7 @@ mod tests {
             let chessboard = chess_set::Chessboard::new(starting_position);
 
             let chess_move = Move::new(piece, from_square, to_square);
-            let result = chess_move.validate(&vec![chessboard]);
+            let result = chess_move.validate(&[chessboard]);
 
             let expected_error = MoveValidationError::CannotCaptureOwnPiece;
             match result {
@@ -240,7 +290,7 @@ mod tests {
             let _ = chessboard.position.insert(to_square, Some(black_king));
 
             let chess_move = Move::new(white_pawn, from_square, to_square);
-            let result = chess_move.validate(&vec![chessboard]);
+            let result = chess_move.validate(&[chessboard]);
 
             let expected_error = MoveValidationError::CannotCaptureOpponentKing;
             match result {
@@ -265,7 +315,7 @@ mod tests {
 
             let chess_move = Move::new(piece, from_square.clone(), to_square.clone());
 
-            let result = chess_move.apply_if_valid(&vec![chessboard]);
+            let result = chess_move.apply_if_valid(&[chessboard]);
 
             let updated_chessboard = result.unwrap();
             assert_eq!(
@@ -285,7 +335,7 @@ mod tests {
             let piece = chessboard.get_piece(&square).unwrap();
             let chess_move = Move::new(piece, square.clone(), square);
 
-            let result = chess_move.apply_if_valid(&vec![chessboard]);
+            let result = chess_move.apply_if_valid(&[chessboard]);
 
             let expected_error = MoveValidationError::MoveIsNotLegalForPiece;
             assert_eq!(result, Err(expected_error))
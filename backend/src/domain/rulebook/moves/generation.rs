@@ -0,0 +1,417 @@
+use super::{chess_move, pieces};
+use crate::domain::chess_set;
+use crate::domain::rulebook::tactics;
+
+/// Generate the legal moves on the latest chessboard in a chessboard history.
+///
+/// Each piece only considers the squares it can reach (sliding along rays, jumping,
+/// pushing, castling or capturing en passant). These pseudo-legal moves are then
+/// filtered out if they would leave the player's king in check.
+///
+/// Moves are ordered by from square, then to square, in the same order as the
+/// squares of a `Chessboard`.
+pub fn generate_legal_moves(
+    player: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> Vec<chess_move::Move> {
+    let latest_chessboard = chessboard_history.last().unwrap();
+    let chessboard = chess_set::BitboardChessboard::from_chessboard(latest_chessboard);
+    let castling_rights = chess_set::Bitboard::from_squares(&latest_chessboard.castling_rights);
+
+    generate_legal_moves_for_position(
+        player,
+        &chessboard,
+        latest_chessboard.en_passant_square,
+        castling_rights,
+    )
+}
+
+/// Generate the legal moves in a position described by its chessboard and game state.
+///
+/// The castling rights are the squares of the rooks that can still (at some point)
+/// be castled with.
+pub fn generate_legal_moves_for_position(
+    player: chess_set::Colour,
+    chessboard: &chess_set::BitboardChessboard,
+    en_passant_square: Option<chess_set::Square>,
+    castling_rights: chess_set::Bitboard,
+) -> Vec<chess_move::Move> {
+    // Outside of check, only king moves, en passant captures and moves by pinned pieces
+    // could leave the king in check, so every other move is legal without trying it out.
+    let is_in_check = chessboard.is_player_in_check(&player);
+    let pins = tactics::find_pins(player, chessboard);
+
+    let mut legal_moves = vec![];
+    for from_square in chessboard.get_occupied_squares(&player).iter() {
+        let piece = chessboard.get_piece(&from_square).unwrap();
+        let pin_ray = pins
+            .iter()
+            .find(|pin| pin.pinned_square == from_square)
+            .map(|pin| chess_set::Bitboard::from_squares(&pin.ray));
+        let to_squares = match piece.get_piece_type() {
+            chess_set::PieceType::Pawn => {
+                get_pawn_targets(chessboard, &piece, &from_square, en_passant_square)
+            }
+            chess_set::PieceType::King => {
+                get_king_targets(chessboard, &piece, &from_square, castling_rights)
+            }
+            _ => get_piece_targets(chessboard, &piece, &from_square),
+        };
+
+        for to_square in to_squares.iter() {
+            let must_try_move = is_in_check
+                || piece.get_piece_type() == &chess_set::PieceType::King
+                || (piece.get_piece_type() == &chess_set::PieceType::Pawn
+                    && Some(to_square) == en_passant_square);
+            for chess_move in get_candidate_moves(piece, from_square, to_square) {
+                let is_legal = match must_try_move {
+                    true => !would_player_be_left_in_check(
+                        &chess_move,
+                        chessboard,
+                        en_passant_square,
+                        castling_rights,
+                    ),
+                    false => pin_ray.is_none_or(|ray| ray.contains(&to_square)),
+                };
+                if is_legal {
+                    legal_moves.push(chess_move);
+                };
+            }
+        }
+    }
+
+    legal_moves
+}
+
+/// Get the en passant square, if the player to move could legally capture onto it.
+///
+/// Two positions only differ by their en passant square if a capture onto it is legal.
+pub fn get_legal_en_passant_square(
+    player: chess_set::Colour,
+    chessboard: &chess_set::BitboardChessboard,
+    en_passant_square: Option<chess_set::Square>,
+) -> Option<chess_set::Square> {
+    let to_square = en_passant_square?;
+    let pawn = chess_set::Piece::new(player, chess_set::PieceType::Pawn);
+    let capturing_pawns = chessboard.get_squares_of(&player, &chess_set::PieceType::Pawn)
+        & chess_set::get_pawn_attacks(&to_square, &player.swap());
+
+    capturing_pawns
+        .iter()
+        .any(|from_square| {
+            let chess_move = chess_move::Move::new(pawn, from_square, to_square);
+            !would_player_be_left_in_check(
+                &chess_move,
+                chessboard,
+                en_passant_square,
+                chess_set::Bitboard::EMPTY,
+            )
+        })
+        .then_some(to_square)
+}
+
+/// Get the moves a piece could make between two squares.
+///
+/// There is only one such move, unless a pawn is reaching the furthest rank, in which
+/// case there is one move per piece type the pawn could be promoted to.
+pub fn get_candidate_moves(
+    piece: chess_set::Piece,
+    from_square: chess_set::Square,
+    to_square: chess_set::Square,
+) -> Vec<chess_move::Move> {
+    let chess_move = chess_move::Move::new(piece, from_square, to_square);
+    if !chess_move.is_to_promotion_rank() {
+        return vec![chess_move];
+    }
+
+    pieces::get_promotion_piece_types()
+        .into_iter()
+        .map(|promote_to| {
+            chess_move::Move::new_promotion(piece, from_square, to_square, promote_to)
+        })
+        .collect()
+}
+
+// Pseudo-legal move generators.
+
+/// Get the squares a knight, bishop, rook or queen can move to.
+fn get_piece_targets(
+    chessboard: &chess_set::BitboardChessboard,
+    piece: &chess_set::Piece,
+    from_square: &chess_set::Square,
+) -> chess_set::Bitboard {
+    let attacks =
+        chess_set::get_piece_attacks(piece, from_square, chessboard.get_all_occupied_squares());
+    attacks & get_capturable_squares(chessboard, piece.get_colour())
+}
+
+fn get_pawn_targets(
+    chessboard: &chess_set::BitboardChessboard,
+    pawn: &chess_set::Piece,
+    from_square: &chess_set::Square,
+    en_passant_square: Option<chess_set::Square>,
+) -> chess_set::Bitboard {
+    let colour = pawn.get_colour();
+    let (forward, starting_rank) = match colour {
+        chess_set::Colour::White => (1, chess_set::Rank::Two),
+        chess_set::Colour::Black => (-1, chess_set::Rank::Seven),
+    };
+    let occupied_squares = chessboard.get_all_occupied_squares();
+
+    let mut targets = chess_set::Bitboard::EMPTY;
+
+    // Pushes.
+    let rank_index = from_square.get_rank().index();
+    let file_index = from_square.get_file().index();
+    let is_on_furthest_rank = !(1..=8).contains(&(rank_index + forward));
+    let single_square = match is_on_furthest_rank {
+        true => None,
+        false => Some(chess_set::Square::from_indexes(
+            rank_index + forward,
+            file_index,
+        )),
+    };
+    if let Some(single_square) = single_square.filter(|square| !occupied_squares.contains(square)) {
+        targets.insert(&single_square);
+
+        if from_square.get_rank() == &starting_rank {
+            let double_square =
+                chess_set::Square::from_indexes(rank_index + 2 * forward, file_index);
+            if !occupied_squares.contains(&double_square) {
+                targets.insert(&double_square);
+            };
+        };
+    };
+
+    // Captures.
+    let attacks = chess_set::get_pawn_attacks(from_square, colour);
+    let opponent_squares = chessboard.get_occupied_squares(&colour.swap());
+    targets = targets | (attacks & opponent_squares & get_capturable_squares(chessboard, colour));
+
+    if let Some(en_passant_square) = en_passant_square {
+        if attacks.contains(&en_passant_square) {
+            targets.insert(&en_passant_square);
+        };
+    };
+
+    targets
+}
+
+fn get_king_targets(
+    chessboard: &chess_set::BitboardChessboard,
+    king: &chess_set::Piece,
+    from_square: &chess_set::Square,
+    castling_rights: chess_set::Bitboard,
+) -> chess_set::Bitboard {
+    let mut targets = get_piece_targets(chessboard, king, from_square);
+
+    for rook_square in castling_rights.iter() {
+        if let Some(to_square) = get_castling_target(chessboard, king, from_square, &rook_square) {
+            targets.insert(&to_square);
+        };
+    }
+
+    targets
+}
+
+/// Get the square the king would move to when castling with a rook, if it's allowed.
+///
+/// The king's destination square is checked along with all other moves, once the
+/// rook has also moved.
+fn get_castling_target(
+    chessboard: &chess_set::BitboardChessboard,
+    king: &chess_set::Piece,
+    from_square: &chess_set::Square,
+    rook_square: &chess_set::Square,
+) -> Option<chess_set::Square> {
+    let rook = chess_set::Piece::new(*king.get_colour(), chess_set::PieceType::Rook);
+    if rook_square.get_rank() != from_square.get_rank()
+        || chessboard.get_piece(rook_square) != Some(rook)
+    {
+        return None;
+    };
+
+    // Only the king and rook can be on the squares they move between.
+    let mut occupied_squares = chessboard.get_all_occupied_squares();
+    occupied_squares.remove(from_square);
+    occupied_squares.remove(rook_square);
+    if !(pieces::get_castling_path(from_square, rook_square) & occupied_squares).is_empty() {
+        return None;
+    };
+
+    // The king can't castle out of check, or through a square that is attacked.
+    let opponent = king.get_colour().swap();
+    if chessboard.is_square_attacked(from_square, &opponent)
+        || pieces::get_castling_king_path(from_square, rook_square)
+            .iter()
+            .any(|square| chessboard.is_square_attacked(&square, &opponent))
+    {
+        return None;
+    };
+
+    Some(pieces::get_castling_to_square(from_square, rook_square))
+}
+
+/// Get the squares a player's piece could move to, if it could reach them.
+///
+/// The opponent's king can never be captured.
+fn get_capturable_squares(
+    chessboard: &chess_set::BitboardChessboard,
+    colour: &chess_set::Colour,
+) -> chess_set::Bitboard {
+    let own_squares = chessboard.get_occupied_squares(colour);
+    let opponent_king_squares =
+        chessboard.get_squares_of(&colour.swap(), &chess_set::PieceType::King);
+    !(own_squares | opponent_king_squares)
+}
+
+// Legality.
+
+fn would_player_be_left_in_check(
+    chess_move: &chess_move::Move,
+    chessboard: &chess_set::BitboardChessboard,
+    en_passant_square: Option<chess_set::Square>,
+    castling_rights: chess_set::Bitboard,
+) -> bool {
+    let mut trial_chessboard = *chessboard;
+    let piece = chess_move.piece;
+    trial_chessboard.remove_piece(&chess_move.from_square);
+
+    let mut to_square = chess_move.to_square;
+    match piece.get_piece_type() {
+        chess_set::PieceType::Pawn if Some(chess_move.to_square) == en_passant_square => {
+            let captured_square = chess_set::Square::new(
+                *chess_move.from_square.get_rank(),
+                *chess_move.to_square.get_file(),
+            );
+            trial_chessboard.remove_piece(&captured_square);
+        }
+        chess_set::PieceType::King => {
+            if let Some(rook_square) = pieces::get_castling_rook_square(chess_move, castling_rights)
+            {
+                let (king_to_square, rook_to_square) =
+                    pieces::get_castled_squares(&chess_move.from_square, &rook_square);
+                let rook = trial_chessboard.remove_piece(&rook_square).unwrap();
+                trial_chessboard.put_piece(&rook_to_square, &rook);
+                to_square = king_to_square;
+            };
+        }
+        _ => {}
+    };
+
+    let moved_piece = match chess_move.promote_to {
+        Some(promote_to) => chess_set::Piece::new(*piece.get_colour(), promote_to),
+        None => piece,
+    };
+    trial_chessboard.put_piece(&to_square, &moved_piece);
+
+    trial_chessboard.is_player_in_check(piece.get_colour())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::rulebook::{check, uci, Fen};
+    use rstest::rstest;
+
+    /// Get the legal moves by validating a move between every pair of squares.
+    fn validate_all_moves(
+        player: chess_set::Colour,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Vec<String> {
+        let chessboard = chessboard_history.last().unwrap();
+
+        let mut legal_moves = vec![];
+        for (from_square, piece) in chessboard.get_pieces(player) {
+            for to_square in chessboard.position.keys() {
+                for chess_move in get_candidate_moves(piece, from_square, *to_square) {
+                    if check::would_player_be_left_in_check(&chess_move, chessboard_history)
+                        == Ok(false)
+                        && chess_move.validate(chessboard_history).is_ok()
+                    {
+                        legal_moves.push(uci::to_uci(&chess_move));
+                    };
+                }
+            }
+        }
+        legal_moves
+    }
+
+    #[rstest]
+    #[case::starting_position("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")]
+    #[case::kiwipete("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")]
+    #[case::kiwipete_black("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1")]
+    #[case::pinned_pawns("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1")]
+    #[case::promotions("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")]
+    #[case::black_promotions("r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1")]
+    #[case::en_passant("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")]
+    #[case::en_passant_pin("8/8/8/KPp4r/8/8/8/4k3 w - c6 0 2")]
+    #[case::castling_through_check("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1")]
+    #[case::castling_out_of_check("r3k2r/8/8/8/4r3/8/8/R3K2R w KQkq - 0 1")]
+    #[case::checked_by_knight("rnbqkbnr/pppp1ppp/8/8/8/3n4/PPPPPPPP/RNBQKBNR w KQkq - 0 1")]
+    #[case::chess960("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9")]
+    #[case::chess960_adjacent_rook("1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1")]
+    #[case::chess960_king_in_place("1r4kr/8/8/8/8/8/8/1R4KR b KQkq - 0 1")]
+    fn generates_same_moves_as_validating_every_move(#[case] fen: &str) {
+        let fen = Fen::parse(fen).unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let generated_moves: Vec<String> =
+            generate_legal_moves(fen.to_play_colour, &chessboard_history)
+                .iter()
+                .map(uci::to_uci)
+                .collect();
+
+        assert_eq!(
+            generated_moves,
+            validate_all_moves(fen.to_play_colour, &chessboard_history)
+        );
+    }
+
+    #[test]
+    fn cannot_castle_through_attacked_square() {
+        let fen = Fen::parse("r3k2r/8/8/8/8/8/5r2/R3K2R w KQkq - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let generated_moves: Vec<String> =
+            generate_legal_moves(chess_set::Colour::White, &chessboard_history)
+                .iter()
+                .map(uci::to_uci)
+                .collect();
+
+        assert!(generated_moves.contains(&"e1c1".to_string()));
+        assert!(!generated_moves.contains(&"e1g1".to_string()));
+    }
+
+    #[test]
+    fn chess960_castles_are_written_as_king_moving_onto_rook() {
+        let fen = Fen::parse("1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let generated_moves: Vec<String> =
+            generate_legal_moves(chess_set::Colour::White, &chessboard_history)
+                .iter()
+                .map(uci::to_uci)
+                .collect();
+
+        assert!(generated_moves.contains(&"f1g1".to_string()));
+        assert!(generated_moves.contains(&"f1b1".to_string()));
+        assert!(!generated_moves.contains(&"f1c1".to_string()));
+    }
+
+    #[test]
+    fn can_capture_en_passant() {
+        let fen =
+            Fen::parse("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let generated_moves: Vec<String> =
+            generate_legal_moves(chess_set::Colour::White, &chessboard_history)
+                .iter()
+                .map(uci::to_uci)
+                .collect();
+
+        assert!(generated_moves.contains(&"e5f6".to_string()));
+        assert!(!generated_moves.contains(&"e5d6".to_string()));
+    }
+}
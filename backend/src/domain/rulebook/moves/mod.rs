@@ -1,3 +1,4 @@
 pub mod chess_move;
+pub mod generation;
 pub mod pieces;
 pub mod translation;
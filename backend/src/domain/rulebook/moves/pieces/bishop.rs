@@ -12,7 +12,7 @@ impl chess_move::MoveRule for AllowDiagonalTranslations {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
         let is_diagonal = diagonals().contains(&chess_move.translation.vector);
         let is_obstructed = chess_move.is_obstructed(chessboard_history.last().unwrap());
@@ -41,7 +41,7 @@ mod tests {
 
     fn is_move_allowed(chess_move: chess_move::Move, chessboard: &Chessboard) -> bool {
         let mut rules = get_bishop_move_rules();
-        rules.any(|rule| rule.allows_move(&chess_move, &vec![chessboard.clone()]))
+        rules.any(|rule| rule.allows_move(&chess_move, std::slice::from_ref(chessboard)))
     }
 
     #[rstest]
@@ -4,9 +4,11 @@ use crate::domain::rulebook::moves::chess_move;
 use std::vec;
 
 pub fn get_king_move_rules() -> vec::IntoIter<Box<dyn chess_move::MoveRule>> {
+    // Castling comes first, since in Chess960 the king might castle by moving onto
+    // its rook on the next square.
     vec![
-        Box::new(AllowSingleSquareAnyDirection) as Box<dyn chess_move::MoveRule>,
         Box::new(AllowCastle) as Box<dyn chess_move::MoveRule>,
+        Box::new(AllowSingleSquareAnyDirection) as Box<dyn chess_move::MoveRule>,
     ]
     .into_iter()
 }
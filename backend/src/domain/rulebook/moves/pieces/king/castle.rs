@@ -1,5 +1,4 @@
 use crate::domain::chess_set;
-use crate::domain::rulebook::check;
 use crate::domain::rulebook::moves::chess_move;
 use std::collections::BTreeMap;
 
@@ -9,38 +8,40 @@ impl chess_move::MoveRule for AllowCastle {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
         let chessboard = chessboard_history.last().unwrap();
+        let castling_rights = chess_set::Bitboard::from_squares(&chessboard.castling_rights);
 
-        // Check the king's move is valid.
-        if !is_king_moving_from_valid_square(chess_move) {
+        // Check the king's move castles with a rook that can still be castled with.
+        let Some(rook_square) = get_castling_rook_square(chess_move, castling_rights) else {
             return false;
         };
-        if !is_king_moving_to_valid_square(chess_move) {
-            return false;
-        };
-        if has_piece_at_square_previously_moved(chess_move, chessboard_history) {
-            return false;
-        };
-        if chess_move.is_obstructed(chessboard) {
+        let rook =
+            chess_set::Piece::new(*chess_move.piece.get_colour(), chess_set::PieceType::Rook);
+        if !(chessboard.get_piece(&rook_square) == Some(rook)) {
             return false;
         };
 
-        // Check the corresponding rook move is valid.
-        let rook_move = get_corresponding_legal_rook_move(chess_move);
-        if has_piece_at_square_previously_moved(&rook_move, chessboard_history) {
-            return false;
-        }
-        if rook_move.is_obstructed(chessboard) {
+        // Check nothing but the king and rook is in the way.
+        let bitboard_chessboard = chess_set::BitboardChessboard::from_chessboard(chessboard);
+        let mut occupied_squares = bitboard_chessboard.get_all_occupied_squares();
+        occupied_squares.remove(&chess_move.from_square);
+        occupied_squares.remove(&rook_square);
+        if !(get_castling_path(&chess_move.from_square, &rook_square) & occupied_squares).is_empty()
+        {
             return false;
         };
 
         // Check the king isn't moving out of or through check.
-        if check::is_player_in_check(chess_move.piece.get_colour(), chessboard.clone()) {
+        let opponent = chess_move.piece.get_colour().swap();
+        if bitboard_chessboard.is_square_attacked(&chess_move.from_square, &opponent) {
             return false;
         }
-        if is_king_moving_from_through_check(chess_move, chessboard_history) {
+        if get_castling_king_path(&chess_move.from_square, &rook_square)
+            .iter()
+            .any(|square| bitboard_chessboard.is_square_attacked(&square, &opponent))
+        {
             return false;
         }
 
@@ -50,117 +51,217 @@ impl chess_move::MoveRule for AllowCastle {
     fn get_move_outcome(
         &self,
         chess_move: &chess_move::Move,
+        chessboard: &chess_set::Chessboard,
     ) -> BTreeMap<chess_set::Square, Option<chess_set::Piece>> {
+        let castling_rights = chess_set::Bitboard::from_squares(&chessboard.castling_rights);
+        let rook_square = get_castling_rook_square(chess_move, castling_rights)
+            .expect("Castle should be validated first!");
+        let (king_to_square, rook_to_square) =
+            get_castled_squares(&chess_move.from_square, &rook_square);
+        let rook = chessboard.get_piece(&rook_square);
+
+        // The king or rook may finish on the other's starting square, so both are
+        // removed before either is put down.
         let mut outcome = BTreeMap::new();
         outcome.insert(chess_move.from_square, None);
-        outcome.insert(chess_move.to_square, Some(chess_move.piece));
-
-        let rook_move = get_corresponding_legal_rook_move(chess_move);
-        outcome.insert(rook_move.from_square, None);
-        outcome.insert(rook_move.to_square, Some(rook_move.piece));
+        outcome.insert(rook_square, None);
+        outcome.insert(king_to_square, Some(chess_move.piece));
+        outcome.insert(rook_to_square, rook);
 
         outcome
     }
+
+    fn allows_own_piece_at_to_square(&self) -> bool {
+        true
+    }
 }
 
-fn is_king_moving_from_valid_square(chess_move: &chess_move::Move) -> bool {
-    let valid_file = chess_move.from_square.get_file() == &chess_set::File::E;
+/// Get the squares of the rooks that could be castled with, judging only by where the pieces are.
+///
+/// This is every rook on its player's back rank, with its king also on the back rank.
+/// In standard chess, only the rooks in the corners are left there with the king on the
+/// e-file, but in Chess960 the king and rooks can start on any file.
+pub fn get_possible_castling_rights(chessboard: &chess_set::Chessboard) -> Vec<chess_set::Square> {
+    let mut castling_rights = vec![];
+
+    for colour in [chess_set::Colour::White, chess_set::Colour::Black] {
+        let rank = get_back_rank(colour);
+        let king = chess_set::Piece::new(colour, chess_set::PieceType::King);
+        let is_king_on_back_rank = chess_set::File::iter()
+            .any(|file| chessboard.get_piece(&chess_set::Square::new(rank, file)) == Some(king));
+        if !is_king_on_back_rank {
+            continue;
+        };
 
-    let actual_rank = chess_move.from_square.get_rank();
-    let valid_rank = match chess_move.piece.get_colour() {
-        chess_set::Colour::White => actual_rank == &chess_set::Rank::One,
-        chess_set::Colour::Black => actual_rank == &chess_set::Rank::Eight,
+        let rook = chess_set::Piece::new(colour, chess_set::PieceType::Rook);
+        for file in chess_set::File::iter() {
+            let rook_square = chess_set::Square::new(rank, file);
+            if chessboard.get_piece(&rook_square) == Some(rook) {
+                castling_rights.push(rook_square);
+            };
+        }
+    }
+
+    castling_rights.sort();
+    castling_rights
+}
+
+/// Get the castling rights that remain after a move.
+///
+/// Castling rights are lost permanently once the king or the relevant rook has moved,
+/// or the rook has been captured.
+pub fn get_castling_rights_after_move(
+    castling_rights: &[chess_set::Square],
+    chess_move: &chess_move::Move,
+) -> Vec<chess_set::Square> {
+    let is_king_move = chess_move.piece.get_piece_type() == &chess_set::PieceType::King;
+    let king_rank = chess_move.from_square.get_rank();
+
+    castling_rights
+        .iter()
+        .filter(|rook_square| {
+            let is_kings_rook = is_king_move && rook_square.get_rank() == king_rank;
+            !(is_kings_rook
+                || rook_square == &&chess_move.from_square
+                || rook_square == &&chess_move.to_square)
+        })
+        .copied()
+        .collect()
+}
+
+/// Get the square of the rook a king's move castles with, if the move is a castle.
+///
+/// See `get_castling_to_square` for how castles are written as moves.
+pub fn get_castling_rook_square(
+    chess_move: &chess_move::Move,
+    castling_rights: chess_set::Bitboard,
+) -> Option<chess_set::Square> {
+    if !(chess_move.piece.get_piece_type() == &chess_set::PieceType::King) {
+        return None;
+    };
+    let back_rank = get_back_rank(*chess_move.piece.get_colour());
+    if chess_move.from_square.get_rank() != &back_rank
+        || chess_move.to_square.get_rank() != &back_rank
+    {
+        return None;
     };
 
-    valid_rank && valid_file
+    castling_rights
+        .iter()
+        .filter(|rook_square| rook_square.get_rank() == &back_rank)
+        .find(|rook_square| {
+            get_castling_to_square(&chess_move.from_square, rook_square) == chess_move.to_square
+        })
 }
 
-fn is_king_moving_to_valid_square(chess_move: &chess_move::Move) -> bool {
-    let valid_rank = chess_move.to_square.get_rank() == chess_move.from_square.get_rank();
+/// Get the square a castle is written as moving the king to.
+///
+/// When the king and rook start on their standard squares, this is the square the
+/// king finishes on (e.g. `e1g1`). Otherwise, as in Chess960, it is the rook's square
+/// (e.g. `f1h1`), since the king might only move one square, or not at all.
+pub fn get_castling_to_square(
+    king_square: &chess_set::Square,
+    rook_square: &chess_set::Square,
+) -> chess_set::Square {
+    let is_standard_castle = king_square.get_file() == &chess_set::File::E
+        && [chess_set::File::A, chess_set::File::H].contains(rook_square.get_file());
+    match is_standard_castle {
+        true => get_castled_squares(king_square, rook_square).0,
+        false => *rook_square,
+    }
+}
 
-    let actual_file = chess_move.to_square.get_file();
-    let valid_file = actual_file == &chess_set::File::C || actual_file == &chess_set::File::G;
+/// Get the squares the king and rook finish on after castling.
+///
+/// Wherever they started, the king finishes on the c-file and the rook on the d-file
+/// when castling queenside, and on the g-file and f-file when castling kingside.
+pub fn get_castled_squares(
+    king_square: &chess_set::Square,
+    rook_square: &chess_set::Square,
+) -> (chess_set::Square, chess_set::Square) {
+    let rank = *king_square.get_rank();
+    let (king_file, rook_file) = match rook_square.get_file() < king_square.get_file() {
+        true => (chess_set::File::C, chess_set::File::D), // Queenside castle.
+        false => (chess_set::File::G, chess_set::File::F), // Kingside castle.
+    };
+    (
+        chess_set::Square::new(rank, king_file),
+        chess_set::Square::new(rank, rook_file),
+    )
+}
 
-    valid_rank && valid_file
+/// Get the squares that must be empty to castle, other than for the king and rook.
+///
+/// These are the squares between where the king and rook start and finish.
+pub fn get_castling_path(
+    king_square: &chess_set::Square,
+    rook_square: &chess_set::Square,
+) -> chess_set::Bitboard {
+    let (king_to_square, rook_to_square) = get_castled_squares(king_square, rook_square);
+    let file_indexes = [king_square, rook_square, &king_to_square, &rook_to_square]
+        .map(|square| square.get_file().index());
+
+    let mut path = chess_set::Bitboard::EMPTY;
+    for file_index in *file_indexes.iter().min().unwrap()..=*file_indexes.iter().max().unwrap() {
+        let square = chess_set::Square::new(
+            *king_square.get_rank(),
+            chess_set::File::from_index(file_index),
+        );
+        path.insert(&square);
+    }
+    path
 }
 
-fn is_king_moving_from_through_check(
-    chess_move: &chess_move::Move,
-    chessboard_history: &Vec<chess_set::Chessboard>,
-) -> bool {
-    let from_file_index = chess_move.from_square.get_file().index();
-    let to_file_index = chess_move.to_square.get_file().index();
+/// Get the squares the king passes through when castling, which can't be attacked.
+///
+/// The king's starting and finishing squares are left out, since they are checked
+/// like those of any other king move.
+pub fn get_castling_king_path(
+    king_square: &chess_set::Square,
+    rook_square: &chess_set::Square,
+) -> chess_set::Bitboard {
+    let (king_to_square, _) = get_castled_squares(king_square, rook_square);
+    let from_file_index = king_square.get_file().index();
+    let to_file_index = king_to_square.get_file().index();
 
     let file_index_range = match from_file_index < to_file_index {
         true => (from_file_index + 1)..to_file_index,
         false => (to_file_index + 1)..from_file_index,
     };
 
+    let mut path = chess_set::Bitboard::EMPTY;
     for file_index in file_index_range {
-        let middle_square = chess_set::Square::new(
-            chess_move.from_square.get_rank().clone(),
+        let square = chess_set::Square::new(
+            *king_square.get_rank(),
             chess_set::File::from_index(file_index),
         );
-        let intermediary_move = chess_move::Move::new(
-            chess_move.piece.clone(),
-            chess_move.from_square,
-            middle_square,
-        );
-        match check::would_player_be_left_in_check(&intermediary_move, chessboard_history) {
-            Ok(true) => return true,
-            _ => continue,
-        }
+        path.insert(&square);
     }
-    false
+    path
 }
 
-fn has_piece_at_square_previously_moved(
-    chess_move: &chess_move::Move,
-    chessboard_history: &Vec<chess_set::Chessboard>,
-) -> bool {
-    for chessboard in chessboard_history.into_iter() {
-        let piece = chessboard.get_piece(&chess_move.from_square);
-        if !(piece == Some(chess_move.piece)) {
-            return true;
-        }
+fn get_back_rank(colour: chess_set::Colour) -> chess_set::Rank {
+    match colour {
+        chess_set::Colour::White => chess_set::Rank::One,
+        chess_set::Colour::Black => chess_set::Rank::Eight,
     }
-    false
-}
-
-fn get_corresponding_legal_rook_move(king_move: &chess_move::Move) -> chess_move::Move {
-    let rank = king_move.from_square.get_rank();
-
-    let from_file = match king_move.to_square.get_file() {
-        chess_set::File::C => chess_set::File::A, // Queenside castle.
-        chess_set::File::G => chess_set::File::H, // Kingside castle.
-        _ => panic!("King's move should be validated first!"),
-    };
-
-    let to_file = match king_move.to_square.get_file() {
-        chess_set::File::C => chess_set::File::D, // Queenside castle.
-        chess_set::File::G => chess_set::File::F, // Kingside castle.
-        _ => panic!("King's move should be validated first!"),
-    };
-
-    let from_square = chess_set::Square::new(rank.clone(), from_file.clone());
-    let to_square = chess_set::Square::new(rank.clone(), to_file.clone());
-    let rook = chess_set::Piece::new(
-        king_move.piece.get_colour().clone(),
-        chess_set::PieceType::Rook,
-    );
-
-    chess_move::Move::new(rook, from_square, to_square)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::AllowCastle;
+    use super::{get_castling_rights_after_move, get_possible_castling_rights, AllowCastle};
     use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
     use crate::domain::rulebook::moves::chess_move;
     use crate::domain::rulebook::moves::chess_move::MoveRule;
     use rstest::rstest;
     use std::collections::BTreeMap;
 
+    fn chessboard_with_castling_rights(starting_position: BTreeMap<Square, Piece>) -> Chessboard {
+        let mut chessboard = Chessboard::new(starting_position);
+        chessboard.castling_rights = get_possible_castling_rights(&chessboard);
+        chessboard
+    }
+
     #[rstest]
     #[case::queenside(File::C, File::A, File::D)]
     #[case::kingside(File::G, File::H, File::F)]
@@ -179,14 +280,14 @@ mod tests {
         let white_rook = Piece::new(Colour::White, PieceType::Rook);
         starting_position.insert(rook_from_square, white_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, king_to_file);
         let castle = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        assert!(AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(AllowCastle.allows_move(&castle, std::slice::from_ref(&chessboard)));
 
-        let outcome = AllowCastle.get_move_outcome(&castle);
+        let outcome = AllowCastle.get_move_outcome(&castle, &chessboard);
         assert_eq!(outcome.get(&king_from_square).unwrap(), &None);
         assert_eq!(outcome.get(&rook_from_square).unwrap(), &None);
         assert_eq!(outcome.get(&king_to_square).unwrap(), &Some(white_king));
@@ -212,14 +313,14 @@ mod tests {
         let black_rook = Piece::new(Colour::Black, PieceType::Rook);
         starting_position.insert(rook_from_square, black_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::Eight, king_to_file);
         let castle = chess_move::Move::new(black_king, king_from_square, king_to_square);
 
-        assert!(AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(AllowCastle.allows_move(&castle, std::slice::from_ref(&chessboard)));
 
-        let outcome = AllowCastle.get_move_outcome(&castle);
+        let outcome = AllowCastle.get_move_outcome(&castle, &chessboard);
         assert_eq!(outcome.get(&king_from_square).unwrap(), &None);
         assert_eq!(outcome.get(&rook_from_square).unwrap(), &None);
         assert_eq!(outcome.get(&king_to_square).unwrap(), &Some(black_king));
@@ -227,6 +328,53 @@ mod tests {
         assert_eq!(outcome.get(&rook_to_square).unwrap(), &Some(black_rook));
     }
 
+    #[test]
+    fn chess960_king_can_castle_onto_adjacent_rook() {
+        let mut starting_position = BTreeMap::new();
+
+        let king_from_square = Square::new(Rank::One, File::F);
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        starting_position.insert(king_from_square, white_king);
+
+        let rook_from_square = Square::new(Rank::One, File::G);
+        let white_rook = Piece::new(Colour::White, PieceType::Rook);
+        starting_position.insert(rook_from_square, white_rook);
+
+        let chessboard = chessboard_with_castling_rights(starting_position);
+
+        // The castle is written as the king moving onto the rook.
+        let castle = chess_move::Move::new(white_king, king_from_square, rook_from_square);
+
+        assert!(AllowCastle.allows_move(&castle, std::slice::from_ref(&chessboard)));
+
+        let outcome = AllowCastle.get_move_outcome(&castle, &chessboard);
+        assert_eq!(outcome.get(&king_from_square).unwrap(), &Some(white_rook));
+        assert_eq!(outcome.get(&rook_from_square).unwrap(), &Some(white_king));
+    }
+
+    #[test]
+    fn chess960_castle_disallowed_if_rook_destination_is_occupied() {
+        let mut starting_position = BTreeMap::new();
+
+        let king_from_square = Square::new(Rank::Eight, File::B);
+        let black_king = Piece::new(Colour::Black, PieceType::King);
+        starting_position.insert(king_from_square, black_king);
+
+        let rook_from_square = Square::new(Rank::Eight, File::A);
+        let black_rook = Piece::new(Colour::Black, PieceType::Rook);
+        starting_position.insert(rook_from_square, black_rook);
+
+        let blocking_queen_square = Square::new(Rank::Eight, File::D);
+        let black_queen = Piece::new(Colour::Black, PieceType::Queen);
+        starting_position.insert(blocking_queen_square, black_queen);
+
+        let chessboard = chessboard_with_castling_rights(starting_position);
+
+        let castle = chess_move::Move::new(black_king, king_from_square, rook_from_square);
+
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
+    }
+
     #[test]
     fn castle_disallowed_if_player_is_in_check() {
         let mut starting_position = BTreeMap::new();
@@ -243,12 +391,12 @@ mod tests {
         let white_rook = Piece::new(Colour::Black, PieceType::Queen);
         starting_position.insert(black_queen_square, white_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, File::C);
         let castle = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[rstest]
@@ -269,12 +417,12 @@ mod tests {
         let white_rook = Piece::new(Colour::Black, PieceType::Queen);
         starting_position.insert(black_queen_square, white_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, File::C);
         let castle = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
@@ -293,12 +441,12 @@ mod tests {
         let white_rook = Piece::new(Colour::White, PieceType::Queen);
         starting_position.insert(white_queen_square, white_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, File::C);
         let castle = chess_move::Move::new(black_king, king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
@@ -313,12 +461,12 @@ mod tests {
         let white_rook = Piece::new(Colour::White, PieceType::Rook);
         starting_position.insert(rook_from_square, white_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, File::C);
         let castle = chess_move::Move::new(white_king, invalid_king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
@@ -333,42 +481,42 @@ mod tests {
         let black_rook = Piece::new(Colour::Black, PieceType::Rook);
         starting_position.insert(rook_from_square, black_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let invalid_king_to_square = Square::new(Rank::Eight, File::D);
         let castle = chess_move::Move::new(black_king, king_from_square, invalid_king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
     fn castle_disallowed_if_king_has_already_moved() {
-        let mut previous_position = BTreeMap::new();
+        let mut starting_position = BTreeMap::new();
 
-        let invalid_king_from_square = Square::new(Rank::Eight, File::D);
+        let king_square = Square::new(Rank::Eight, File::E);
         let black_king = Piece::new(Colour::Black, PieceType::King);
-        previous_position.insert(invalid_king_from_square, black_king.clone());
+        starting_position.insert(king_square, black_king);
 
         let rook_from_square = Square::new(Rank::Eight, File::A);
         let black_rook = Piece::new(Colour::Black, PieceType::Rook);
-        previous_position.insert(rook_from_square, black_rook);
-
-        let previous_chessboard = Chessboard::new(previous_position);
-        let mut current_chessboard = previous_chessboard.clone();
-
-        let mut position_updates = BTreeMap::new();
-        let valid_king_from_square = Square::new(Rank::Eight, File::E);
-        position_updates.insert(invalid_king_from_square, None);
-        position_updates.insert(valid_king_from_square, Some(black_king));
+        starting_position.insert(rook_from_square, black_rook);
 
-        current_chessboard.update_position(position_updates);
-        let chessboard_history = vec![previous_chessboard, current_chessboard];
+        // Move the king away and back again, so it is on its starting square.
+        let mut chessboard = chessboard_with_castling_rights(starting_position);
+        let other_king_square = Square::new(Rank::Eight, File::D);
+        for (from_square, to_square) in [
+            (king_square, other_king_square),
+            (other_king_square, king_square),
+        ] {
+            let king_move = chess_move::Move::new(black_king, from_square, to_square);
+            chessboard.castling_rights =
+                get_castling_rights_after_move(&chessboard.castling_rights, &king_move);
+        }
 
-        let valid_king_to_square = Square::new(Rank::Eight, File::C);
-        let castle =
-            chess_move::Move::new(black_king, valid_king_from_square, valid_king_to_square);
+        let king_to_square = Square::new(Rank::Eight, File::C);
+        let castle = chess_move::Move::new(black_king, king_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &chessboard_history));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
@@ -387,12 +535,12 @@ mod tests {
         let white_rook = Piece::new(Colour::White, PieceType::Knight);
         starting_position.insert(blocking_knight_square, white_rook);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, File::C);
         let castle = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
@@ -403,42 +551,75 @@ mod tests {
         let white_king = Piece::new(Colour::White, PieceType::King);
         starting_position.insert(king_from_square, white_king);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::One, File::C);
         let castle = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 
     #[test]
     fn castle_disallowed_if_rook_has_already_moved() {
-        let mut previous_position = BTreeMap::new();
+        let mut starting_position = BTreeMap::new();
 
-        let valid_king_from_square = Square::new(Rank::Eight, File::E);
+        let king_from_square = Square::new(Rank::Eight, File::E);
         let black_king = Piece::new(Colour::Black, PieceType::King);
-        previous_position.insert(valid_king_from_square, black_king);
+        starting_position.insert(king_from_square, black_king);
 
-        let invalid_rook_from_square = Square::new(Rank::Eight, File::B);
+        let rook_square = Square::new(Rank::Eight, File::A);
         let black_rook = Piece::new(Colour::Black, PieceType::Rook);
-        previous_position.insert(invalid_rook_from_square, black_rook.clone());
+        starting_position.insert(rook_square, black_rook);
+
+        let mut chessboard = chessboard_with_castling_rights(starting_position);
+        let rook_move =
+            chess_move::Move::new(black_rook, rook_square, Square::new(Rank::Eight, File::B));
+        chessboard.castling_rights =
+            get_castling_rights_after_move(&chessboard.castling_rights, &rook_move);
+
+        let king_to_square = Square::new(Rank::Eight, File::C);
+        let castle = chess_move::Move::new(black_king, king_from_square, king_to_square);
+
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
+    }
+
+    #[test]
+    fn castle_disallowed_without_castling_rights() {
+        let mut starting_position = BTreeMap::new();
 
-        let previous_chessboard = Chessboard::new(previous_position);
-        let mut current_chessboard = previous_chessboard.clone();
+        let king_from_square = Square::new(Rank::One, File::E);
+        let white_king = Piece::new(Colour::White, PieceType::King);
+        starting_position.insert(king_from_square, white_king);
+
+        let rook_from_square = Square::new(Rank::One, File::H);
+        let white_rook = Piece::new(Colour::White, PieceType::Rook);
+        starting_position.insert(rook_from_square, white_rook);
+
+        // E.g. a position set up without castling rights.
+        let chessboard = Chessboard::new(starting_position);
 
-        let mut position_updates = BTreeMap::new();
-        let valid_rook_from_square = Square::new(Rank::Eight, File::A);
-        position_updates.insert(invalid_rook_from_square, None);
-        position_updates.insert(valid_rook_from_square, Some(black_rook));
+        let king_to_square = Square::new(Rank::One, File::G);
+        let castle = chess_move::Move::new(white_king, king_from_square, king_to_square);
 
-        current_chessboard.update_position(position_updates);
-        let chessboard_history = vec![previous_chessboard, current_chessboard];
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
+    }
 
-        let valid_king_to_square = Square::new(Rank::Eight, File::C);
-        let castle =
-            chess_move::Move::new(black_king, valid_king_from_square, valid_king_to_square);
+    #[test]
+    fn capturing_rook_loses_castling_right() {
+        let castling_rights = vec![
+            Square::new(Rank::Eight, File::A),
+            Square::new(Rank::Eight, File::H),
+        ];
+        let white_bishop = Piece::new(Colour::White, PieceType::Bishop);
+        let capture = chess_move::Move::new(
+            white_bishop,
+            Square::new(Rank::Two, File::B),
+            Square::new(Rank::Eight, File::H),
+        );
+
+        let castling_rights = get_castling_rights_after_move(&castling_rights, &capture);
 
-        assert!(!AllowCastle.allows_move(&castle, &chessboard_history));
+        assert_eq!(castling_rights, vec![Square::new(Rank::Eight, File::A)]);
     }
 
     #[test]
@@ -457,11 +638,11 @@ mod tests {
         let white_knight = Piece::new(Colour::White, PieceType::Knight);
         starting_position.insert(blocking_knight_square, white_knight);
 
-        let chessboard = Chessboard::new(starting_position);
+        let chessboard = chessboard_with_castling_rights(starting_position);
 
         let king_to_square = Square::new(Rank::Eight, File::C);
         let castle = chess_move::Move::new(black_king, king_from_square, king_to_square);
 
-        assert!(!AllowCastle.allows_move(&castle, &vec![chessboard]));
+        assert!(!AllowCastle.allows_move(&castle, &[chessboard]));
     }
 }
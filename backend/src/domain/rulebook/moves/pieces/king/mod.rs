@@ -3,3 +3,7 @@ mod castle;
 mod single_square_any_direction;
 
 pub use all_rules::get_king_move_rules;
+pub use castle::{
+    get_castled_squares, get_castling_king_path, get_castling_path, get_castling_rights_after_move,
+    get_castling_rook_square, get_castling_to_square, get_possible_castling_rights,
+};
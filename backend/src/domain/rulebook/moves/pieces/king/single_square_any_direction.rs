@@ -8,7 +8,7 @@ impl chess_move::MoveRule for AllowSingleSquareAnyDirection {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
         let _ = chessboard_history;
 
@@ -59,7 +59,7 @@ mod tests {
         let chessboard = factories::chessboard();
         let chess_move = chess_move::Move::new(king, from_square, to_square);
 
-        assert!(AllowSingleSquareAnyDirection.allows_move(&chess_move, &vec![chessboard]));
+        assert!(AllowSingleSquareAnyDirection.allows_move(&chess_move, &[chessboard]));
     }
 
     #[rstest]
@@ -72,6 +72,6 @@ mod tests {
         let chessboard = factories::chessboard();
         let chess_move = chess_move::Move::new(king, from_square, to_square);
 
-        assert!(!AllowSingleSquareAnyDirection.allows_move(&chess_move, &vec![chessboard]));
+        assert!(!AllowSingleSquareAnyDirection.allows_move(&chess_move, &[chessboard]));
     }
 }
@@ -7,3 +7,10 @@ mod queen;
 mod rook;
 
 pub use all_rules::get_move_rules_for_piece;
+pub use king::{
+    get_castled_squares, get_castling_king_path, get_castling_path, get_castling_rights_after_move,
+    get_castling_rook_square, get_castling_to_square, get_possible_castling_rights,
+};
+pub use pawn::{
+    get_double_advancement_squares, get_en_passant_square_after_move, get_promotion_piece_types,
+};
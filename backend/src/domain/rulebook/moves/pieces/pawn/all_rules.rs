@@ -1,6 +1,7 @@
 use super::diagonal_capture;
 use super::double_square_forward;
 use super::en_passant;
+use super::promotion;
 use super::single_square_forward;
 use crate::domain::rulebook::moves::chess_move;
 use std::vec;
@@ -11,6 +12,7 @@ pub fn get_pawn_move_rules() -> vec::IntoIter<Box<dyn chess_move::MoveRule>> {
         Box::new(double_square_forward::AllowDoubleSquareForward) as Box<dyn chess_move::MoveRule>,
         Box::new(diagonal_capture::AllowDiagonalCapture) as Box<dyn chess_move::MoveRule>,
         Box::new(en_passant::AllowEnPassantCapture) as Box<dyn chess_move::MoveRule>,
+        Box::new(promotion::AllowPromotion) as Box<dyn chess_move::MoveRule>,
     ]
     .into_iter()
 }
@@ -26,7 +28,7 @@ mod tests {
 
     fn is_move_allowed(chess_move: &chess_move::Move, chessboard: &Chessboard) -> bool {
         let mut rules = get_pawn_move_rules();
-        rules.any(|rule| rule.allows_move(&chess_move, &vec![chessboard.clone()]))
+        rules.any(|rule| rule.allows_move(chess_move, std::slice::from_ref(chessboard)))
     }
 
     // Allowed.
@@ -225,17 +227,14 @@ mod en_passant_tests {
     use rstest::rstest;
     use std::collections::BTreeMap;
 
-    fn is_move_allowed(
-        chess_move: &chess_move::Move,
-        chessboard_history: &Vec<Chessboard>,
-    ) -> bool {
+    fn is_move_allowed(chess_move: &chess_move::Move, chessboard_history: &[Chessboard]) -> bool {
         let mut rules = get_pawn_move_rules();
-        rules.any(|rule| rule.allows_move(&chess_move, chessboard_history))
+        rules.any(|rule| rule.allows_move(chess_move, chessboard_history))
     }
 
     fn get_allowing_rule(
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<Chessboard>,
+        chessboard_history: &[Chessboard],
     ) -> Option<Box<dyn chess_move::MoveRule>> {
         for rule in get_pawn_move_rules() {
             if rule.allows_move(chess_move, chessboard_history) {
@@ -266,6 +265,7 @@ mod en_passant_tests {
 
         let mut current_state = previous_state.clone();
         current_state.update_position(position_updates);
+        current_state.en_passant_square = Some(Square::new(Rank::Six, File::D));
 
         let white_pawn_to_square = Square::new(Rank::Six, File::D);
         let en_passant =
@@ -276,7 +276,9 @@ mod en_passant_tests {
 
         let allowing_move = get_allowing_rule(&en_passant, &chessboard_history);
 
-        let move_outcome = allowing_move.unwrap().get_move_outcome(&en_passant);
+        let move_outcome = allowing_move
+            .unwrap()
+            .get_move_outcome(&en_passant, chessboard_history.last().unwrap());
         assert_eq!(move_outcome.get(&white_pawn_from_square).unwrap(), &None);
         assert_eq!(
             move_outcome.get(&white_pawn_to_square).unwrap(),
@@ -306,6 +308,7 @@ mod en_passant_tests {
 
         let mut current_state = previous_state.clone();
         current_state.update_position(position_updates);
+        current_state.en_passant_square = Some(Square::new(Rank::Three, File::G));
 
         let black_pawn_to_square = Square::new(Rank::Three, File::G);
         let en_passant =
@@ -316,7 +319,9 @@ mod en_passant_tests {
 
         let allowing_move = get_allowing_rule(&en_passant, &chessboard_history);
 
-        let move_outcome = allowing_move.unwrap().get_move_outcome(&en_passant);
+        let move_outcome = allowing_move
+            .unwrap()
+            .get_move_outcome(&en_passant, chessboard_history.last().unwrap());
         assert_eq!(move_outcome.get(&black_pawn_from_square).unwrap(), &None);
         assert_eq!(
             move_outcome.get(&black_pawn_to_square).unwrap(),
@@ -7,7 +7,7 @@ impl chess_move::MoveRule for AllowDiagonalCapture {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
         let chessboard = chessboard_history.last().unwrap();
         let forwards = translation::ChessVector::forwards(chess_move.piece.get_colour());
@@ -20,8 +20,9 @@ impl chess_move::MoveRule for AllowDiagonalCapture {
             || chess_move.translation.vector == forwards_and_left;
 
         let is_capture = is_square_occupied_by_opponent_piece(&chess_move, chessboard);
+        let is_promotion = chess_move.promote_to.is_some();
 
-        is_forwards_diagonal && chess_move.translation.scalar == 1 && is_capture
+        is_forwards_diagonal && chess_move.translation.scalar == 1 && is_capture && !is_promotion
     }
 }
 
@@ -7,7 +7,7 @@ impl chess_move::MoveRule for AllowDoubleSquareForward {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
         let chessboard = chessboard_history.last().unwrap();
         let forwards = translation::ChessVector::forwards(chess_move.piece.get_colour());
@@ -21,12 +21,14 @@ impl chess_move::MoveRule for AllowDoubleSquareForward {
             return false;
         };
         let is_obstructed = chessboard.is_square_occupied(&middle_square);
+        let is_promotion = chess_move.promote_to.is_some();
 
         is_forwards
             && is_two_squares
             && is_first_move_for_pawn
             && !is_square_occupied
             && !is_obstructed
+            && !is_promotion
     }
 }
 
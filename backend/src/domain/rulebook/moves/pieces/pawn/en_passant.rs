@@ -8,16 +8,22 @@ impl chess_move::MoveRule for AllowEnPassantCapture {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
-        opponent_made_double_pawn_advancement_over_target_square(chess_move, chessboard_history)
+        // En passant is only allowed immediately after the opponent makes a double pawn advancement.
+        let en_passant_square = chessboard_history.last().unwrap().en_passant_square;
+
+        en_passant_square == Some(chess_move.to_square)
             && is_translation_valid(&chess_move)
+            && chess_move.promote_to.is_none()
     }
 
     fn get_move_outcome(
         &self,
         chess_move: &chess_move::Move,
+        chessboard: &chess_set::Chessboard,
     ) -> BTreeMap<chess_set::Square, Option<chess_set::Piece>> {
+        let _ = chessboard;
         let mut outcome = BTreeMap::new();
 
         outcome.insert(chess_move.from_square, None);
@@ -30,42 +36,38 @@ impl chess_move::MoveRule for AllowEnPassantCapture {
     }
 }
 
-// En passant is only allowed immediately after the opponent makes a double pawn advancement.
-fn opponent_made_double_pawn_advancement_over_target_square(
+/// Get the en passant square after a move, which is the square a double advancing pawn skips.
+pub fn get_en_passant_square_after_move(
     chess_move: &chess_move::Move,
-    chessboard_history: &Vec<chess_set::Chessboard>,
-) -> bool {
-    let history_length = chessboard_history.len();
-    if history_length < 2 {
-        return false;
-    }
-
-    let opponent_colour = &chess_move.piece.get_colour().swap();
-
-    let previous_state = &chessboard_history[history_length - 2];
-    let from_square = get_square_captured_pawn_should_have_moved_from(&chess_move);
-    let opponent_pawn_moved_from_expected_square =
-        is_piece_at_square_opponent_pawn(previous_state, opponent_colour, &from_square);
-
-    let current_state = chessboard_history.last().unwrap();
-    let to_square = get_square_captured_pawn_should_have_moved_to(chess_move);
-    let opponent_pawn_moved_to_expected_square =
-        is_piece_at_square_opponent_pawn(current_state, opponent_colour, &to_square);
+) -> Option<chess_set::Square> {
+    let is_pawn = chess_move.piece.get_piece_type() == &chess_set::PieceType::Pawn;
+    let is_double_advancement =
+        chess_move.translation.vector.x == 0 && chess_move.translation.scalar == 2;
+    if !(is_pawn && is_double_advancement) {
+        return None;
+    };
 
-    opponent_pawn_moved_from_expected_square && opponent_pawn_moved_to_expected_square
+    let rank_index =
+        (chess_move.from_square.get_rank().index() + chess_move.to_square.get_rank().index()) / 2;
+    let file_index = chess_move.from_square.get_file().index();
+    Some(chess_set::Square::from_indexes(rank_index, file_index))
 }
 
-fn is_piece_at_square_opponent_pawn(
-    chessboard: &chess_set::Chessboard,
-    opponent_colour: &chess_set::Colour,
-    square: &chess_set::Square,
-) -> bool {
-    let Some(piece) = chessboard.get_piece(square) else {
-        return false;
+/// Get the squares an opponent pawn moved from and to, when advancing over an en passant square.
+pub fn get_double_advancement_squares(
+    to_play_colour: chess_set::Colour,
+    en_passant_square: &chess_set::Square,
+) -> (chess_set::Square, chess_set::Square) {
+    let forward = match to_play_colour {
+        chess_set::Colour::White => 1,
+        chess_set::Colour::Black => -1,
     };
-    let is_opponent = piece.get_colour() == opponent_colour;
-    let is_pawn = piece.get_piece_type() == &chess_set::PieceType::Pawn;
-    is_opponent && is_pawn
+    let rank_index = en_passant_square.get_rank().index();
+    let file_index = en_passant_square.get_file().index();
+
+    let from_square = chess_set::Square::from_indexes(rank_index + forward, file_index);
+    let to_square = chess_set::Square::from_indexes(rank_index - forward, file_index);
+    (from_square, to_square)
 }
 
 fn get_square_captured_pawn_should_have_moved_to(
@@ -76,18 +78,6 @@ fn get_square_captured_pawn_should_have_moved_to(
     chess_set::Square::new(rank.clone(), file.clone())
 }
 
-fn get_square_captured_pawn_should_have_moved_from(
-    chess_move: &chess_move::Move,
-) -> chess_set::Square {
-    let rank = match chess_move.piece.get_colour() {
-        // Opponent pawn should have moved from their starting rank.
-        &chess_set::Colour::White => chess_set::Rank::Seven,
-        &chess_set::Colour::Black => chess_set::Rank::Two,
-    };
-    let file = chess_move.to_square.get_file();
-    chess_set::Square::new(rank.clone(), file.clone())
-}
-
 fn is_translation_valid(chess_move: &chess_move::Move) -> bool {
     // En passant can only be made after a pawn has advanced exactly 3 squares.
     let starting_rank_valid = match chess_move.piece.get_colour() {
@@ -104,5 +94,5 @@ fn is_translation_valid(chess_move: &chess_move::Move) -> bool {
     let is_forwards_diagonal = chess_move.translation.vector == forwards_and_right
         || chess_move.translation.vector == forwards_and_left;
 
-    starting_rank_valid && is_forwards_diagonal
+    starting_rank_valid && is_forwards_diagonal && chess_move.translation.scalar == 1
 }
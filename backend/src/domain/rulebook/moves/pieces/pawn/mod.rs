@@ -2,6 +2,9 @@ mod all_rules;
 mod diagonal_capture;
 mod double_square_forward;
 mod en_passant;
+mod promotion;
 mod single_square_forward;
 
 pub use all_rules::get_pawn_move_rules;
+pub use en_passant::{get_double_advancement_squares, get_en_passant_square_after_move};
+pub use promotion::get_promotion_piece_types;
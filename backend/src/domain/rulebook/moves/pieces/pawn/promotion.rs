@@ -0,0 +1,175 @@
+use super::{diagonal_capture, single_square_forward};
+use crate::domain::chess_set;
+use crate::domain::rulebook::moves::chess_move;
+use std::collections::BTreeMap;
+
+/// Allow a pawn reaching the furthest rank to be exchanged for another piece.
+///
+/// The pawn can reach the furthest rank either by moving forwards, or by capturing.
+pub struct AllowPromotion;
+
+impl chess_move::MoveRule for AllowPromotion {
+    fn allows_move(
+        &self,
+        chess_move: &chess_move::Move,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> bool {
+        let Some(promote_to) = chess_move.promote_to else {
+            return false;
+        };
+        let is_valid_piece_type = get_promotion_piece_types().contains(&promote_to);
+
+        // Test the pawn's own move, without the promotion.
+        let mut pawn_move = chess_move.clone();
+        pawn_move.promote_to = None;
+        let is_valid_pawn_move = single_square_forward::AllowSingleSquareForward
+            .allows_move(&pawn_move, chessboard_history)
+            || diagonal_capture::AllowDiagonalCapture.allows_move(&pawn_move, chessboard_history);
+
+        chess_move.is_to_promotion_rank() && is_valid_piece_type && is_valid_pawn_move
+    }
+
+    fn get_move_outcome(
+        &self,
+        chess_move: &chess_move::Move,
+        chessboard: &chess_set::Chessboard,
+    ) -> BTreeMap<chess_set::Square, Option<chess_set::Piece>> {
+        let _ = chessboard;
+
+        let promote_to = chess_move.promote_to.unwrap();
+        let promoted_piece = chess_set::Piece::new(*chess_move.piece.get_colour(), promote_to);
+
+        let mut outcome = BTreeMap::new();
+        outcome.insert(chess_move.from_square, None);
+        outcome.insert(chess_move.to_square, Some(promoted_piece));
+
+        outcome
+    }
+}
+
+/// The piece types that a pawn can be promoted to.
+pub fn get_promotion_piece_types() -> Vec<chess_set::PieceType> {
+    vec![
+        chess_set::PieceType::Queen,
+        chess_set::PieceType::Rook,
+        chess_set::PieceType::Bishop,
+        chess_set::PieceType::Knight,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AllowPromotion;
+    use crate::domain::chess_set::{Chessboard, Colour, File, Piece, PieceType, Rank, Square};
+    use crate::domain::rulebook::moves::chess_move;
+    use crate::domain::rulebook::moves::chess_move::MoveRule;
+    use rstest::rstest;
+    use std::collections::BTreeMap;
+
+    #[rstest]
+    #[case::queen(PieceType::Queen)]
+    #[case::rook(PieceType::Rook)]
+    #[case::bishop(PieceType::Bishop)]
+    #[case::knight(PieceType::Knight)]
+    fn white_pawn_can_be_promoted_by_moving_forwards(#[case] promote_to: PieceType) {
+        let mut starting_position = BTreeMap::new();
+
+        let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+        let from_square = Square::new(Rank::Seven, File::B);
+        starting_position.insert(from_square, white_pawn);
+
+        let chessboard = Chessboard::new(starting_position);
+
+        let to_square = Square::new(Rank::Eight, File::B);
+        let promotion =
+            chess_move::Move::new_promotion(white_pawn, from_square, to_square, promote_to);
+
+        assert!(AllowPromotion.allows_move(&promotion, std::slice::from_ref(&chessboard)));
+
+        let outcome = AllowPromotion.get_move_outcome(&promotion, &chessboard);
+        let promoted_piece = Piece::new(Colour::White, promote_to);
+        assert_eq!(outcome.get(&from_square).unwrap(), &None);
+        assert_eq!(outcome.get(&to_square).unwrap(), &Some(promoted_piece));
+    }
+
+    #[test]
+    fn black_pawn_can_be_promoted_by_capturing() {
+        let mut starting_position = BTreeMap::new();
+
+        let black_pawn = Piece::new(Colour::Black, PieceType::Pawn);
+        let from_square = Square::new(Rank::Two, File::G);
+        starting_position.insert(from_square, black_pawn);
+
+        let white_rook = Piece::new(Colour::White, PieceType::Rook);
+        let to_square = Square::new(Rank::One, File::H);
+        starting_position.insert(to_square, white_rook);
+
+        let chessboard = Chessboard::new(starting_position);
+
+        let promotion =
+            chess_move::Move::new_promotion(black_pawn, from_square, to_square, PieceType::Queen);
+
+        assert!(AllowPromotion.allows_move(&promotion, std::slice::from_ref(&chessboard)));
+
+        let outcome = AllowPromotion.get_move_outcome(&promotion, &chessboard);
+        let black_queen = Piece::new(Colour::Black, PieceType::Queen);
+        assert_eq!(outcome.get(&from_square).unwrap(), &None);
+        assert_eq!(outcome.get(&to_square).unwrap(), &Some(black_queen));
+    }
+
+    #[rstest]
+    #[case::king(PieceType::King)]
+    #[case::pawn(PieceType::Pawn)]
+    fn cannot_promote_to_invalid_piece_type(#[case] promote_to: PieceType) {
+        let mut starting_position = BTreeMap::new();
+
+        let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+        let from_square = Square::new(Rank::Seven, File::B);
+        starting_position.insert(from_square, white_pawn);
+
+        let chessboard = Chessboard::new(starting_position);
+
+        let to_square = Square::new(Rank::Eight, File::B);
+        let promotion =
+            chess_move::Move::new_promotion(white_pawn, from_square, to_square, promote_to);
+
+        assert!(!AllowPromotion.allows_move(&promotion, &[chessboard]));
+    }
+
+    #[test]
+    fn cannot_promote_before_reaching_furthest_rank() {
+        let mut starting_position = BTreeMap::new();
+
+        let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+        let from_square = Square::new(Rank::Six, File::B);
+        starting_position.insert(from_square, white_pawn);
+
+        let chessboard = Chessboard::new(starting_position);
+
+        let to_square = Square::new(Rank::Seven, File::B);
+        let promotion =
+            chess_move::Move::new_promotion(white_pawn, from_square, to_square, PieceType::Queen);
+
+        assert!(!AllowPromotion.allows_move(&promotion, &[chessboard]));
+    }
+
+    #[test]
+    fn cannot_promote_when_forward_square_is_occupied() {
+        let mut starting_position = BTreeMap::new();
+
+        let white_pawn = Piece::new(Colour::White, PieceType::Pawn);
+        let from_square = Square::new(Rank::Seven, File::B);
+        starting_position.insert(from_square, white_pawn);
+
+        let black_knight = Piece::new(Colour::Black, PieceType::Knight);
+        let to_square = Square::new(Rank::Eight, File::B);
+        starting_position.insert(to_square, black_knight);
+
+        let chessboard = Chessboard::new(starting_position);
+
+        let promotion =
+            chess_move::Move::new_promotion(white_pawn, from_square, to_square, PieceType::Queen);
+
+        assert!(!AllowPromotion.allows_move(&promotion, &[chessboard]));
+    }
+}
@@ -7,7 +7,7 @@ impl chess_move::MoveRule for AllowSingleSquareForward {
     fn allows_move(
         &self,
         chess_move: &chess_move::Move,
-        chessboard_history: &Vec<chess_set::Chessboard>,
+        chessboard_history: &[chess_set::Chessboard],
     ) -> bool {
         let chessboard = chessboard_history.last().unwrap();
         let forwards = translation::ChessVector::forwards(chess_move.piece.get_colour());
@@ -16,6 +16,8 @@ impl chess_move::MoveRule for AllowSingleSquareForward {
         let is_single_square = chess_move.translation.scalar == 1;
         let is_square_occupied = chessboard.is_square_occupied(&chess_move.to_square);
 
-        is_forwards && is_single_square && !is_square_occupied
+        let is_promotion = chess_move.promote_to.is_some();
+
+        is_forwards && is_single_square && !is_square_occupied && !is_promotion
     }
 }
@@ -23,7 +23,7 @@ mod tests {
 
     fn is_move_allowed(chess_move: chess_move::Move, chessboard: &Chessboard) -> bool {
         let mut rules = get_queen_move_rules();
-        rules.any(|rule| rule.allows_move(&chess_move, &vec![chessboard.clone()]))
+        rules.any(|rule| rule.allows_move(&chess_move, std::slice::from_ref(chessboard)))
     }
 
     #[rstest]
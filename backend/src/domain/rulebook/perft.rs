@@ -0,0 +1,139 @@
+use super::moves::chess_move;
+use super::{check, search_position};
+use crate::domain::chess_set;
+
+/// Count the leaf nodes of the legal move tree, to a certain depth.
+///
+/// This is used to verify move generation, by comparing against known node counts.
+pub fn perft(
+    player: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+    depth: u8,
+) -> u64 {
+    let mut position = search_position::SearchPosition::from_chessboard_history(
+        player,
+        0,
+        check::ChecksGiven::default(),
+        chessboard_history,
+    );
+    count_nodes(&mut position, depth)
+}
+
+/// Count the leaf nodes beneath each legal move, to a certain depth.
+///
+/// The counts sum to `perft` at the same depth, so comparing them against a trusted
+/// move generator narrows a discrepancy down to a single move.
+pub fn divide(
+    player: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+    depth: u8,
+) -> Vec<(chess_move::Move, u64)> {
+    if depth == 0 {
+        return vec![];
+    };
+
+    let mut position = search_position::SearchPosition::from_chessboard_history(
+        player,
+        0,
+        check::ChecksGiven::default(),
+        chessboard_history,
+    );
+    position
+        .get_legal_moves()
+        .into_iter()
+        .map(|chess_move| {
+            let node_count = count_nodes_after_move(&chess_move, &mut position, depth - 1);
+            (chess_move, node_count)
+        })
+        .collect()
+}
+
+fn count_nodes(position: &mut search_position::SearchPosition, depth: u8) -> u64 {
+    if depth == 0 {
+        return 1;
+    };
+
+    let legal_moves = position.get_legal_moves();
+    // There's no need to play the moves on the final ply, only to count them.
+    if depth == 1 {
+        return legal_moves.len() as u64;
+    };
+
+    legal_moves
+        .iter()
+        .map(|chess_move| count_nodes_after_move(chess_move, position, depth - 1))
+        .sum()
+}
+
+fn count_nodes_after_move(
+    chess_move: &chess_move::Move,
+    position: &mut search_position::SearchPosition,
+    depth: u8,
+) -> u64 {
+    position.make_move(chess_move);
+    let node_count = count_nodes(position, depth);
+    position.unmake_move();
+    node_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::rulebook::{uci, Fen};
+    use crate::testing::factories;
+    use rstest::rstest;
+
+    #[test]
+    fn counts_single_node_at_depth_zero() {
+        let chessboard_history = vec![factories::chessboard()];
+
+        assert_eq!(perft(chess_set::Colour::White, &chessboard_history, 0), 1);
+    }
+
+    #[test]
+    fn counts_nodes_from_starting_position() {
+        let chessboard_history = vec![factories::chessboard()];
+
+        assert_eq!(perft(chess_set::Colour::White, &chessboard_history, 1), 20);
+        assert_eq!(perft(chess_set::Colour::White, &chessboard_history, 2), 400);
+    }
+
+    #[rstest]
+    #[case(
+        "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        12189
+    )]
+    #[case(
+        "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        18002
+    )]
+    #[case("b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9", 10471)]
+    #[case(
+        "1rqbkrbn/1ppppp1p/1n6/p1N3p1/8/2P4P/PP1PPPP1/1RQBKRBN w FBfb - 0 9",
+        14569
+    )]
+    fn counts_nodes_in_chess960_positions(#[case] fen: &str, #[case] expected_node_count: u64) {
+        let fen = Fen::parse(fen).unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        assert_eq!(
+            perft(fen.to_play_colour, &chessboard_history, 3),
+            expected_node_count
+        );
+    }
+
+    #[test]
+    fn divides_nodes_between_legal_moves() {
+        let chessboard_history = vec![factories::chessboard()];
+
+        let divided = divide(chess_set::Colour::White, &chessboard_history, 2);
+
+        assert_eq!(divided.len(), 20);
+        assert!(divided.iter().all(|(_, node_count)| node_count == &20));
+        let total: u64 = divided.iter().map(|(_, node_count)| node_count).sum();
+        assert_eq!(total, 400);
+        assert!(divided
+            .iter()
+            .any(|(chess_move, _)| uci::to_uci(chess_move) == "e2e4"));
+    }
+}
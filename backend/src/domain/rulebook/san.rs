@@ -0,0 +1,528 @@
+use super::check;
+use super::checkmate;
+use super::moves::{chess_move, pieces};
+use crate::domain::chess_set;
+
+/// Describe a move in standard algebraic notation, e.g. `Nbd7`, `exd5`, `O-O` or `e8=Q+`.
+///
+/// The chessboard history should be the history before the move is played.
+pub fn to_san(
+    chess_move: &chess_move::Move,
+    chessboard_history: &[chess_set::Chessboard],
+) -> String {
+    let legal_moves =
+        checkmate::get_legal_moves(*chess_move.piece.get_colour(), chessboard_history);
+    write_san(chess_move, chessboard_history, &legal_moves)
+}
+
+/// Pair each of a player's legal moves with its standard algebraic notation.
+///
+/// The legal moves must be all of the player's legal moves, e.g. under the rules of a
+/// variant, so that moves are only disambiguated from each other when they need to be.
+pub fn write_legal_moves_in_san(
+    legal_moves: &[chess_move::Move],
+    chessboard_history: &[chess_set::Chessboard],
+) -> Vec<(chess_move::Move, String)> {
+    legal_moves
+        .iter()
+        .map(|chess_move| {
+            let san = write_san(chess_move, chessboard_history, legal_moves);
+            (chess_move.clone(), san)
+        })
+        .collect()
+}
+
+/// Find the legal move described by a move in standard algebraic notation.
+///
+/// Check and annotation suffixes (e.g. `+`, `#` and `!?`) are ignored, and castling
+/// may be written with zeros. `None` is returned if no legal move, or more than one
+/// legal move, matches.
+pub fn from_san(
+    san: &str,
+    player: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> Option<chess_move::Move> {
+    let description = parse_san(san)?;
+    let chessboard = chessboard_history.last().unwrap();
+
+    let mut matching_moves: Vec<chess_move::Move> =
+        checkmate::get_legal_moves(player, chessboard_history)
+            .into_iter()
+            .filter(|chess_move| description.matches(chess_move, chessboard))
+            .collect();
+
+    match matching_moves.len() {
+        1 => matching_moves.pop(),
+        _ => None,
+    }
+}
+
+/// The parts of a move that can be read from standard algebraic notation.
+struct SanDescription {
+    piece_type: chess_set::PieceType,
+    castles_kingside: Option<bool>,
+    from_file_index: Option<i8>,
+    from_rank_index: Option<i8>,
+    to_square: Option<chess_set::Square>,
+    promote_to: Option<chess_set::PieceType>,
+}
+
+impl SanDescription {
+    fn matches(&self, chess_move: &chess_move::Move, chessboard: &chess_set::Chessboard) -> bool {
+        if !(chess_move.piece.get_piece_type() == &self.piece_type)
+            || !(chess_move.promote_to == self.promote_to)
+        {
+            return false;
+        };
+
+        let is_castling = is_castling(chess_move, chessboard);
+        if let Some(castles_kingside) = self.castles_kingside {
+            return is_castling && is_castling_kingside(chess_move) == castles_kingside;
+        };
+        if is_castling {
+            return false;
+        };
+
+        Some(chess_move.to_square) == self.to_square
+            && self
+                .from_file_index
+                .is_none_or(|index| chess_move.from_square.get_file().index() == index)
+            && self
+                .from_rank_index
+                .is_none_or(|index| chess_move.from_square.get_rank().index() == index)
+    }
+}
+
+fn parse_san(san: &str) -> Option<SanDescription> {
+    let san = san.trim_end_matches(|c| "+#!?".contains(c));
+
+    let castles_kingside = match san {
+        "O-O" | "0-0" => Some(true),
+        "O-O-O" | "0-0-0" => Some(false),
+        _ => None,
+    };
+    if castles_kingside.is_some() {
+        return Some(SanDescription {
+            piece_type: chess_set::PieceType::King,
+            castles_kingside,
+            from_file_index: None,
+            from_rank_index: None,
+            to_square: None,
+            promote_to: None,
+        });
+    };
+
+    let mut chars: Vec<char> = san.chars().collect();
+
+    let piece_type = match chars
+        .first()
+        .and_then(|c| chess_set::PieceType::from_notation(*c))
+    {
+        Some(piece_type) => {
+            chars.remove(0);
+            piece_type
+        }
+        None => chess_set::PieceType::Pawn,
+    };
+
+    let promote_to = match chars
+        .last()
+        .and_then(|c| chess_set::PieceType::from_notation(*c))
+    {
+        Some(promote_to) => {
+            chars.pop();
+            if chars.last() == Some(&'=') {
+                chars.pop();
+            };
+            Some(promote_to)
+        }
+        None => None,
+    };
+
+    if chars.len() < 2 {
+        return None;
+    };
+    let to_square_notation: String = chars.split_off(chars.len() - 2).into_iter().collect();
+    let to_square = chess_set::Square::from_algebraic(&to_square_notation)?;
+
+    if chars.last() == Some(&'x') {
+        chars.pop();
+    };
+    let mut from_file_index = None;
+    let mut from_rank_index = None;
+    for c in chars {
+        match c {
+            'a'..='h' if from_file_index.is_none() => {
+                from_file_index = Some((c as u8 - b'a') as i8 + 1)
+            }
+            '1'..='8' if from_rank_index.is_none() => {
+                from_rank_index = Some((c as u8 - b'0') as i8)
+            }
+            _ => return None,
+        };
+    }
+
+    Some(SanDescription {
+        piece_type,
+        castles_kingside: None,
+        from_file_index,
+        from_rank_index,
+        to_square: Some(to_square),
+        promote_to,
+    })
+}
+
+/// Write a move, given all the legal moves the player could have made instead.
+fn write_san(
+    chess_move: &chess_move::Move,
+    chessboard_history: &[chess_set::Chessboard],
+    legal_moves: &[chess_move::Move],
+) -> String {
+    let chessboard = chessboard_history.last().unwrap();
+    let mut san = match is_castling(chess_move, chessboard) {
+        true => write_castling(chess_move),
+        false => write_ordinary_move(chess_move, chessboard, legal_moves),
+    };
+    san.push_str(&write_check_suffix(chess_move, chessboard_history));
+    san
+}
+
+fn write_castling(chess_move: &chess_move::Move) -> String {
+    match is_castling_kingside(chess_move) {
+        true => "O-O".to_string(),
+        false => "O-O-O".to_string(),
+    }
+}
+
+fn write_ordinary_move(
+    chess_move: &chess_move::Move,
+    chessboard: &chess_set::Chessboard,
+    legal_moves: &[chess_move::Move],
+) -> String {
+    let piece_type = chess_move.piece.get_piece_type();
+    let is_capture = is_capture(chess_move, chessboard);
+
+    let mut san = String::new();
+    if piece_type == &chess_set::PieceType::Pawn {
+        // Pawn captures are identified by the file the pawn moved from.
+        if is_capture {
+            san.push_str(&chess_move.from_square.get_file().to_string().to_lowercase());
+        };
+    } else {
+        san.push_str(&piece_type.to_string());
+        san.push_str(&write_disambiguation(chess_move, legal_moves));
+    };
+
+    if is_capture {
+        san.push('x');
+    };
+    san.push_str(&chess_move.to_square.to_algebraic());
+
+    if let Some(promote_to) = chess_move.promote_to {
+        san.push_str(&format!("={}", promote_to));
+    };
+    san
+}
+
+/// Identify the moved piece when another piece of the same type could move to the same square.
+///
+/// The file is preferred, then the rank, and finally the full square.
+fn write_disambiguation(chess_move: &chess_move::Move, legal_moves: &[chess_move::Move]) -> String {
+    let ambiguous_from_squares: Vec<chess_set::Square> = legal_moves
+        .iter()
+        .filter(|other_move| {
+            other_move.piece == chess_move.piece
+                && other_move.to_square == chess_move.to_square
+                && other_move.from_square != chess_move.from_square
+        })
+        .map(|other_move| other_move.from_square)
+        .collect();
+
+    if ambiguous_from_squares.is_empty() {
+        return "".to_string();
+    };
+
+    let from_square = chess_move.from_square;
+    let file = from_square.get_file().to_string().to_lowercase();
+    let rank = from_square.get_rank().index().to_string();
+    if !ambiguous_from_squares
+        .iter()
+        .any(|square| square.get_file() == from_square.get_file())
+    {
+        file
+    } else if !ambiguous_from_squares
+        .iter()
+        .any(|square| square.get_rank() == from_square.get_rank())
+    {
+        rank
+    } else {
+        from_square.to_algebraic()
+    }
+}
+
+fn write_check_suffix(
+    chess_move: &chess_move::Move,
+    chessboard_history: &[chess_set::Chessboard],
+) -> String {
+    let Ok(chessboard) = chess_move.apply_if_valid(chessboard_history) else {
+        return "".to_string();
+    };
+    let opponent = chess_move.piece.get_colour().swap();
+    if !check::is_player_in_check(&opponent, &chessboard) {
+        return "".to_string();
+    };
+
+    let mut updated_history = chessboard_history.to_vec();
+    updated_history.push(chessboard);
+    match checkmate::is_player_checkmated(opponent, &updated_history) {
+        true => "#".to_string(),
+        false => "+".to_string(),
+    }
+}
+
+fn is_castling(chess_move: &chess_move::Move, chessboard: &chess_set::Chessboard) -> bool {
+    let castling_rights = chess_set::Bitboard::from_squares(&chessboard.castling_rights);
+    pieces::get_castling_rook_square(chess_move, castling_rights).is_some()
+}
+
+/// Test whether a castle is kingside, which is towards the h-file.
+///
+/// Castles are written as the king moving towards its rook, so this holds whether the
+/// king moves to its destination or onto the rook's square.
+fn is_castling_kingside(chess_move: &chess_move::Move) -> bool {
+    chess_move.to_square.get_file() > chess_move.from_square.get_file()
+}
+
+/// Test whether a move captures a piece, including pawns captured en passant.
+fn is_capture(chess_move: &chess_move::Move, chessboard: &chess_set::Chessboard) -> bool {
+    if chessboard.is_square_occupied(&chess_move.to_square) {
+        return true;
+    };
+    chess_move.piece.get_piece_type() == &chess_set::PieceType::Pawn
+        && chess_move.from_square.get_file() != chess_move.to_square.get_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::rulebook::Fen;
+    use crate::testing::factories;
+    use rstest::rstest;
+
+    fn get_san(fen: &str, from_square: &str, to_square: &str) -> String {
+        let fen = Fen::parse(fen).unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+        let chess_move = checkmate::get_legal_moves(fen.to_play_colour, &chessboard_history)
+            .into_iter()
+            .find(|chess_move| {
+                chess_move.from_square.to_algebraic() == from_square
+                    && chess_move.to_square.to_algebraic() == to_square
+                    && chess_move.promote_to.unwrap_or(chess_set::PieceType::Queen)
+                        == chess_set::PieceType::Queen
+            })
+            .unwrap();
+
+        to_san(&chess_move, &chessboard_history)
+    }
+
+    #[test]
+    fn writes_pawn_and_piece_moves_from_starting_position() {
+        let chessboard_history = vec![factories::chessboard()];
+        let white_moves = checkmate::get_legal_moves(chess_set::Colour::White, &chessboard_history);
+
+        let mut sans: Vec<String> = white_moves
+            .iter()
+            .map(|chess_move| to_san(chess_move, &chessboard_history))
+            .collect();
+        sans.sort();
+
+        assert_eq!(
+            sans,
+            vec![
+                "Na3", "Nc3", "Nf3", "Nh3", "a3", "a4", "b3", "b4", "c3", "c4", "d3", "d4", "e3",
+                "e4", "f3", "f4", "g3", "g4", "h3", "h4"
+            ]
+        );
+    }
+
+    #[test]
+    fn gets_legal_moves_in_san() {
+        let fen = Fen::parse("4k3/8/8/8/8/8/5K2/R6R w - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let legal_moves = write_legal_moves_in_san(
+            &checkmate::get_legal_moves(fen.to_play_colour, &chessboard_history),
+            &chessboard_history,
+        );
+
+        for (chess_move, san) in legal_moves.iter() {
+            assert_eq!(san, &to_san(chess_move, &chessboard_history));
+        }
+        let sans: Vec<&String> = legal_moves.iter().map(|(_, san)| san).collect();
+        assert!(sans.contains(&&"Rad1".to_string()));
+        assert!(sans.contains(&&"Rhe1+".to_string()));
+    }
+
+    #[rstest]
+    #[case::pawn_capture(
+        "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2",
+        "e4",
+        "d5",
+        "exd5"
+    )]
+    #[case::en_passant(
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "e5",
+        "f6",
+        "exf6"
+    )]
+    #[case::piece_capture(
+        "rnbqkbnr/ppp1pppp/8/3p4/8/2N5/PPPPPPPP/R1BQKBNR w KQkq - 0 2",
+        "c3",
+        "d5",
+        "Nxd5"
+    )]
+    #[case::kingside_castling(
+        "r3k2r/pppqbppp/2n1pn2/3p4/3P4/2N1PN2/PPPQBPPP/R3K2R w KQkq - 0 8",
+        "e1",
+        "g1",
+        "O-O"
+    )]
+    #[case::queenside_castling(
+        "r3k2r/pppqbppp/2n1pn2/3p4/3P4/2N1PN2/PPPQBPPP/R3K2R b KQkq - 0 8",
+        "e8",
+        "c8",
+        "O-O-O"
+    )]
+    #[case::chess960_kingside_castling_with_check(
+        "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1",
+        "f1",
+        "g1",
+        "O-O+"
+    )]
+    #[case::chess960_queenside_castling(
+        "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1",
+        "f1",
+        "b1",
+        "O-O-O"
+    )]
+    #[case::promotion("8/4P3/8/8/8/k7/8/K7 w - - 0 1", "e7", "e8", "e8=Q")]
+    #[case::check(
+        "rnbqkbnr/ppppp1pp/8/5p2/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
+        "d1",
+        "h5",
+        "Qh5+"
+    )]
+    #[case::checkmate(
+        "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2",
+        "d8",
+        "h4",
+        "Qh4#"
+    )]
+    fn writes_san(
+        #[case] fen: &str,
+        #[case] from_square: &str,
+        #[case] to_square: &str,
+        #[case] expected_san: &str,
+    ) {
+        assert_eq!(get_san(fen, from_square, to_square), expected_san);
+    }
+
+    #[rstest]
+    #[case::by_file("4k3/8/8/8/8/8/5K2/R6R w - - 0 1", "a1", "d1", "Rad1")]
+    #[case::by_rank("4k3/8/8/R7/8/8/8/R4K2 w - - 0 1", "a1", "a3", "R1a3")]
+    #[case::by_square("4k3/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1", "a1", "b2", "Qa1b2")]
+    fn disambiguates_piece_moves(
+        #[case] fen: &str,
+        #[case] from_square: &str,
+        #[case] to_square: &str,
+        #[case] expected_san: &str,
+    ) {
+        assert_eq!(get_san(fen, from_square, to_square), expected_san);
+    }
+
+    #[rstest]
+    #[case::pawn_move("e4", "e2", "e4")]
+    #[case::piece_move("Nf3", "g1", "f3")]
+    #[case::ignores_suffixes("Nf3!?", "g1", "f3")]
+    fn reads_san_from_starting_position(
+        #[case] san: &str,
+        #[case] from_square: &str,
+        #[case] to_square: &str,
+    ) {
+        let chessboard_history = vec![factories::chessboard()];
+
+        let chess_move = from_san(san, chess_set::Colour::White, &chessboard_history).unwrap();
+
+        assert_eq!(chess_move.from_square.to_algebraic(), from_square);
+        assert_eq!(chess_move.to_square.to_algebraic(), to_square);
+    }
+
+    #[rstest]
+    #[case::castling(
+        "r3k2r/pppqbppp/2n1pn2/3p4/3P4/2N1PN2/PPPQBPPP/R3K2R w KQkq - 0 8",
+        "O-O",
+        "e1",
+        "g1"
+    )]
+    #[case::castling_with_zeros(
+        "r3k2r/pppqbppp/2n1pn2/3p4/3P4/2N1PN2/PPPQBPPP/R3K2R b KQkq - 0 8",
+        "0-0-0",
+        "e8",
+        "c8"
+    )]
+    #[case::en_passant(
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "exf6",
+        "e5",
+        "f6"
+    )]
+    #[case::chess960_castling("1r4kr/8/8/8/8/8/8/1R4KR b KQkq - 0 1", "O-O", "g8", "h8")]
+    #[case::disambiguated("4k3/8/8/R7/8/8/8/R4K2 w - - 0 1", "R1a3", "a1", "a3")]
+    fn reads_san(
+        #[case] fen: &str,
+        #[case] san: &str,
+        #[case] from_square: &str,
+        #[case] to_square: &str,
+    ) {
+        let fen = Fen::parse(fen).unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let chess_move = from_san(san, fen.to_play_colour, &chessboard_history).unwrap();
+
+        assert_eq!(chess_move.from_square.to_algebraic(), from_square);
+        assert_eq!(chess_move.to_square.to_algebraic(), to_square);
+    }
+
+    #[test]
+    fn reads_promotion() {
+        let fen = Fen::parse("8/4P3/8/8/8/k7/8/K7 w - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let chess_move = from_san("e8=N", fen.to_play_colour, &chessboard_history).unwrap();
+
+        assert_eq!(chess_move.promote_to, Some(chess_set::PieceType::Knight));
+    }
+
+    #[rstest]
+    #[case::illegal_move("e5")]
+    #[case::blocked_move("Nd2")]
+    #[case::not_san("hello")]
+    fn cannot_read_invalid_san(#[case] san: &str) {
+        let chessboard_history = vec![factories::chessboard()];
+
+        let chess_move = from_san(san, chess_set::Colour::White, &chessboard_history);
+
+        assert!(chess_move.is_none());
+    }
+
+    #[test]
+    fn cannot_read_ambiguous_san() {
+        let fen = Fen::parse("4k3/8/8/8/8/8/5K2/R6R w - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let chess_move = from_san("Rd1", fen.to_play_colour, &chessboard_history);
+
+        assert!(chess_move.is_none());
+    }
+}
@@ -0,0 +1,555 @@
+use super::check;
+use super::draws::{self, FIVEFOLD_REPETITION_COUNT, SEVENTY_FIVE_MOVE_RULE_HALFMOVES};
+use super::moves::{chess_move, generation, pieces};
+use crate::domain::chess_set;
+
+/// How a game has ended, from the perspective of a search.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Won(chess_set::Colour),
+    Drawn(draws::Draw),
+}
+
+/// A position that moves can be made on, and then unmade, when searching the game tree.
+///
+/// Unlike a `Game`, this does not store the full chessboard history. Castling rights,
+/// the en passant square and the halfmove clock are instead tracked explicitly, and
+/// whatever a move overwrites is kept so that the move can be unmade.
+///
+/// The position's Zobrist hash is updated incrementally as moves are made.
+#[derive(Clone)]
+pub struct SearchPosition {
+    chessboard: chess_set::BitboardChessboard,
+    to_play_colour: chess_set::Colour,
+    castling_rights: chess_set::Bitboard,
+    en_passant_square: Option<chess_set::Square>,
+    halfmove_clock: u16,
+    zobrist_hash: u64,
+    // The checks each player has given, which only matter in variants such as Three-check.
+    checks_given: check::ChecksGiven,
+    undo_history: Vec<Undo>,
+    // The hashes of earlier positions that could still be repeated (i.e. since the last
+    // pawn move or capture), with the most recent position last.
+    previous_zobrist_hashes: Vec<u64>,
+}
+
+/// Everything needed to unmake a move.
+#[derive(Clone)]
+struct Undo {
+    chess_move: chess_move::Move,
+    captured_piece: Option<(chess_set::Square, chess_set::Piece)>,
+    castling_rights: chess_set::Bitboard,
+    en_passant_square: Option<chess_set::Square>,
+    halfmove_clock: u16,
+    zobrist_hash: u64,
+    checks_given: check::ChecksGiven,
+}
+
+impl SearchPosition {
+    // Factories.
+
+    /// Get the position at the end of a chessboard history.
+    ///
+    /// The starting halfmove clock is the halfmove clock of the earliest chessboard in
+    /// the history, e.g. as given by the FEN a game started from. The checks given are
+    /// those given by the end of the history.
+    pub fn from_chessboard_history(
+        to_play_colour: chess_set::Colour,
+        starting_halfmove_clock: u16,
+        checks_given: check::ChecksGiven,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Self {
+        let halfmove_clock = draws::get_halfmove_clock(starting_halfmove_clock, chessboard_history);
+        let latest_index = chessboard_history.len() - 1;
+        // Only the positions in the chessboard history can be repeated.
+        let repeatable_plies = draws::get_halfmove_clock(0, chessboard_history) as usize;
+
+        let mut previous_zobrist_hashes = vec![];
+        let mut player = to_play_colour;
+        for index in (latest_index - repeatable_plies..latest_index).rev() {
+            player = player.swap();
+            let position = Self::from_state(player, &chessboard_history[..=index], halfmove_clock);
+            previous_zobrist_hashes.insert(0, position.zobrist_hash);
+        }
+
+        let mut position = Self::from_state(to_play_colour, chessboard_history, halfmove_clock);
+        position.previous_zobrist_hashes = previous_zobrist_hashes;
+        position.checks_given = checks_given;
+        position
+    }
+
+    fn from_state(
+        to_play_colour: chess_set::Colour,
+        chessboard_history: &[chess_set::Chessboard],
+        halfmove_clock: u16,
+    ) -> Self {
+        let chessboard = chessboard_history.last().unwrap();
+        let castling_rights = chess_set::Bitboard::from_squares(&chessboard.castling_rights);
+
+        let mut position = Self {
+            chessboard: chess_set::BitboardChessboard::from_chessboard(chessboard),
+            to_play_colour,
+            castling_rights,
+            en_passant_square: chessboard.en_passant_square,
+            halfmove_clock,
+            zobrist_hash: 0,
+            checks_given: check::ChecksGiven::default(),
+            undo_history: vec![],
+            previous_zobrist_hashes: vec![],
+        };
+        position.zobrist_hash = position.compute_zobrist_hash();
+        position
+    }
+
+    // Mutators.
+
+    /// Make a legal move, remembering enough to unmake it again.
+    pub fn make_move(&mut self, chess_move: &chess_move::Move) {
+        self.previous_zobrist_hashes.push(self.zobrist_hash);
+        let zobrist_hash = self.zobrist_hash;
+        // Remove the game state from the hash, and add it back once it has been updated.
+        self.zobrist_hash ^= self.get_game_state_zobrist_key();
+
+        let piece = chess_move.piece;
+        let rank = *chess_move.from_square.get_rank();
+        let castling_rook_square =
+            pieces::get_castling_rook_square(chess_move, self.castling_rights);
+        self.remove_piece(&chess_move.from_square);
+
+        let captured_piece = match piece.get_piece_type() {
+            chess_set::PieceType::King if castling_rook_square.is_some() => None,
+            chess_set::PieceType::Pawn if Some(chess_move.to_square) == self.en_passant_square => {
+                let captured_square =
+                    chess_set::Square::new(rank, *chess_move.to_square.get_file());
+                self.remove_piece(&captured_square)
+                    .map(|captured_piece| (captured_square, captured_piece))
+            }
+            _ => self
+                .remove_piece(&chess_move.to_square)
+                .map(|captured_piece| (chess_move.to_square, captured_piece)),
+        };
+
+        let to_square = match castling_rook_square {
+            Some(rook_square) => {
+                let (king_to_square, rook_to_square) =
+                    pieces::get_castled_squares(&chess_move.from_square, &rook_square);
+                let rook = self.remove_piece(&rook_square).unwrap();
+                self.put_piece(&rook_to_square, &rook);
+                king_to_square
+            }
+            None => chess_move.to_square,
+        };
+
+        let moved_piece = match chess_move.promote_to {
+            Some(promote_to) => chess_set::Piece::new(*piece.get_colour(), promote_to),
+            None => piece,
+        };
+        self.put_piece(&to_square, &moved_piece);
+
+        self.undo_history.push(Undo {
+            chess_move: chess_move.clone(),
+            captured_piece,
+            castling_rights: self.castling_rights,
+            en_passant_square: self.en_passant_square,
+            halfmove_clock: self.halfmove_clock,
+            zobrist_hash,
+            checks_given: self.checks_given,
+        });
+
+        // Castling rights are lost when the king or rook moves, or the rook is captured.
+        let mut lost_castling_rights = chess_set::Bitboard::from_square(&chess_move.from_square)
+            | chess_set::Bitboard::from_square(&chess_move.to_square);
+        if piece.get_piece_type() == &chess_set::PieceType::King {
+            // The king's rooks could be on any file of its rank (e.g. in Chess960).
+            let rank_squares = chess_set::Bitboard::new(0xff << (8 * (rank.index() - 1)));
+            lost_castling_rights = lost_castling_rights | rank_squares;
+        };
+        self.castling_rights = self.castling_rights & !lost_castling_rights;
+
+        self.en_passant_square = match piece.get_piece_type() {
+            chess_set::PieceType::Pawn if chess_move.translation.scalar == 2 => {
+                let rank_index = (chess_move.from_square.get_rank().index()
+                    + chess_move.to_square.get_rank().index())
+                    / 2;
+                let file_index = chess_move.from_square.get_file().index();
+                Some(chess_set::Square::from_indexes(rank_index, file_index))
+            }
+            _ => None,
+        };
+
+        let is_irreversible =
+            captured_piece.is_some() || piece.get_piece_type() == &chess_set::PieceType::Pawn;
+        self.halfmove_clock = match is_irreversible {
+            true => 0,
+            false => self.halfmove_clock + 1,
+        };
+        self.to_play_colour = self.to_play_colour.swap();
+        self.zobrist_hash ^= self.get_game_state_zobrist_key();
+
+        if self.chessboard.is_player_in_check(&self.to_play_colour) {
+            self.checks_given.add_check(piece.get_colour());
+        };
+    }
+
+    /// Unmake the most recently made move.
+    pub fn unmake_move(&mut self) {
+        let undo = self.undo_history.pop().expect("No move to unmake.");
+        self.previous_zobrist_hashes.pop();
+        let chess_move = &undo.chess_move;
+
+        match pieces::get_castling_rook_square(chess_move, undo.castling_rights) {
+            Some(rook_square) => {
+                let (king_to_square, rook_to_square) =
+                    pieces::get_castled_squares(&chess_move.from_square, &rook_square);
+                self.chessboard.remove_piece(&king_to_square);
+                let rook = self.chessboard.remove_piece(&rook_to_square).unwrap();
+                self.chessboard.put_piece(&rook_square, &rook);
+            }
+            None => {
+                self.chessboard.remove_piece(&chess_move.to_square);
+            }
+        };
+        self.chessboard
+            .put_piece(&chess_move.from_square, &chess_move.piece);
+
+        if let Some((captured_square, captured_piece)) = undo.captured_piece {
+            self.chessboard.put_piece(&captured_square, &captured_piece);
+        };
+
+        self.castling_rights = undo.castling_rights;
+        self.en_passant_square = undo.en_passant_square;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.zobrist_hash = undo.zobrist_hash;
+        self.checks_given = undo.checks_given;
+        self.to_play_colour = self.to_play_colour.swap();
+    }
+
+    fn remove_piece(&mut self, square: &chess_set::Square) -> Option<chess_set::Piece> {
+        let removed_piece = self.chessboard.remove_piece(square)?;
+        self.zobrist_hash ^= chess_set::zobrist::get_piece_key(&removed_piece, square);
+        Some(removed_piece)
+    }
+
+    fn put_piece(&mut self, square: &chess_set::Square, piece: &chess_set::Piece) {
+        self.chessboard.put_piece(square, piece);
+        self.zobrist_hash ^= chess_set::zobrist::get_piece_key(piece, square);
+    }
+
+    // Queries.
+
+    pub fn get_chessboard(&self) -> &chess_set::BitboardChessboard {
+        &self.chessboard
+    }
+
+    pub fn get_to_play_colour(&self) -> chess_set::Colour {
+        self.to_play_colour
+    }
+
+    pub fn get_zobrist_hash(&self) -> u64 {
+        self.zobrist_hash
+    }
+
+    pub fn get_checks_given(&self, player: &chess_set::Colour) -> u8 {
+        self.checks_given.get(player)
+    }
+
+    pub fn get_legal_moves(&self) -> Vec<chess_move::Move> {
+        generation::generate_legal_moves_for_position(
+            self.to_play_colour,
+            &self.chessboard,
+            self.en_passant_square,
+            self.castling_rights,
+        )
+    }
+
+    /// Get the outcome of the game at this position, if the game has ended.
+    ///
+    /// The legal moves in the position must be passed, since they are needed to
+    /// detect checkmate and stalemate, and will usually have been generated anyway.
+    pub fn get_outcome(&self, legal_moves: &[chess_move::Move]) -> Option<Outcome> {
+        // Draws are checked in the same order as `draws::is_draw`, which shares its check
+        // for insufficient material. That is only met by bare kings, which can never
+        // checkmate, so checking for it before checkmate changes nothing.
+        if draws::is_insufficient_material(&self.chessboard) {
+            return Some(Outcome::Drawn(draws::Draw::InsufficientMaterial));
+        };
+        self.get_outcome_regardless_of_material(legal_moves)
+    }
+
+    /// Get the outcome of the game at this position, if the game has ended, without
+    /// drawing games where neither player has the material to checkmate.
+    ///
+    /// This is for variants that can be won without checkmating, e.g. King of the Hill.
+    pub fn get_outcome_regardless_of_material(
+        &self,
+        legal_moves: &[chess_move::Move],
+    ) -> Option<Outcome> {
+        if legal_moves.is_empty() && self.chessboard.is_player_in_check(&self.to_play_colour) {
+            return Some(Outcome::Won(self.to_play_colour.swap()));
+        };
+        if legal_moves.is_empty() {
+            return Some(Outcome::Drawn(draws::Draw::Stalemate));
+        };
+        if self.halfmove_clock >= SEVENTY_FIVE_MOVE_RULE_HALFMOVES {
+            return Some(Outcome::Drawn(draws::Draw::SeventyFiveMoveRule));
+        };
+        if self.get_repetition_count() >= FIVEFOLD_REPETITION_COUNT {
+            return Some(Outcome::Drawn(draws::Draw::Repetition));
+        };
+
+        None
+    }
+
+    /// Count the number of times the current position has occurred, including now.
+    ///
+    /// Positions are compared by their Zobrist hash.
+    pub fn get_repetition_count(&self) -> usize {
+        let mut repetition_count = 1;
+        for previous_zobrist_hash in self
+            .previous_zobrist_hashes
+            .iter()
+            .rev()
+            .take(self.halfmove_clock as usize)
+            .skip(1)
+            .step_by(2)
+        {
+            if previous_zobrist_hash == &self.zobrist_hash {
+                repetition_count += 1;
+            };
+        }
+        repetition_count
+    }
+
+    fn compute_zobrist_hash(&self) -> u64 {
+        chess_set::zobrist::get_zobrist_hash(
+            &self.chessboard,
+            &self.to_play_colour,
+            self.castling_rights,
+            self.get_legal_en_passant_square(),
+        )
+    }
+
+    /// Get the part of the Zobrist hash that doesn't come from the pieces.
+    fn get_game_state_zobrist_key(&self) -> u64 {
+        chess_set::zobrist::get_to_play_key(&self.to_play_colour)
+            ^ chess_set::zobrist::get_castling_rights_key(self.castling_rights)
+            ^ chess_set::zobrist::get_en_passant_key(self.get_legal_en_passant_square())
+    }
+
+    fn get_legal_en_passant_square(&self) -> Option<chess_set::Square> {
+        generation::get_legal_en_passant_square(
+            self.to_play_colour,
+            &self.chessboard,
+            self.en_passant_square,
+        )
+    }
+}
+
+/// Get the Zobrist hash of the latest position in a chessboard history.
+pub fn get_zobrist_hash(
+    to_play_colour: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> u64 {
+    let halfmove_clock = draws::get_halfmove_clock(0, chessboard_history);
+    SearchPosition::from_state(to_play_colour, chessboard_history, halfmove_clock).zobrist_hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::rulebook::{fen, uci};
+    use rstest::rstest;
+
+    fn position_from_fen(fen: &str) -> SearchPosition {
+        let fen = fen::Fen::parse(fen).unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+        SearchPosition::from_chessboard_history(
+            fen.to_play_colour,
+            fen.halfmove_clock,
+            fen.checks_given.unwrap_or_default(),
+            &chessboard_history,
+        )
+    }
+
+    fn find_move(position: &SearchPosition, uci_move: &str) -> chess_move::Move {
+        position
+            .get_legal_moves()
+            .into_iter()
+            .find(|chess_move| uci::to_uci(chess_move) == uci_move)
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case::quiet_move("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1", "a1b1")]
+    #[case::capture("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1", "a1a8")]
+    #[case::en_passant("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1", "e5d6")]
+    #[case::castle_kingside("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1", "e1g1")]
+    #[case::castle_queenside("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1", "e1c1")]
+    #[case::chess960_castle_swapping_king_and_rook(
+        "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1",
+        "f1g1"
+    )]
+    #[case::chess960_castle_with_king_in_place("1r4kr/8/8/8/8/8/8/1R4KR b KQkq - 0 1", "g8h8")]
+    #[case::promotion("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7b8n")]
+    fn unmaking_move_restores_position(#[case] fen: &str, #[case] uci_move: &str) {
+        let mut position = position_from_fen(fen);
+        let original_position = position.clone();
+        let original_legal_moves = position.get_legal_moves().len();
+
+        let chess_move = find_move(&position, uci_move);
+        position.make_move(&chess_move);
+        position.unmake_move();
+
+        assert_eq!(position.chessboard, original_position.chessboard);
+        assert_eq!(position.castling_rights, original_position.castling_rights);
+        assert_eq!(position.zobrist_hash, original_position.zobrist_hash);
+        assert_eq!(
+            position.en_passant_square,
+            original_position.en_passant_square
+        );
+        assert_eq!(position.halfmove_clock, original_position.halfmove_clock);
+        assert_eq!(position.get_legal_moves().len(), original_legal_moves);
+    }
+
+    #[test]
+    fn zobrist_hash_is_updated_incrementally() {
+        let mut position = position_from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        );
+
+        for chess_move in position.get_legal_moves() {
+            position.make_move(&chess_move);
+            for reply in position.get_legal_moves() {
+                position.make_move(&reply);
+                assert_eq!(position.zobrist_hash, position.compute_zobrist_hash());
+                position.unmake_move();
+            }
+            assert_eq!(position.zobrist_hash, position.compute_zobrist_hash());
+            position.unmake_move();
+        }
+    }
+
+    #[test]
+    fn transposed_positions_have_same_zobrist_hash() {
+        let mut position =
+            position_from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let mut transposed_position = position.clone();
+
+        for uci_move in ["g1f3", "g8f6", "b1c3"] {
+            let chess_move = find_move(&position, uci_move);
+            position.make_move(&chess_move);
+        }
+        for uci_move in ["b1c3", "g8f6", "g1f3"] {
+            let chess_move = find_move(&transposed_position, uci_move);
+            transposed_position.make_move(&chess_move);
+        }
+
+        assert_eq!(
+            position.get_zobrist_hash(),
+            transposed_position.get_zobrist_hash()
+        );
+    }
+
+    #[test]
+    fn uncapturable_en_passant_square_does_not_change_zobrist_hash() {
+        let with_en_passant_square =
+            position_from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        let without_en_passant_square =
+            position_from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+
+        assert_eq!(
+            with_en_passant_square.get_zobrist_hash(),
+            without_en_passant_square.get_zobrist_hash()
+        );
+    }
+
+    #[test]
+    fn castling_moves_rook_and_loses_castling_rights() {
+        let mut position = position_from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+
+        let chess_move = find_move(&position, "e1g1");
+        position.make_move(&chess_move);
+
+        let rook_square = chess_set::Square::new(chess_set::Rank::One, chess_set::File::F);
+        let rook = chess_set::Piece::new(chess_set::Colour::White, chess_set::PieceType::Rook);
+        assert_eq!(
+            position.get_chessboard().get_piece(&rook_square),
+            Some(rook)
+        );
+        assert_eq!(position.castling_rights.count(), 2);
+    }
+
+    #[test]
+    fn counts_repeated_positions() {
+        let mut position = position_from_fen("4k3/8/8/8/8/8/8/1R2K3 w - - 0 1");
+
+        for uci_move in ["b1b2", "e8d8", "b2b1", "d8e8"] {
+            let chess_move = find_move(&position, uci_move);
+            position.make_move(&chess_move);
+        }
+
+        assert_eq!(position.get_repetition_count(), 2);
+        assert_eq!(position.get_outcome(&position.get_legal_moves()), None);
+    }
+
+    #[rstest]
+    #[case::checkmate(
+        "k7/1Q6/1K6/8/8/8/8/8 b - - 0 1",
+        Some(Outcome::Won(chess_set::Colour::White))
+    )]
+    #[case::stalemate(
+        "k7/2Q5/1K6/8/8/8/8/8 b - - 0 1",
+        Some(Outcome::Drawn(draws::Draw::Stalemate))
+    )]
+    #[case::insufficient_material(
+        "k7/8/1K6/8/8/8/8/8 b - - 0 1",
+        Some(Outcome::Drawn(draws::Draw::InsufficientMaterial))
+    )]
+    #[case::in_progress("k7/8/1K6/8/8/8/8/1Q6 b - - 0 1", None)]
+    fn gets_outcome(#[case] fen: &str, #[case] expected_outcome: Option<Outcome>) {
+        let position = position_from_fen(fen);
+
+        let outcome = position.get_outcome(&position.get_legal_moves());
+
+        assert_eq!(outcome, expected_outcome);
+    }
+
+    #[rstest]
+    #[case::bare_kings("k7/8/1K6/8/8/8/8/8 b - - 0 1")]
+    #[case::king_and_bishop("k7/8/1K6/8/8/8/8/5B2 b - - 0 1")]
+    #[case::king_and_knight("k7/8/1K6/8/8/8/8/5N2 b - - 0 1")]
+    fn insufficient_material_agrees_with_chessboard_history(#[case] fen: &str) {
+        let position = position_from_fen(fen);
+        let chessboard_history = fen::Fen::parse(fen).unwrap().to_chessboard_history();
+
+        let outcome = position.get_outcome(&position.get_legal_moves());
+
+        let expected_outcome =
+            draws::is_draw(chess_set::Colour::Black, 0, &chessboard_history).map(Outcome::Drawn);
+        assert_eq!(outcome, expected_outcome);
+    }
+
+    #[test]
+    fn bare_kings_are_not_drawn_regardless_of_material() {
+        let position = position_from_fen("k7/8/1K6/8/8/8/8/8 b - - 0 1");
+
+        let outcome = position.get_outcome_regardless_of_material(&position.get_legal_moves());
+
+        assert_eq!(outcome, None);
+    }
+
+    #[test]
+    fn counts_checks_given_as_moves_are_made_and_unmade() {
+        let mut position = position_from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1");
+
+        let check = find_move(&position, "a1a8");
+        position.make_move(&check);
+
+        assert_eq!(position.get_checks_given(&chess_set::Colour::White), 1);
+        assert_eq!(position.get_checks_given(&chess_set::Colour::Black), 0);
+
+        position.unmake_move();
+
+        assert_eq!(position.get_checks_given(&chess_set::Colour::White), 0);
+    }
+}
@@ -1,10 +1,62 @@
+use super::moves::pieces;
 use crate::domain::chess_set;
 use std::collections::BTreeMap;
 
+/// The number of distinct starting positions in Chess960.
+pub const CHESS960_POSITION_COUNT: u16 = 960;
+
+/// The empty squares the two knights are placed on in Chess960, indexed by the knights'
+/// digit of the position's number.
+const CHESS960_KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 0),
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (1, 1),
+    (1, 2),
+    (1, 3),
+    (2, 2),
+    (2, 3),
+    (3, 3),
+];
+
+/// Get the chessboard a standard game starts from, with both players able to castle either side.
+pub fn get_official_starting_chessboard() -> chess_set::Chessboard {
+    let mut chessboard = chess_set::Chessboard::new(get_official_starting_position());
+    chessboard.castling_rights = pieces::get_possible_castling_rights(&chessboard);
+    chessboard
+}
+
+/// Get the chessboard a Chess960 game starts from, given the position's number (0 to 959).
+///
+/// Both players can castle with either rook. Position 518 is the standard starting position.
+pub fn get_chess960_starting_chessboard(index: u16) -> Option<chess_set::Chessboard> {
+    let mut chessboard = chess_set::Chessboard::new(get_chess960_starting_position(index)?);
+    chessboard.castling_rights = pieces::get_possible_castling_rights(&chessboard);
+    Some(chessboard)
+}
+
 pub fn get_official_starting_position() -> BTreeMap<chess_set::Square, chess_set::Piece> {
+    get_starting_position(get_home_rank())
+}
+
+/// Get one of the 960 starting positions of Chess960, numbered 0 to 959.
+///
+/// The numbering is the standard one (Scharnagl's), and the black pieces mirror the white.
+pub fn get_chess960_starting_position(
+    index: u16,
+) -> Option<BTreeMap<chess_set::Square, chess_set::Piece>> {
+    if index >= CHESS960_POSITION_COUNT {
+        return None;
+    };
+    Some(get_starting_position(get_chess960_home_rank(index)))
+}
+
+fn get_starting_position(
+    home_rank: Vec<chess_set::PieceType>,
+) -> BTreeMap<chess_set::Square, chess_set::Piece> {
     let mut starting_position = BTreeMap::new();
 
-    let home_rank = get_home_rank();
     for (index, file) in chess_set::File::iter().enumerate() {
         // Add the pieces.
         let piece_type = home_rank[index];
@@ -43,12 +95,66 @@ fn get_home_rank() -> Vec<chess_set::PieceType> {
     ];
 }
 
+/// Place the pieces of a Chess960 home rank, one digit of the position's number at a time.
+///
+/// The bishops go on opposite-coloured squares, then the queen and knights go on the
+/// empty squares, and the king goes between the rooks on the three squares left over.
+fn get_chess960_home_rank(index: u16) -> Vec<chess_set::PieceType> {
+    let mut home_rank: Vec<Option<chess_set::PieceType>> = vec![None; 8];
+    let mut index = index as usize;
+
+    // The light-squared bishop is on the b-, d-, f- or h-file.
+    home_rank[2 * (index % 4) + 1] = Some(chess_set::PieceType::Bishop);
+    index /= 4;
+    // The dark-squared bishop is on the a-, c-, e- or g-file.
+    home_rank[2 * (index % 4)] = Some(chess_set::PieceType::Bishop);
+    index /= 4;
+
+    place_on_empty_square(&mut home_rank, index % 6, chess_set::PieceType::Queen);
+    index /= 6;
+
+    // The knights are placed in the order of the ten ways of choosing two of the five
+    // empty squares, with the second knight's square counted once the first is placed.
+    let (first_knight, second_knight) = CHESS960_KNIGHT_PLACEMENTS[index];
+    place_on_empty_square(&mut home_rank, first_knight, chess_set::PieceType::Knight);
+    place_on_empty_square(&mut home_rank, second_knight, chess_set::PieceType::Knight);
+
+    for piece_type in [
+        chess_set::PieceType::Rook,
+        chess_set::PieceType::King,
+        chess_set::PieceType::Rook,
+    ] {
+        place_on_empty_square(&mut home_rank, 0, piece_type);
+    }
+
+    home_rank.into_iter().map(Option::unwrap).collect()
+}
+
+fn place_on_empty_square(
+    home_rank: &mut [Option<chess_set::PieceType>],
+    empty_square_index: usize,
+    piece_type: chess_set::PieceType,
+) {
+    let file_index = home_rank
+        .iter()
+        .enumerate()
+        .filter(|(_, maybe_piece_type)| maybe_piece_type.is_none())
+        .nth(empty_square_index)
+        .map(|(file_index, _)| file_index)
+        .unwrap();
+    home_rank[file_index] = Some(piece_type);
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
         chess_set::{Colour, File, Piece, PieceType, Rank, Square},
-        get_official_starting_position,
+        get_chess960_starting_chessboard, get_chess960_starting_position,
+        get_official_starting_chessboard, get_official_starting_position,
     };
+    use crate::domain::rulebook::Fen;
+    use rstest::rstest;
+    use std::collections::HashSet;
 
     #[test]
     fn creates_btree_map_representing_starting_position() {
@@ -73,4 +179,76 @@ mod tests {
             assert_eq!(starting_position.get(&check.0).unwrap(), &check.1);
         }
     }
+
+    #[test]
+    fn starting_chessboard_has_every_castling_right() {
+        let chessboard = get_official_starting_chessboard();
+
+        let expected_castling_rights = vec![
+            Square::new(Rank::One, File::A),
+            Square::new(Rank::One, File::H),
+            Square::new(Rank::Eight, File::A),
+            Square::new(Rank::Eight, File::H),
+        ];
+        assert_eq!(chessboard.castling_rights, expected_castling_rights);
+        assert_eq!(chessboard.en_passant_square, None);
+    }
+
+    #[rstest]
+    #[case::first(0, "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1")]
+    #[case::standard(518, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")]
+    #[case::last(959, "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1")]
+    fn chess960_positions_are_numbered_in_standard_order(#[case] index: u16, #[case] fen: &str) {
+        let chessboard = get_chess960_starting_chessboard(index).unwrap();
+
+        let starting_fen = Fen::from_chessboard_history(Colour::White, 0, 1, &[chessboard]);
+        assert_eq!(starting_fen.to_string(), fen);
+    }
+
+    #[test]
+    fn every_chess960_position_is_distinct_and_valid() {
+        let mut home_ranks = HashSet::new();
+
+        for index in 0..960 {
+            let starting_position = get_chess960_starting_position(index).unwrap();
+            let home_rank: Vec<PieceType> = File::iter()
+                .map(|file| {
+                    let square = Square::new(Rank::One, file);
+                    *starting_position.get(&square).unwrap().get_piece_type()
+                })
+                .collect();
+            let files_of = |piece_type: PieceType| -> Vec<usize> {
+                (0..8)
+                    .filter(|index| home_rank[*index] == piece_type)
+                    .collect()
+            };
+
+            let bishop_files = files_of(PieceType::Bishop);
+            assert_ne!(bishop_files[0] % 2, bishop_files[1] % 2);
+            let rook_files = files_of(PieceType::Rook);
+            let king_file = files_of(PieceType::King)[0];
+            assert!(rook_files[0] < king_file && king_file < rook_files[1]);
+            assert_eq!(files_of(PieceType::Knight).len(), 2);
+            assert_eq!(files_of(PieceType::Queen).len(), 1);
+
+            home_ranks.insert(format!("{:?}", home_rank));
+        }
+
+        assert_eq!(home_ranks.len(), 960);
+        assert!(get_chess960_starting_position(960).is_none());
+    }
+
+    #[test]
+    fn chess960_chessboard_has_castling_rights_for_both_rooks() {
+        // Position 0 is BBQNNRKR, with the rooks on the f- and h-files.
+        let chessboard = get_chess960_starting_chessboard(0).unwrap();
+
+        let expected_castling_rights = vec![
+            Square::new(Rank::One, File::F),
+            Square::new(Rank::One, File::H),
+            Square::new(Rank::Eight, File::F),
+            Square::new(Rank::Eight, File::H),
+        ];
+        assert_eq!(chessboard.castling_rights, expected_castling_rights);
+    }
 }
@@ -0,0 +1,314 @@
+use crate::domain::chess_set;
+
+/// A piece that cannot leave the line between its king and an opponent's sliding piece.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pin {
+    pub pinned_square: chess_set::Square,
+    pub pinning_square: chess_set::Square,
+    /// The squares the pinned piece can still move to: those between the king and the
+    /// pinning piece, and the pinning piece's own square.
+    pub ray: Vec<chess_set::Square>,
+}
+
+/// A sliding piece attacking a piece, which would expose a less valuable piece behind it if moved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Skewer {
+    pub skewering_square: chess_set::Square,
+    pub front_square: chess_set::Square,
+    pub rear_square: chess_set::Square,
+}
+
+/// A piece blocking its own sliding piece, which would attack an opponent's piece if moved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscoveredAttack {
+    pub moving_square: chess_set::Square,
+    pub attacking_square: chess_set::Square,
+    pub target_square: chess_set::Square,
+}
+
+/// A sliding piece, the first piece along one of its lines, and the piece behind that.
+struct XRay {
+    slider_square: chess_set::Square,
+    front_square: chess_set::Square,
+    rear_square: chess_set::Square,
+}
+
+/// Get the player's pieces that are absolutely pinned to their king.
+pub fn get_pins(player: chess_set::Colour, chessboard: &chess_set::Chessboard) -> Vec<Pin> {
+    find_pins(
+        player,
+        &chess_set::BitboardChessboard::from_chessboard(chessboard),
+    )
+}
+
+/// Get the opponent's pieces the player is skewering.
+pub fn get_skewers(player: chess_set::Colour, chessboard: &chess_set::Chessboard) -> Vec<Skewer> {
+    let chessboard = chess_set::BitboardChessboard::from_chessboard(chessboard);
+    let opponent = player.swap();
+
+    get_x_rays(player, &chessboard)
+        .into_iter()
+        .filter_map(|x_ray| {
+            let front_piece = chessboard.get_piece(&x_ray.front_square).unwrap();
+            let rear_piece = chessboard.get_piece(&x_ray.rear_square).unwrap();
+            let is_skewer = front_piece.get_colour() == &opponent
+                && rear_piece.get_colour() == &opponent
+                && get_piece_value(front_piece.get_piece_type())
+                    > get_piece_value(rear_piece.get_piece_type());
+            is_skewer.then_some(Skewer {
+                skewering_square: x_ray.slider_square,
+                front_square: x_ray.front_square,
+                rear_square: x_ray.rear_square,
+            })
+        })
+        .collect()
+}
+
+/// Get the player's pieces that would uncover an attack on an opponent's piece by moving.
+pub fn get_discovered_attacks(
+    player: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> Vec<DiscoveredAttack> {
+    let chessboard = chess_set::BitboardChessboard::from_chessboard(chessboard);
+
+    get_x_rays(player, &chessboard)
+        .into_iter()
+        .filter_map(|x_ray| {
+            let front_piece = chessboard.get_piece(&x_ray.front_square).unwrap();
+            let rear_piece = chessboard.get_piece(&x_ray.rear_square).unwrap();
+            let is_discovered_attack =
+                front_piece.get_colour() == &player && rear_piece.get_colour() == &player.swap();
+            is_discovered_attack.then_some(DiscoveredAttack {
+                moving_square: x_ray.front_square,
+                attacking_square: x_ray.slider_square,
+                target_square: x_ray.rear_square,
+            })
+        })
+        .collect()
+}
+
+/// Get the player's pieces that would give check by moving out of their slider's way.
+///
+/// Note the check is only discovered if the piece moves off the line it is blocking.
+pub fn get_discovered_checks(
+    player: chess_set::Colour,
+    chessboard: &chess_set::Chessboard,
+) -> Vec<DiscoveredAttack> {
+    let opponent_king = chess_set::Piece::new(player.swap(), chess_set::PieceType::King);
+
+    get_discovered_attacks(player, chessboard)
+        .into_iter()
+        .filter(|discovered_attack| {
+            chessboard.get_piece(&discovered_attack.target_square) == Some(opponent_king)
+        })
+        .collect()
+}
+
+pub(super) fn find_pins(
+    player: chess_set::Colour,
+    chessboard: &chess_set::BitboardChessboard,
+) -> Vec<Pin> {
+    let king = chess_set::Piece::new(player, chess_set::PieceType::King);
+
+    get_x_rays(player.swap(), chessboard)
+        .into_iter()
+        .filter(|x_ray| {
+            chessboard
+                .get_piece(&x_ray.front_square)
+                .unwrap()
+                .get_colour()
+                == &player
+                && chessboard.get_piece(&x_ray.rear_square) == Some(king)
+        })
+        .map(|x_ray| {
+            let ray = chess_set::get_squares_between(&x_ray.rear_square, &x_ray.slider_square)
+                | chess_set::Bitboard::from_square(&x_ray.slider_square);
+            Pin {
+                pinned_square: x_ray.front_square,
+                pinning_square: x_ray.slider_square,
+                ray: ray.iter().collect(),
+            }
+        })
+        .collect()
+}
+
+/// Get every pair of pieces lined up behind one another along the lines of a player's sliders.
+fn get_x_rays(player: chess_set::Colour, chessboard: &chess_set::BitboardChessboard) -> Vec<XRay> {
+    let occupied_squares = chessboard.get_all_occupied_squares();
+
+    let mut x_rays = vec![];
+    for slider_square in chessboard.get_occupied_squares(&player).iter() {
+        let slider = chessboard.get_piece(&slider_square).unwrap();
+        if !is_sliding_piece(slider.get_piece_type()) {
+            continue;
+        };
+
+        let attacks = chess_set::get_piece_attacks(&slider, &slider_square, occupied_squares);
+        for front_square in (attacks & occupied_squares).iter() {
+            // Lifting the front piece off the board reveals the next piece along the same line.
+            let mut remaining_squares = occupied_squares;
+            remaining_squares.remove(&front_square);
+            let x_ray_attacks =
+                chess_set::get_piece_attacks(&slider, &slider_square, remaining_squares);
+
+            let rear_square = (x_ray_attacks & remaining_squares).iter().find(|square| {
+                chess_set::get_squares_between(&slider_square, square).contains(&front_square)
+            });
+            if let Some(rear_square) = rear_square {
+                x_rays.push(XRay {
+                    slider_square,
+                    front_square,
+                    rear_square,
+                });
+            };
+        }
+    }
+    x_rays
+}
+
+fn is_sliding_piece(piece_type: &chess_set::PieceType) -> bool {
+    matches!(
+        piece_type,
+        chess_set::PieceType::Bishop | chess_set::PieceType::Rook | chess_set::PieceType::Queen
+    )
+}
+
+/// The conventional value of each piece, with the king worth more than everything else.
+fn get_piece_value(piece_type: &chess_set::PieceType) -> u8 {
+    match piece_type {
+        chess_set::PieceType::Pawn => 1,
+        chess_set::PieceType::Knight => 3,
+        chess_set::PieceType::Bishop => 3,
+        chess_set::PieceType::Rook => 5,
+        chess_set::PieceType::Queen => 9,
+        chess_set::PieceType::King => u8::MAX,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, Square};
+    use crate::domain::rulebook::Fen;
+    use crate::testing::factories;
+
+    fn chessboard_from_fen(fen: &str) -> chess_set::Chessboard {
+        Fen::parse(fen)
+            .unwrap()
+            .to_chessboard_history()
+            .pop()
+            .unwrap()
+    }
+
+    fn square(notation: &str) -> Square {
+        Square::from_algebraic(notation).unwrap()
+    }
+
+    #[cfg(test)]
+    mod pin_tests {
+        use super::*;
+
+        #[test]
+        fn finds_piece_pinned_by_bishop() {
+            let chessboard = chessboard_from_fen("4k3/8/8/b7/8/2N5/8/4K3 w - - 0 1");
+
+            let pins = get_pins(Colour::White, &chessboard);
+
+            let expected_pin = Pin {
+                pinned_square: square("c3"),
+                pinning_square: square("a5"),
+                ray: vec![square("d2"), square("c3"), square("b4"), square("a5")],
+            };
+            assert_eq!(pins, vec![expected_pin]);
+        }
+
+        #[test]
+        fn piece_is_not_pinned_when_another_piece_also_blocks() {
+            let chessboard = chessboard_from_fen("4r1k1/8/8/8/4P3/4N3/8/4K3 w - - 0 1");
+
+            let pins = get_pins(Colour::White, &chessboard);
+
+            assert_eq!(pins, vec![]);
+        }
+
+        #[test]
+        fn opponent_piece_between_king_and_slider_is_not_pinned() {
+            let chessboard = chessboard_from_fen("4r1k1/8/8/8/4p3/8/8/4K3 w - - 0 1");
+
+            let pins = get_pins(Colour::White, &chessboard);
+
+            assert_eq!(pins, vec![]);
+        }
+
+        #[test]
+        fn no_pins_in_starting_position() {
+            let chessboard = factories::chessboard();
+
+            assert_eq!(get_pins(Colour::White, &chessboard), vec![]);
+            assert_eq!(get_pins(Colour::Black, &chessboard), vec![]);
+        }
+    }
+
+    #[cfg(test)]
+    mod skewer_tests {
+        use super::*;
+
+        #[test]
+        fn finds_skewer_of_king_and_rook() {
+            let chessboard = chessboard_from_fen("8/8/r3k2R/8/8/8/8/4K3 b - - 0 1");
+
+            let skewers = get_skewers(Colour::White, &chessboard);
+
+            let expected_skewer = Skewer {
+                skewering_square: square("h6"),
+                front_square: square("e6"),
+                rear_square: square("a6"),
+            };
+            assert_eq!(skewers, vec![expected_skewer]);
+        }
+
+        #[test]
+        fn more_valuable_piece_behind_is_not_a_skewer() {
+            let chessboard = chessboard_from_fen("4k3/8/q2n3R/8/8/8/8/4K3 w - - 0 1");
+
+            let skewers = get_skewers(Colour::White, &chessboard);
+
+            assert_eq!(skewers, vec![]);
+        }
+    }
+
+    #[cfg(test)]
+    mod discovered_attack_tests {
+        use super::*;
+
+        #[test]
+        fn finds_discovered_check() {
+            let chessboard = chessboard_from_fen("4k3/8/8/8/4N3/8/8/K3R3 w - - 0 1");
+
+            let discovered_checks = get_discovered_checks(Colour::White, &chessboard);
+
+            let expected_discovered_check = DiscoveredAttack {
+                moving_square: square("e4"),
+                attacking_square: square("e1"),
+                target_square: square("e8"),
+            };
+            assert_eq!(discovered_checks, vec![expected_discovered_check]);
+        }
+
+        #[test]
+        fn finds_discovered_attack_on_piece_other_than_king() {
+            let chessboard = chessboard_from_fen("4k3/8/8/q7/8/2N5/8/K3B3 w - - 0 1");
+
+            let discovered_attacks = get_discovered_attacks(Colour::White, &chessboard);
+            let discovered_checks = get_discovered_checks(Colour::White, &chessboard);
+
+            let expected_discovered_attack = DiscoveredAttack {
+                moving_square: square("c3"),
+                attacking_square: square("e1"),
+                target_square: square("a5"),
+            };
+            assert_eq!(discovered_attacks, vec![expected_discovered_attack]);
+            assert_eq!(discovered_checks, vec![]);
+        }
+    }
+}
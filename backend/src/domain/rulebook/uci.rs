@@ -0,0 +1,157 @@
+use super::moves::chess_move;
+use crate::domain::chess_set;
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum UciError {
+    #[error("'{0}' is not a valid UCI move.")]
+    InvalidUci(String),
+
+    #[error("There is no piece to move at {0}.")]
+    NoPieceAtFromSquare(String),
+}
+
+/// The squares and promotion piece type read from a UCI move.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UciMove {
+    pub from_square: chess_set::Square,
+    pub to_square: chess_set::Square,
+    pub promote_to: Option<chess_set::PieceType>,
+}
+
+/// Parse a move in UCI's long algebraic notation, e.g. `e2e4`, `e7e8q` or `e1g1`.
+///
+/// Castling is written as the king's move.
+pub fn parse_uci(uci: &str) -> Result<UciMove, UciError> {
+    let invalid_uci = || UciError::InvalidUci(uci.to_string());
+    if !uci.is_ascii() || !(4..=5).contains(&uci.len()) {
+        return Err(invalid_uci());
+    };
+
+    let from_square = chess_set::Square::from_algebraic(&uci[0..2]).ok_or_else(invalid_uci)?;
+    let to_square = chess_set::Square::from_algebraic(&uci[2..4]).ok_or_else(invalid_uci)?;
+    let promote_to = match uci.chars().nth(4) {
+        Some(letter) => match chess_set::PieceType::from_notation(letter.to_ascii_uppercase()) {
+            Some(chess_set::PieceType::Pawn) | Some(chess_set::PieceType::King) | None => {
+                return Err(invalid_uci())
+            }
+            piece_type => piece_type,
+        },
+        None => None,
+    };
+
+    Ok(UciMove {
+        from_square,
+        to_square,
+        promote_to,
+    })
+}
+
+/// Read a UCI move as a move of the piece on its from square.
+///
+/// The move is not validated against the rules of chess.
+pub fn from_uci(
+    uci: &str,
+    chessboard: &chess_set::Chessboard,
+) -> Result<chess_move::Move, UciError> {
+    let uci_move = parse_uci(uci)?;
+    let Some(piece) = chessboard.get_piece(&uci_move.from_square) else {
+        return Err(UciError::NoPieceAtFromSquare(
+            uci_move.from_square.to_algebraic(),
+        ));
+    };
+
+    let chess_move = match uci_move.promote_to {
+        Some(promote_to) => chess_move::Move::new_promotion(
+            piece,
+            uci_move.from_square,
+            uci_move.to_square,
+            promote_to,
+        ),
+        None => chess_move::Move::new(piece, uci_move.from_square, uci_move.to_square),
+    };
+    Ok(chess_move)
+}
+
+/// Write a move in UCI's long algebraic notation, e.g. `e7e8q`.
+pub fn to_uci(chess_move: &chess_move::Move) -> String {
+    let mut uci = format!(
+        "{}{}",
+        chess_move.from_square.to_algebraic(),
+        chess_move.to_square.to_algebraic()
+    );
+    if let Some(promote_to) = chess_move.promote_to {
+        uci.push_str(&promote_to.to_string().to_lowercase());
+    };
+    uci
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+    use crate::testing::factories;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::pawn_move("e2e4", None)]
+    #[case::castling("e1g1", None)]
+    #[case::promotion("e7e8q", Some(PieceType::Queen))]
+    #[case::underpromotion("a2a1n", Some(PieceType::Knight))]
+    fn uci_round_trips(#[case] uci: &str, #[case] promote_to: Option<PieceType>) {
+        let uci_move = parse_uci(uci).unwrap();
+
+        assert_eq!(uci_move.promote_to, promote_to);
+
+        let piece = Piece::new(Colour::White, PieceType::Pawn);
+        let chess_move = match uci_move.promote_to {
+            Some(promote_to) => chess_move::Move::new_promotion(
+                piece,
+                uci_move.from_square,
+                uci_move.to_square,
+                promote_to,
+            ),
+            None => chess_move::Move::new(piece, uci_move.from_square, uci_move.to_square),
+        };
+        assert_eq!(to_uci(&chess_move), uci);
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::too_short("e2e")]
+    #[case::too_long("e7e8qq")]
+    #[case::off_board("e2e9")]
+    #[case::uppercase_square("E2E4")]
+    #[case::promotion_to_king("e7e8k")]
+    #[case::unknown_piece("e7e8x")]
+    #[case::not_ascii("e2é4")]
+    fn errors_for_invalid_uci(#[case] uci: &str) {
+        assert_eq!(parse_uci(uci), Err(UciError::InvalidUci(uci.to_string())));
+    }
+
+    #[test]
+    fn reads_move_of_piece_on_from_square() {
+        let chessboard = factories::chessboard();
+
+        let chess_move = from_uci("g1f3", &chessboard).unwrap();
+
+        assert_eq!(
+            chess_move.piece,
+            Piece::new(Colour::White, PieceType::Knight)
+        );
+        assert_eq!(chess_move.from_square, Square::new(Rank::One, File::G));
+        assert_eq!(chess_move.to_square, Square::new(Rank::Three, File::F));
+    }
+
+    #[test]
+    fn errors_when_from_square_is_empty() {
+        let chessboard = factories::chessboard();
+
+        let result = from_uci("e4e5", &chessboard);
+
+        assert!(matches!(
+            result,
+            Err(UciError::NoPieceAtFromSquare(square)) if square == "e4"
+        ));
+    }
+}
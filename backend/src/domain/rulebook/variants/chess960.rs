@@ -0,0 +1,47 @@
+use super::super::starting_position;
+use super::variant;
+use crate::domain::chess_set;
+
+/// Fischer random chess, which starts from one of 960 shuffled back ranks.
+///
+/// The standard move rules already allow castling with the king and rooks on any file,
+/// so only the starting positions differ.
+pub struct Chess960;
+
+impl variant::Variant for Chess960 {
+    fn get_starting_position_count(&self) -> u16 {
+        starting_position::CHESS960_POSITION_COUNT
+    }
+
+    fn get_starting_chessboard(&self, index: u16) -> Option<chess_set::Chessboard> {
+        starting_position::get_chess960_starting_chessboard(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::domain::rulebook::variants::Variant;
+
+    #[test]
+    fn can_start_from_every_chess960_position() {
+        assert_eq!(Chess960.get_starting_position_count(), 960);
+        assert!(Chess960.get_starting_chessboard(959).is_some());
+        assert!(Chess960.get_starting_chessboard(960).is_none());
+    }
+
+    #[test]
+    fn standard_position_is_a_chess960_position() {
+        let chessboard = Chess960.get_starting_chessboard(518).unwrap();
+
+        assert_eq!(
+            chessboard,
+            starting_position::get_official_starting_chessboard()
+        );
+        assert_eq!(
+            Chess960.get_legal_moves(Colour::White, &[chessboard]).len(),
+            20
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use super::super::moves::chess_move;
+use super::super::{check, draws, search_position};
+use super::variant;
+use crate::domain::chess_set;
+use std::cmp;
+
+/// The score for each square a king is closer to the hill than its opponent's king.
+const HILL_DISTANCE_SCORE: i32 = 60;
+
+/// King of the Hill, where a king reaching one of the four centre squares wins.
+///
+/// A lone king can still win by reaching the hill, so games are never drawn for
+/// insufficient material.
+pub struct KingOfTheHill;
+
+impl variant::Variant for KingOfTheHill {
+    fn get_win(
+        &self,
+        to_play_colour: chess_set::Colour,
+        _checks_given: &check::ChecksGiven,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<(chess_set::Colour, variant::Win)> {
+        if let Some(win) = variant::get_checkmate_win(to_play_colour, chessboard_history) {
+            return Some(win);
+        };
+
+        // Only the player who just moved can have reached the hill.
+        let mover = to_play_colour.swap();
+        let chessboard =
+            chess_set::BitboardChessboard::from_chessboard(chessboard_history.last().unwrap());
+        match is_king_on_hill(&chessboard, &mover) {
+            true => Some((mover, variant::Win::KingOfTheHill)),
+            false => None,
+        }
+    }
+
+    fn get_draw(
+        &self,
+        to_play_colour: chess_set::Colour,
+        starting_halfmove_clock: u16,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<draws::Draw> {
+        draws::is_draw_regardless_of_material(
+            to_play_colour,
+            starting_halfmove_clock,
+            chessboard_history,
+        )
+    }
+
+    fn can_still_win(&self, colour: chess_set::Colour, chessboard: &chess_set::Chessboard) -> bool {
+        // Even a lone king can walk to the hill.
+        let _ = (colour, chessboard);
+        true
+    }
+
+    fn get_search_outcome(
+        &self,
+        position: &search_position::SearchPosition,
+        legal_moves: &[chess_move::Move],
+    ) -> Option<search_position::Outcome> {
+        let mover = position.get_to_play_colour().swap();
+        if is_king_on_hill(position.get_chessboard(), &mover) {
+            return Some(search_position::Outcome::Won(mover));
+        };
+        position.get_outcome_regardless_of_material(legal_moves)
+    }
+
+    /// Reward having a king closer to the hill than the opponent's king.
+    fn evaluate_search_position(
+        &self,
+        position: &search_position::SearchPosition,
+        for_colour: &chess_set::Colour,
+    ) -> i32 {
+        let chessboard = position.get_chessboard();
+        let distance = get_king_distance_to_hill(chessboard, for_colour);
+        let opponent_distance = get_king_distance_to_hill(chessboard, &for_colour.swap());
+        HILL_DISTANCE_SCORE * (opponent_distance - distance)
+    }
+}
+
+/// Get the four centre squares: d4, e4, d5 and e5.
+fn get_hill() -> chess_set::Bitboard {
+    chess_set::Bitboard::from_squares(&[
+        chess_set::Square::new(chess_set::Rank::Four, chess_set::File::D),
+        chess_set::Square::new(chess_set::Rank::Four, chess_set::File::E),
+        chess_set::Square::new(chess_set::Rank::Five, chess_set::File::D),
+        chess_set::Square::new(chess_set::Rank::Five, chess_set::File::E),
+    ])
+}
+
+fn is_king_on_hill(chessboard: &chess_set::BitboardChessboard, colour: &chess_set::Colour) -> bool {
+    let king_squares = chessboard.get_squares_of(colour, &chess_set::PieceType::King);
+    !(king_squares & get_hill()).is_empty()
+}
+
+/// Count the king moves it would take a player's king to reach the hill on an empty board.
+fn get_king_distance_to_hill(
+    chessboard: &chess_set::BitboardChessboard,
+    colour: &chess_set::Colour,
+) -> i32 {
+    let king_squares = chessboard.get_squares_of(colour, &chess_set::PieceType::King);
+    let Some(king_square) = king_squares.iter().next() else {
+        return 0;
+    };
+
+    // The hill spans ranks and files 4 and 5.
+    let get_distance = |index: i8| cmp::max(4 - index, cmp::max(index - 5, 0)) as i32;
+    cmp::max(
+        get_distance(king_square.get_rank().index()),
+        get_distance(king_square.get_file().index()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::domain::rulebook::variants::{Variant, Win};
+    use crate::domain::rulebook::Fen;
+    use rstest::rstest;
+
+    fn get_chessboard_history(fen: &str) -> (Colour, Vec<chess_set::Chessboard>) {
+        let fen = Fen::parse(fen).unwrap();
+        (fen.to_play_colour, fen.to_chessboard_history())
+    }
+
+    #[rstest]
+    #[case::d4("8/8/8/8/3K4/8/8/7k b - - 1 1", Some((Colour::White, Win::KingOfTheHill)))]
+    #[case::e5("7K/8/8/4k3/8/8/8/8 w - - 1 1", Some((Colour::Black, Win::KingOfTheHill)))]
+    #[case::next_to_hill("8/8/8/8/8/3K4/8/7k b - - 1 1", None)]
+    fn king_reaching_the_hill_wins(#[case] fen: &str, #[case] expected_win: Option<(Colour, Win)>) {
+        let (to_play_colour, chessboard_history) = get_chessboard_history(fen);
+
+        let win = KingOfTheHill.get_win(
+            to_play_colour,
+            &check::ChecksGiven::default(),
+            &chessboard_history,
+        );
+
+        assert_eq!(win, expected_win);
+    }
+
+    #[test]
+    fn bare_kings_are_not_drawn() {
+        let (to_play_colour, chessboard_history) =
+            get_chessboard_history("8/8/8/8/8/3K4/8/7k b - - 1 1");
+
+        let draw = KingOfTheHill.get_draw(to_play_colour, 0, &chessboard_history);
+
+        assert_eq!(draw, None);
+    }
+
+    #[test]
+    fn lone_king_can_still_win() {
+        let (_, chessboard_history) = get_chessboard_history("8/8/8/8/8/3K4/8/7k b - - 1 1");
+
+        assert!(KingOfTheHill.can_still_win(Colour::White, chessboard_history.last().unwrap()));
+    }
+
+    #[test]
+    fn search_outcome_is_won_when_king_reaches_the_hill() {
+        let (to_play_colour, chessboard_history) =
+            get_chessboard_history("8/8/8/8/3K4/8/8/7k b - - 1 1");
+        let position = search_position::SearchPosition::from_chessboard_history(
+            to_play_colour,
+            0,
+            check::ChecksGiven::default(),
+            &chessboard_history,
+        );
+
+        let outcome = KingOfTheHill.get_search_outcome(&position, &position.get_legal_moves());
+
+        assert_eq!(outcome, Some(search_position::Outcome::Won(Colour::White)));
+    }
+
+    #[test]
+    fn king_closer_to_the_hill_is_evaluated_higher() {
+        let (to_play_colour, chessboard_history) =
+            get_chessboard_history("8/8/8/8/8/3K4/8/7k w - - 0 1");
+        let position = search_position::SearchPosition::from_chessboard_history(
+            to_play_colour,
+            0,
+            check::ChecksGiven::default(),
+            &chessboard_history,
+        );
+
+        assert_eq!(
+            KingOfTheHill.evaluate_search_position(&position, &Colour::White),
+            2 * HILL_DISTANCE_SCORE
+        );
+        assert_eq!(
+            KingOfTheHill.evaluate_search_position(&position, &Colour::Black),
+            -2 * HILL_DISTANCE_SCORE
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod chess960;
+mod king_of_the_hill;
+mod standard;
+mod three_check;
+mod variant;
+
+pub use chess960::Chess960;
+pub use king_of_the_hill::KingOfTheHill;
+pub use standard::Standard;
+pub use three_check::ThreeCheck;
+pub use variant::{Variant, Win};
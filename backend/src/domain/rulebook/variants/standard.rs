@@ -0,0 +1,6 @@
+use super::variant;
+
+/// Standard chess, as played under the FIDE laws.
+pub struct Standard;
+
+impl variant::Variant for Standard {}
@@ -0,0 +1,184 @@
+use super::super::moves::chess_move;
+use super::super::{check, draws, search_position};
+use super::variant;
+use crate::domain::chess_set;
+
+/// The number of checks a player must give to win.
+const CHECKS_TO_WIN: u8 = 3;
+
+/// The score for each check given, since every check brings a player closer to winning.
+const CHECK_SCORE: i32 = 250;
+
+/// Three-check, where the player who gives the third check wins.
+///
+/// A player with any piece besides their king can still give checks, so games are only
+/// drawn for insufficient material when both players have a bare king.
+pub struct ThreeCheck;
+
+impl variant::Variant for ThreeCheck {
+    fn get_win(
+        &self,
+        to_play_colour: chess_set::Colour,
+        checks_given: &check::ChecksGiven,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<(chess_set::Colour, variant::Win)> {
+        if let Some(win) = variant::get_checkmate_win(to_play_colour, chessboard_history) {
+            return Some(win);
+        };
+
+        // Only the player who just moved can have given another check.
+        let mover = to_play_colour.swap();
+        match checks_given.get(&mover) >= CHECKS_TO_WIN {
+            true => Some((mover, variant::Win::ThreeChecks)),
+            false => None,
+        }
+    }
+
+    fn get_draw(
+        &self,
+        to_play_colour: chess_set::Colour,
+        starting_halfmove_clock: u16,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<draws::Draw> {
+        let chessboard = chessboard_history.last().unwrap();
+        let has_bare_kings = chessboard.position.values().flatten().count() == 2;
+        match draws::is_draw(to_play_colour, starting_halfmove_clock, chessboard_history) {
+            Some(draws::Draw::InsufficientMaterial) if !has_bare_kings => {
+                draws::is_draw_regardless_of_material(
+                    to_play_colour,
+                    starting_halfmove_clock,
+                    chessboard_history,
+                )
+            }
+            draw => draw,
+        }
+    }
+
+    fn can_still_win(&self, colour: chess_set::Colour, chessboard: &chess_set::Chessboard) -> bool {
+        // Any piece besides the king can give checks.
+        let n_pieces = chessboard
+            .position
+            .values()
+            .flatten()
+            .filter(|piece| piece.get_colour() == &colour)
+            .count();
+        n_pieces > 1
+    }
+
+    fn get_search_outcome(
+        &self,
+        position: &search_position::SearchPosition,
+        legal_moves: &[chess_move::Move],
+    ) -> Option<search_position::Outcome> {
+        let mover = position.get_to_play_colour().swap();
+        if position.get_checks_given(&mover) >= CHECKS_TO_WIN {
+            return Some(search_position::Outcome::Won(mover));
+        };
+        // Search positions are only drawn for insufficient material with bare kings.
+        position.get_outcome(legal_moves)
+    }
+
+    /// Reward having given more checks than the opponent.
+    fn evaluate_search_position(
+        &self,
+        position: &search_position::SearchPosition,
+        for_colour: &chess_set::Colour,
+    ) -> i32 {
+        let checks_given = position.get_checks_given(for_colour) as i32;
+        let opponent_checks_given = position.get_checks_given(&for_colour.swap()) as i32;
+        CHECK_SCORE * (checks_given - opponent_checks_given)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::domain::rulebook::variants::{Variant, Win};
+    use crate::domain::rulebook::{from_uci, get_official_starting_chessboard};
+
+    /// Play moves from the official starting position.
+    fn play_moves(ucis: &[&str]) -> Vec<chess_set::Chessboard> {
+        let mut chessboard_history = vec![get_official_starting_chessboard()];
+        for uci in ucis {
+            let chess_move = from_uci(uci, chessboard_history.last().unwrap()).unwrap();
+            let chessboard = chess_move.apply_if_valid(&chessboard_history).unwrap();
+            chessboard_history.push(chessboard);
+        }
+        chessboard_history
+    }
+
+    // 1. e4 d5 2. Bb5+ c6 3. Bxc6+ Nxc6 4. Qh5, threatening Qxf7+.
+    const TWO_CHECKS: [&str; 7] = ["e2e4", "d7d5", "f1b5", "c7c6", "b5c6", "b8c6", "d1h5"];
+
+    #[test]
+    fn third_check_wins() {
+        let mut ucis = TWO_CHECKS.to_vec();
+        ucis.extend(["a7a6", "h5f7"]);
+        let chessboard_history = play_moves(&ucis);
+        let checks_given = check::ChecksGiven::new(3, 0);
+
+        let win = ThreeCheck.get_win(Colour::Black, &checks_given, &chessboard_history);
+
+        assert_eq!(win, Some((Colour::White, Win::ThreeChecks)));
+    }
+
+    #[test]
+    fn second_check_does_not_win() {
+        let chessboard_history = play_moves(&TWO_CHECKS);
+        let checks_given = check::ChecksGiven::new(2, 0);
+
+        let win = ThreeCheck.get_win(Colour::Black, &checks_given, &chessboard_history);
+
+        assert_eq!(win, None);
+    }
+
+    #[test]
+    fn king_and_knight_against_king_is_not_drawn() {
+        let fen = crate::domain::rulebook::Fen::parse("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let draw = ThreeCheck.get_draw(Colour::White, 0, &chessboard_history);
+
+        assert_eq!(draw, None);
+    }
+
+    #[test]
+    fn king_and_knight_can_still_win() {
+        let fen = crate::domain::rulebook::Fen::parse("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let chessboard = chessboard_history.last().unwrap();
+        assert!(ThreeCheck.can_still_win(Colour::White, chessboard));
+        assert!(!ThreeCheck.can_still_win(Colour::Black, chessboard));
+    }
+
+    #[test]
+    fn search_outcome_is_won_by_third_check() {
+        let chessboard_history = play_moves(&TWO_CHECKS);
+        let mut position = search_position::SearchPosition::from_chessboard_history(
+            Colour::Black,
+            0,
+            check::ChecksGiven::new(2, 0),
+            &chessboard_history,
+        );
+        let a7a6 = from_uci("a7a6", chessboard_history.last().unwrap()).unwrap();
+        position.make_move(&a7a6);
+        let queen_takes_f7 = position
+            .get_legal_moves()
+            .into_iter()
+            .find(|chess_move| {
+                chess_move.to_square == chess_set::Square::from_algebraic("f7").unwrap()
+            })
+            .unwrap();
+        position.make_move(&queen_takes_f7);
+
+        let outcome = ThreeCheck.get_search_outcome(&position, &position.get_legal_moves());
+
+        assert_eq!(outcome, Some(search_position::Outcome::Won(Colour::White)));
+        assert_eq!(
+            ThreeCheck.evaluate_search_position(&position, &Colour::Black),
+            -3 * CHECK_SCORE
+        );
+    }
+}
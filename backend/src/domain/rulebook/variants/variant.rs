@@ -0,0 +1,214 @@
+use super::super::moves::chess_move;
+use super::super::{check, checkmate, draws, search_position, starting_position};
+use crate::domain::chess_set;
+
+/// How a game can be won over the board, i.e. other than by resignation or on time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Win {
+    Checkmate,
+    /// The winner's king reached one of the four centre squares.
+    KingOfTheHill,
+    /// The winner gave their third check.
+    ThreeChecks,
+}
+
+/// The rules of a chess variant: where games start, how pieces move, and how games end.
+///
+/// Every rule defaults to standard chess, so a variant only overrides the rules it changes.
+/// Engines search through `SearchPosition`s rather than chessboard histories, so variants
+/// that change how moves are generated or how games end must override the search rules too.
+pub trait Variant {
+    // Starting positions.
+
+    /// The number of positions a game of the variant can start from.
+    fn get_starting_position_count(&self) -> u16 {
+        1
+    }
+
+    /// Get the chessboard a game starts from, given the starting position's number.
+    fn get_starting_chessboard(&self, index: u16) -> Option<chess_set::Chessboard> {
+        match index {
+            0 => Some(starting_position::get_official_starting_chessboard()),
+            _ => None,
+        }
+    }
+
+    // Move rules.
+
+    /// Get the legal moves that can be played on the latest chessboard in a chessboard history.
+    fn get_legal_moves(
+        &self,
+        player: chess_set::Colour,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Vec<chess_move::Move> {
+        checkmate::get_legal_moves(player, chessboard_history)
+    }
+
+    /// Test whether a move the standard move rules allow is legal in the variant.
+    ///
+    /// Variants that override `get_legal_moves` to rule out moves must override this too,
+    /// so that single moves can be validated without generating every legal move.
+    fn allows_move(
+        &self,
+        chess_move: &chess_move::Move,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> bool {
+        let _ = (chess_move, chessboard_history);
+        true
+    }
+
+    // Win and draw conditions.
+
+    /// Get the winner of the game, and how they won, if the game has been won.
+    ///
+    /// The checks given are those given by the end of the history, which only variants
+    /// such as Three-check are won by.
+    fn get_win(
+        &self,
+        to_play_colour: chess_set::Colour,
+        checks_given: &check::ChecksGiven,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<(chess_set::Colour, Win)> {
+        let _ = checks_given;
+        get_checkmate_win(to_play_colour, chessboard_history)
+    }
+
+    /// Test whether the game is automatically drawn.
+    ///
+    /// The starting halfmove clock is the halfmove clock of the earliest chessboard in the
+    /// history, which counts towards the fifty- and seventy-five-move rules.
+    fn get_draw(
+        &self,
+        to_play_colour: chess_set::Colour,
+        starting_halfmove_clock: u16,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<draws::Draw> {
+        draws::is_draw(to_play_colour, starting_halfmove_clock, chessboard_history)
+    }
+
+    /// Test whether the player to move can claim a draw.
+    fn get_claimable_draw(
+        &self,
+        to_play_colour: chess_set::Colour,
+        starting_halfmove_clock: u16,
+        chessboard_history: &[chess_set::Chessboard],
+    ) -> Option<draws::Draw> {
+        draws::get_claimable_draw(to_play_colour, starting_halfmove_clock, chessboard_history)
+    }
+
+    /// Test whether a player could still win by any sequence of legal moves.
+    ///
+    /// A player who runs out of time loses only if their opponent could still win,
+    /// and draws otherwise.
+    fn can_still_win(&self, colour: chess_set::Colour, chessboard: &chess_set::Chessboard) -> bool {
+        draws::has_sufficient_mating_material(colour, chessboard)
+    }
+
+    // Search rules.
+
+    /// Get the legal moves in a position being searched.
+    fn get_search_legal_moves(
+        &self,
+        position: &search_position::SearchPosition,
+    ) -> Vec<chess_move::Move> {
+        position.get_legal_moves()
+    }
+
+    /// Get the outcome of the game at a position being searched, if the game has ended.
+    fn get_search_outcome(
+        &self,
+        position: &search_position::SearchPosition,
+        legal_moves: &[chess_move::Move],
+    ) -> Option<search_position::Outcome> {
+        position.get_outcome(legal_moves)
+    }
+
+    /// Score how close a player is to winning by the variant's own win conditions.
+    ///
+    /// Engine evaluators only understand standard chess, so this is added to their
+    /// evaluation of the position, to steer the search towards the variant's goals.
+    fn evaluate_search_position(
+        &self,
+        position: &search_position::SearchPosition,
+        for_colour: &chess_set::Colour,
+    ) -> i32 {
+        let _ = (position, for_colour);
+        0
+    }
+}
+
+/// Get the winner of the game if the player to move is checkmated.
+pub(super) fn get_checkmate_win(
+    to_play_colour: chess_set::Colour,
+    chessboard_history: &[chess_set::Chessboard],
+) -> Option<(chess_set::Colour, Win)> {
+    match checkmate::is_player_checkmated(to_play_colour, chessboard_history) {
+        true => Some((to_play_colour.swap(), Win::Checkmate)),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Standard;
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::domain::rulebook::Fen;
+
+    #[test]
+    fn standard_chess_starts_from_the_official_starting_position() {
+        assert_eq!(Standard.get_starting_position_count(), 1);
+        assert_eq!(
+            Standard.get_starting_chessboard(0),
+            Some(starting_position::get_official_starting_chessboard())
+        );
+        assert_eq!(Standard.get_starting_chessboard(1), None);
+    }
+
+    #[test]
+    fn standard_chess_allows_every_move_the_move_rules_allow() {
+        let chessboard_history = vec![starting_position::get_official_starting_chessboard()];
+
+        for chess_move in Standard.get_legal_moves(Colour::White, &chessboard_history) {
+            assert!(Standard.allows_move(&chess_move, &chessboard_history));
+        }
+    }
+
+    #[test]
+    fn standard_chess_is_won_by_checkmate() {
+        let fen = Fen::parse("R5k1/5ppp/8/8/8/8/8/4K3 b - - 1 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+
+        let win = Standard.get_win(
+            Colour::Black,
+            &check::ChecksGiven::default(),
+            &chessboard_history,
+        );
+
+        assert_eq!(win, Some((Colour::White, Win::Checkmate)));
+    }
+
+    #[test]
+    fn standard_chess_search_agrees_with_chessboard_history() {
+        let fen = Fen::parse("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        let chessboard_history = fen.to_chessboard_history();
+        let position = search_position::SearchPosition::from_chessboard_history(
+            Colour::Black,
+            0,
+            check::ChecksGiven::default(),
+            &chessboard_history,
+        );
+
+        let legal_moves = Standard.get_search_legal_moves(&position);
+
+        assert!(legal_moves.is_empty());
+        assert_eq!(
+            Standard.get_search_outcome(&position, &legal_moves),
+            Some(search_position::Outcome::Drawn(draws::Draw::Stalemate))
+        );
+        assert_eq!(
+            Standard.get_draw(Colour::Black, 0, &chessboard_history),
+            Some(draws::Draw::Stalemate)
+        );
+    }
+}
@@ -18,9 +18,23 @@ pub fn rocket_build() -> rocket::Rocket<rocket::Build> {
             "/api",
             rocket::routes![
                 routes::start_game,
+                routes::import_games,
                 routes::get_game_state,
+                routes::get_fen,
+                routes::get_pgn,
+                routes::get_attacks,
                 routes::play_move,
                 routes::generate_and_play_next_move,
+                routes::claim_draw,
+                routes::resign,
+                routes::offer_draw,
+                routes::accept_draw,
+                routes::decline_draw,
+                routes::generate_draw_offer_response,
+                routes::request_takeback,
+                routes::accept_takeback,
+                routes::decline_takeback,
+                routes::generate_takeback_response,
                 routes::get_legal_moves,
             ],
         )
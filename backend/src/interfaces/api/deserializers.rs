@@ -1,29 +1,124 @@
 use crate::config;
-use crate::domain::chess_set;
+use crate::domain::{chess_set, clock, game};
 use serde;
+use thiserror;
 
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum DeserializationError {
+    #[error("'{0}' is not a valid player; it should be White or Black.")]
+    InvalidPlayer(String),
+}
+
+#[derive(serde::Deserialize)]
+pub struct StartGame {
+    fen: Option<String>,
+    time_control: Option<clock::TimeControl>,
+    variant: Option<game::GameVariant>,
+    /// The index of the Chess960 starting position, picked at random if not given.
+    chess960_position: Option<u16>,
+}
+
+impl StartGame {
+    pub fn get_fen(&self) -> Option<&str> {
+        self.fen.as_deref()
+    }
+
+    pub fn get_time_control(&self) -> Option<clock::TimeControl> {
+        self.time_control
+    }
+
+    pub fn get_variant(&self) -> game::GameVariant {
+        self.variant.unwrap_or(game::GameVariant::Standard)
+    }
+
+    pub fn get_chess960_position(&self) -> Option<u16> {
+        self.chess960_position
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct ImportGames {
+    pub pgn: String,
+}
+
+/// A move, given either by its squares, in standard algebraic notation or in UCI notation.
 #[derive(serde::Deserialize)]
 pub struct Move<'request> {
     player: &'request str,
-    from_square: &'request str,
-    to_square: &'request str,
+    from_square: Option<&'request str>,
+    to_square: Option<&'request str>,
+    promote_to: Option<chess_set::PieceType>,
+    san: Option<&'request str>,
+    uci: Option<&'request str>,
 }
 
 impl<'request> Move<'request> {
-    pub fn get_player(&'request self) -> chess_set::Colour {
-        match self.player {
-            "White" => chess_set::Colour::White,
-            "Black" => chess_set::Colour::Black,
-            _ => panic!("Invalid colour!"),
-        }
+    pub fn get_player(&'request self) -> Result<chess_set::Colour, DeserializationError> {
+        deserialize_to_colour(self.player)
+    }
+
+    pub fn get_from_square(&'request self) -> Option<chess_set::Square> {
+        self.from_square.and_then(deserialize_to_square)
+    }
+
+    pub fn get_to_square(&'request self) -> Option<chess_set::Square> {
+        self.to_square.and_then(deserialize_to_square)
+    }
+
+    pub fn get_san(&'request self) -> Option<&'request str> {
+        self.san
     }
 
-    pub fn get_from_square(&'request self) -> chess_set::Square {
-        deserialize_to_square(self.from_square)
+    pub fn get_uci(&'request self) -> Option<&'request str> {
+        self.uci
     }
 
-    pub fn get_to_square(&'request self) -> chess_set::Square {
-        deserialize_to_square(self.to_square)
+    pub fn get_promote_to(&'request self) -> Option<chess_set::PieceType> {
+        self.promote_to
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClaimDraw<'request> {
+    player: &'request str,
+}
+
+impl<'request> ClaimDraw<'request> {
+    pub fn get_player(&'request self) -> Result<chess_set::Colour, DeserializationError> {
+        deserialize_to_colour(self.player)
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct Resign<'request> {
+    player: &'request str,
+}
+
+impl<'request> Resign<'request> {
+    pub fn get_player(&'request self) -> Result<chess_set::Colour, DeserializationError> {
+        deserialize_to_colour(self.player)
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct DrawOffer<'request> {
+    player: &'request str,
+}
+
+impl<'request> DrawOffer<'request> {
+    pub fn get_player(&'request self) -> Result<chess_set::Colour, DeserializationError> {
+        deserialize_to_colour(self.player)
+    }
+}
+
+#[derive(serde::Deserialize)]
+pub struct Takeback<'request> {
+    player: &'request str,
+}
+
+impl<'request> Takeback<'request> {
+    pub fn get_player(&'request self) -> Result<chess_set::Colour, DeserializationError> {
+        deserialize_to_colour(self.player)
     }
 }
 
@@ -32,44 +127,180 @@ pub struct GenerateMove {
     pub engine: config::ChessEngineImplementation,
 }
 
+fn deserialize_to_colour(value: &str) -> Result<chess_set::Colour, DeserializationError> {
+    match value {
+        "White" => Ok(chess_set::Colour::White),
+        "Black" => Ok(chess_set::Colour::Black),
+        _ => Err(DeserializationError::InvalidPlayer(value.to_string())),
+    }
+}
+
 /// Convert `A1` to the square in file A, and rank 1.
-fn deserialize_to_square(value: &str) -> chess_set::Square {
-    let chars: Vec<char> = value.chars().collect();
-    let file = chess_set::File::from_letter(chars[0]);
-    let rank = chess_set::Rank::from_index(chars[1].to_digit(10).unwrap() as i8);
-    chess_set::Square::new(rank, file)
+fn deserialize_to_square(value: &str) -> Option<chess_set::Square> {
+    chess_set::Square::from_algebraic(&value.to_lowercase())
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
-    use crate::domain::chess_set::{Colour, File, Rank, Square};
+    use crate::domain::chess_set::{Colour, File, PieceType, Rank, Square};
 
     #[test]
     fn can_get_domain_objects_from_move() {
         let move_ = Move {
             player: "White",
-            from_square: "A2",
-            to_square: "A4",
+            from_square: Some("A2"),
+            to_square: Some("A4"),
+            promote_to: None,
+            san: None,
+            uci: None,
         };
 
-        assert_eq!(move_.get_player(), Colour::White);
+        assert_eq!(move_.get_player(), Ok(Colour::White));
 
         let expected_from_square = Square::new(Rank::Two, File::A);
-        assert_eq!(move_.get_from_square(), expected_from_square);
+        assert_eq!(move_.get_from_square(), Some(expected_from_square));
 
         let expected_to_square = Square::new(Rank::Four, File::A);
-        assert_eq!(move_.get_to_square(), expected_to_square);
+        assert_eq!(move_.get_to_square(), Some(expected_to_square));
+
+        assert_eq!(move_.get_promote_to(), None);
+    }
+
+    #[test]
+    fn can_deserialize_move_with_promotion() {
+        let payload =
+            r#"{"player": "Black", "from_square": "B2", "to_square": "B1", "promote_to": "Rook"}"#;
+
+        let move_: Move = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(move_.get_player(), Ok(Colour::Black));
+        assert_eq!(move_.get_promote_to(), Some(PieceType::Rook));
+    }
+
+    #[test]
+    fn can_deserialize_move_in_san() {
+        let payload = r#"{"player": "White", "san": "Nf3"}"#;
+
+        let move_: Move = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(move_.get_san(), Some("Nf3"));
+        assert_eq!(move_.get_from_square(), None);
+        assert_eq!(move_.get_to_square(), None);
+    }
+
+    #[test]
+    fn can_deserialize_start_game_with_fen() {
+        let payload = r#"{"fen": "4k3/8/8/8/8/8/8/4K3 w - - 0 1"}"#;
+
+        let start_game: StartGame = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(start_game.get_fen(), Some("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+    }
+
+    #[test]
+    fn can_deserialize_start_game_without_fen() {
+        let start_game: StartGame = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(start_game.get_fen(), None);
+        assert_eq!(start_game.get_variant(), game::GameVariant::Standard);
+    }
+
+    #[test]
+    fn can_deserialize_start_game_with_variant() {
+        let payload = r#"{"variant": "Chess960", "chess960_position": 518}"#;
+
+        let start_game: StartGame = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(start_game.get_variant(), game::GameVariant::Chess960);
+        assert_eq!(start_game.get_chess960_position(), Some(518));
+    }
+
+    #[test]
+    fn can_deserialize_start_game_with_time_control() {
+        let payload = r#"{"time_control": {"Bronstein": {"base_ms": 300000, "delay_ms": 3000}}}"#;
+
+        let start_game: StartGame = serde_json::from_str(payload).unwrap();
+
+        let expected_time_control = clock::TimeControl::Bronstein {
+            base_ms: 300_000,
+            delay_ms: 3_000,
+        };
+        assert_eq!(start_game.get_time_control(), Some(expected_time_control));
+    }
+
+    #[test]
+    fn can_deserialize_claim_draw() {
+        let payload = r#"{"player": "Black"}"#;
+
+        let claim_draw: ClaimDraw = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(claim_draw.get_player(), Ok(Colour::Black));
+    }
+
+    #[test]
+    fn can_deserialize_resign() {
+        let payload = r#"{"player": "White"}"#;
+
+        let resign: Resign = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(resign.get_player(), Ok(Colour::White));
+    }
+
+    #[test]
+    fn can_deserialize_takeback() {
+        let payload = r#"{"player": "White"}"#;
+
+        let takeback: Takeback = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(takeback.get_player(), Ok(Colour::White));
+    }
+
+    #[test]
+    fn can_deserialize_draw_offer() {
+        let payload = r#"{"player": "Black"}"#;
+
+        let draw_offer: DrawOffer = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(draw_offer.get_player(), Ok(Colour::Black));
+    }
+
+    #[test]
+    fn cannot_deserialize_invalid_player() {
+        let payload = r#"{"player": "Green"}"#;
+
+        let resign: Resign = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(
+            resign.get_player(),
+            Err(DeserializationError::InvalidPlayer("Green".to_string()))
+        );
     }
 
     #[test]
     fn can_deserialize_str_to_square() {
         let string = "C7";
 
-        let square = deserialize_to_square(string);
+        let square = deserialize_to_square(string).unwrap();
 
         assert_eq!(square.get_rank(), &Rank::Seven);
         assert_eq!(square.get_file(), &File::C);
     }
+
+    #[test]
+    fn cannot_deserialize_invalid_str_to_square() {
+        assert_eq!(deserialize_to_square("C9"), None);
+        assert_eq!(deserialize_to_square("C"), None);
+    }
+
+    #[test]
+    fn can_deserialize_move_in_uci() {
+        let payload = r#"{"player": "Black", "uci": "e7e5"}"#;
+
+        let move_: Move = serde_json::from_str(payload).unwrap();
+
+        assert_eq!(move_.get_uci(), Some("e7e5"));
+        assert_eq!(move_.get_san(), None);
+    }
 }
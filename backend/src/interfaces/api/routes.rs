@@ -3,23 +3,78 @@ use rocket::http;
 use rocket::serde::json;
 
 use crate::config;
-use crate::domain::rulebook;
+use crate::domain::{chess_set, game, pgn, rulebook};
 use crate::services::games;
 
-use super::deserializers;
+use super::{deserializers, serializers};
 
-#[rocket::post("/games/start")]
-pub async fn start_game() -> (http::Status, json::Json<String>) {
+#[rocket::post("/games/start", data = "<start_game>")]
+pub async fn start_game(
+    start_game: Option<json::Json<deserializers::StartGame>>,
+) -> (http::Status, json::Json<String>) {
+    let repo = config::get_game_repo();
+    let (fen, time_control, variant, chess960_position) = match &start_game {
+        Some(start_game) => (
+            start_game.get_fen(),
+            start_game.get_time_control(),
+            start_game.get_variant(),
+            start_game.get_chess960_position(),
+        ),
+        None => (None, None, game::GameVariant::Standard, None),
+    };
+
+    match games::start_game(repo, fen, time_control, variant, chess960_position) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Created, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/import", data = "<import_games>")]
+pub async fn import_games(
+    import_games: json::Json<deserializers::ImportGames>,
+) -> (http::Status, json::Json<String>) {
     let repo = config::get_game_repo();
-    let game = games::start_game(repo);
-    let payload = serde_json::to_string(&game).unwrap();
-    (http::Status::Created, json::Json(payload))
+
+    let mut has_imported_game = false;
+    let mut results = vec![];
+    for result in games::import_games(repo, &import_games.pgn) {
+        match result {
+            Ok(game) => {
+                has_imported_game = true;
+                results.push(json::json!({"game": game}));
+            }
+            Err(err) => {
+                let mut payload = json::json!({"error": format!("{}", err)});
+                if let games::ImportGameError::IllegalMove { ply, san } = err {
+                    payload["ply"] = json::json!(ply);
+                    payload["san"] = json::json!(san);
+                };
+                results.push(payload);
+            }
+        }
+    }
+
+    let status = match has_imported_game {
+        true => http::Status::Created,
+        false => http::Status::BadRequest,
+    };
+    let payload = json::json!({"games": results});
+    (status, json::Json(json::to_string(&payload).unwrap()))
 }
 
 #[rocket::get("/games/<id>")]
 pub async fn get_game_state(id: i32) -> (http::Status, json::Json<String>) {
-    let mut repo = config::get_game_repo();
-    match repo.get(&id) {
+    let repo = config::get_game_repo();
+    match games::get_game(repo, &id) {
         Some(game) => {
             let payload = serde_json::to_string(&game).unwrap();
             (http::Status::Ok, json::Json(payload))
@@ -28,20 +83,93 @@ pub async fn get_game_state(id: i32) -> (http::Status, json::Json<String>) {
     }
 }
 
+#[rocket::get("/games/<id>/fen")]
+pub async fn get_fen(id: i32) -> (http::Status, json::Json<String>) {
+    let repo = config::get_game_repo();
+    match games::get_game(repo, &id) {
+        Some(game) => {
+            let payload = json::json!({"fen": game.get_fen().to_string()});
+            (
+                http::Status::Ok,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+        None => (http::Status::NotFound, json::Json("".into())),
+    }
+}
+
+#[rocket::get("/games/<id>/pgn")]
+pub async fn get_pgn(id: i32) -> (http::Status, json::Json<String>) {
+    let repo = config::get_game_repo();
+    match games::get_game(repo, &id) {
+        Some(game) => {
+            let payload = json::json!({"pgn": pgn::Pgn::from_game(&game).to_string()});
+            (
+                http::Status::Ok,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+        None => (http::Status::NotFound, json::Json("".into())),
+    }
+}
+
+#[rocket::get("/games/<id>/attacks")]
+pub async fn get_attacks(id: i32) -> (http::Status, json::Json<String>) {
+    let repo = config::get_game_repo();
+    match games::get_game(repo, &id) {
+        Some(game) => {
+            let chessboard = game.current_chessboard();
+            let payload = json::json!({
+                "White": rulebook::get_attack_counts(chess_set::Colour::White, chessboard),
+                "Black": rulebook::get_attack_counts(chess_set::Colour::Black, chessboard),
+            });
+            (
+                http::Status::Ok,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+        None => (http::Status::NotFound, json::Json("".into())),
+    }
+}
+
 #[rocket::post("/games/<id>/play-move", data = "<play_move>")]
 pub async fn play_move(
     id: i32,
     play_move: json::Json<deserializers::Move<'_>>,
 ) -> (http::Status, json::Json<String>) {
+    let player = match play_move.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
     let repo = config::get_game_repo();
 
-    match games::play_move(
-        repo,
-        &id,
-        &play_move.get_player(),
-        &play_move.get_from_square(),
-        &play_move.get_to_square(),
+    let result = match (
+        play_move.get_san(),
+        play_move.get_uci(),
+        play_move.get_from_square(),
+        play_move.get_to_square(),
     ) {
+        (Some(san), _, _, _) => games::play_san_move(repo, &id, &player, san),
+        (None, Some(uci), _, _) => games::play_uci_move(repo, &id, &player, uci),
+        (None, None, Some(from_square), Some(to_square)) => games::play_move(
+            repo,
+            &id,
+            &player,
+            &from_square,
+            &to_square,
+            play_move.get_promote_to(),
+        ),
+        _ => {
+            let payload =
+                json::json!({"error": "A move must be given in SAN, in UCI or by its squares."});
+            return (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            );
+        }
+    };
+
+    match result {
         Ok(game) => {
             let payload = serde_json::to_string(&game).unwrap();
             (http::Status::Ok, json::Json(payload))
@@ -79,20 +207,300 @@ pub async fn generate_and_play_next_move(
     }
 }
 
+#[rocket::post("/games/<id>/claim-draw", data = "<claim_draw>")]
+pub async fn claim_draw(
+    id: i32,
+    claim_draw: json::Json<deserializers::ClaimDraw<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match claim_draw.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::claim_draw(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/resign", data = "<resign>")]
+pub async fn resign(
+    id: i32,
+    resign: json::Json<deserializers::Resign<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match resign.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::resign(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/offer-draw", data = "<draw_offer>")]
+pub async fn offer_draw(
+    id: i32,
+    draw_offer: json::Json<deserializers::DrawOffer<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match draw_offer.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::offer_draw(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/accept-draw", data = "<draw_offer>")]
+pub async fn accept_draw(
+    id: i32,
+    draw_offer: json::Json<deserializers::DrawOffer<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match draw_offer.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::accept_draw(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/decline-draw", data = "<draw_offer>")]
+pub async fn decline_draw(
+    id: i32,
+    draw_offer: json::Json<deserializers::DrawOffer<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match draw_offer.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::decline_draw(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post(
+    "/games/<id>/generate-draw-offer-response",
+    data = "<generate_response>"
+)]
+pub async fn generate_draw_offer_response(
+    id: i32,
+    generate_response: json::Json<deserializers::GenerateMove>,
+) -> (http::Status, json::Json<String>) {
+    let repo = config::get_game_repo();
+    let engine = config::get_chess_engine(&generate_response.engine);
+
+    match games::respond_to_draw_offer(repo, engine, &id) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/request-takeback", data = "<takeback>")]
+pub async fn request_takeback(
+    id: i32,
+    takeback: json::Json<deserializers::Takeback<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match takeback.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::request_takeback(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/accept-takeback", data = "<takeback>")]
+pub async fn accept_takeback(
+    id: i32,
+    takeback: json::Json<deserializers::Takeback<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match takeback.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::accept_takeback(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/decline-takeback", data = "<takeback>")]
+pub async fn decline_takeback(
+    id: i32,
+    takeback: json::Json<deserializers::Takeback<'_>>,
+) -> (http::Status, json::Json<String>) {
+    let player = match takeback.get_player() {
+        Ok(player) => player,
+        Err(err) => return get_bad_request_response(err),
+    };
+    let repo = config::get_game_repo();
+
+    match games::decline_takeback(repo, &id, &player) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
+#[rocket::post("/games/<id>/generate-takeback-response", data = "<generate_response>")]
+pub async fn generate_takeback_response(
+    id: i32,
+    generate_response: json::Json<deserializers::GenerateMove>,
+) -> (http::Status, json::Json<String>) {
+    let repo = config::get_game_repo();
+    let engine = config::get_chess_engine(&generate_response.engine);
+
+    match games::respond_to_takeback(repo, engine, &id) {
+        Ok(game) => {
+            let payload = serde_json::to_string(&game).unwrap();
+            (http::Status::Ok, json::Json(payload))
+        }
+        Err(err) => {
+            let payload = json::json!({"error": format!("{}", err)});
+            (
+                http::Status::BadRequest,
+                json::Json(json::to_string(&payload).unwrap()),
+            )
+        }
+    }
+}
+
 #[rocket::get("/games/<id>/get-legal-moves")]
 pub async fn get_legal_moves(id: i32) -> (http::Status, json::Json<String>) {
-    let mut repo = config::get_game_repo();
-    let game = match repo.get(&id) {
+    let repo = config::get_game_repo();
+    let game = match games::get_game(repo, &id) {
         Some(game) => game,
         None => return (http::Status::NotFound, json::Json("".into())),
     };
     match game.get_status().to_play_colour() {
-        Some(to_play_colour) => {
-            let legal_moves =
-                rulebook::get_legal_moves(to_play_colour, game.get_chessboard_history());
+        Some(_) => {
+            let chessboard_history = game.get_chessboard_history();
+            let legal_moves: Vec<serializers::LegalMove> = game
+                .get_legal_moves_in_san()
+                .into_iter()
+                .map(|(chess_move, san)| {
+                    let classification = rulebook::classify_move(&chess_move, chessboard_history)
+                        .expect("Legal moves should be valid.");
+                    serializers::LegalMove {
+                        chess_move,
+                        san,
+                        classification,
+                    }
+                })
+                .collect();
             let payload = serde_json::to_string(&legal_moves).unwrap();
             return (http::Status::Ok, json::Json(payload));
         }
         None => return (http::Status::BadRequest, json::Json("".into())),
     }
 }
+
+/// Respond that the request was invalid, e.g. because it named a player that doesn't exist.
+fn get_bad_request_response(
+    err: deserializers::DeserializationError,
+) -> (http::Status, json::Json<String>) {
+    let payload = json::json!({"error": format!("{}", err)});
+    (
+        http::Status::BadRequest,
+        json::Json(json::to_string(&payload).unwrap()),
+    )
+}
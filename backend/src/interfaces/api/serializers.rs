@@ -1,4 +1,4 @@
-use crate::domain::{chess_set, game, rulebook};
+use crate::domain::{chess_set, clock, game, rulebook};
 use serde;
 use serde::ser::SerializeStruct;
 
@@ -56,7 +56,46 @@ impl serde::Serialize for game::Game {
         let mut state = serializer.serialize_struct("game::Game", 1)?;
         state.serialize_field("id", &self.get_id())?;
         state.serialize_field("status", &self.get_status())?;
+        state.serialize_field("termination", &self.get_termination())?;
+        state.serialize_field("draw_offer", &self.get_draw_offer())?;
+        state.serialize_field("takeback_request", &self.get_takeback_request())?;
+        state.serialize_field("clock", &self.get_clock())?;
+        state.serialize_field("variant", &self.get_variant())?;
+        state.serialize_field(
+            "white_checks_given",
+            &self.get_checks_given(&chess_set::Colour::White),
+        )?;
+        state.serialize_field(
+            "black_checks_given",
+            &self.get_checks_given(&chess_set::Colour::Black),
+        )?;
         state.serialize_field("chessboard", &self.current_chessboard())?;
+        // Serialized as hex, since JSON numbers can't safely represent every 64-bit integer.
+        let zobrist_hash = format!("{:016x}", self.get_zobrist_hash());
+        state.serialize_field("zobrist_hash", &zobrist_hash)?;
+        state.serialize_field("move_classifications", &self.get_move_classifications())?;
+        state.serialize_field("moves", &self.get_moves_in_san())?;
+        state.end()
+    }
+}
+
+impl serde::Serialize for clock::Clock {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("clock::Clock", 1)?;
+        state.serialize_field("time_control", self.get_time_control())?;
+        state.serialize_field(
+            "white_remaining_ms",
+            &self.get_remaining_ms(chess_set::Colour::White),
+        )?;
+        state.serialize_field(
+            "black_remaining_ms",
+            &self.get_remaining_ms(chess_set::Colour::Black),
+        )?;
+        state.serialize_field("turn_started_at_ms", &self.get_turn_started_at_ms())?;
+        state.serialize_field("move_remaining_ms", self.get_move_remaining_ms())?;
         state.end()
     }
 }
@@ -70,6 +109,34 @@ impl serde::Serialize for rulebook::Move {
         state.serialize_field("from_square", &self.from_square)?;
         state.serialize_field("to_square", &self.to_square)?;
         state.serialize_field("player", self.piece.get_colour())?;
+        state.serialize_field("promote_to", &self.promote_to)?;
+        state.end()
+    }
+}
+
+/// A legal move, along with its standard algebraic and UCI notations and its classification.
+pub struct LegalMove {
+    pub chess_move: rulebook::Move,
+    pub san: String,
+    pub classification: rulebook::MoveClassification,
+}
+
+impl serde::Serialize for LegalMove {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("LegalMove", 1)?;
+        state.serialize_field("from_square", &self.chess_move.from_square)?;
+        state.serialize_field("to_square", &self.chess_move.to_square)?;
+        state.serialize_field("player", self.chess_move.piece.get_colour())?;
+        state.serialize_field("promote_to", &self.chess_move.promote_to)?;
+        state.serialize_field("san", &self.san)?;
+        state.serialize_field("uci", &rulebook::to_uci(&self.chess_move))?;
+        state.serialize_field("kind", &self.classification.kind)?;
+        state.serialize_field("captured_piece", &self.classification.captured_piece)?;
+        state.serialize_field("gives_check", &self.classification.gives_check)?;
+        state.serialize_field("gives_checkmate", &self.classification.gives_checkmate)?;
         state.end()
     }
 }
@@ -125,11 +192,69 @@ mod tests {
 
         let game_json = serde_json::to_string(&game).unwrap();
 
-        assert!(
-            game_json.starts_with(r#"{"id":1,"status":"ToPlayWhite","chessboard":{"position":{"#)
+        assert!(game_json.starts_with(
+            r#"{"id":1,"status":"ToPlayWhite","termination":null,"draw_offer":null,"takeback_request":null,"clock":null,"variant":"Standard","white_checks_given":0,"black_checks_given":0,"chessboard":{"position":{"#
+        ));
+    }
+
+    #[test]
+    fn serializes_game_termination_to_json() {
+        let chessboard_history = vec![factories::chessboard()];
+        let game = game::Game::reincarnate(
+            1,
+            game::GameStatus::WonByBlack,
+            Some(game::GameTermination::Checkmate),
+            None,
+            None,
+            None,
+            game::GameVariant::Standard,
+            game::GameStart::default(),
+            vec![],
+            chessboard_history,
+        );
+
+        let game_json = serde_json::to_string(&game).unwrap();
+
+        assert!(game_json.contains(r#""status":"WonByBlack","termination":"Checkmate""#));
+    }
+
+    #[test]
+    fn serializes_draw_offer_to_json() {
+        let mut game = game::Game::new(1);
+        game.offer_draw(&chess_set::Colour::White).unwrap();
+
+        let game_json = serde_json::to_string(&game).unwrap();
+
+        assert!(game_json.contains(r#""draw_offer":{"offered_by":"White","ply":0}"#));
+    }
+
+    #[test]
+    fn serializes_clock_to_json() {
+        let time_control = clock::TimeControl::Fischer {
+            base_ms: 60_000,
+            increment_ms: 1_000,
+        };
+        let game_clock =
+            clock::Clock::reincarnate(time_control, 59_000, 60_000, 1_234, vec![59_000]);
+
+        let clock_json = serde_json::to_string(&game_clock).unwrap();
+
+        assert_eq!(
+            clock_json,
+            r#"{"time_control":{"Fischer":{"base_ms":60000,"increment_ms":1000}},"white_remaining_ms":59000,"black_remaining_ms":60000,"turn_started_at_ms":1234,"move_remaining_ms":[59000]}"#
         );
     }
 
+    #[test]
+    fn serializes_game_zobrist_hash_to_json_as_hex() {
+        let game = game::Game::new(1);
+
+        let game_json = serde_json::to_string(&game).unwrap();
+
+        let expected_hash = format!(r#""zobrist_hash":"{:016x}""#, game.get_zobrist_hash());
+        assert!(game_json.contains(&expected_hash));
+    }
+
     #[test]
     fn serializes_move_to_json() {
         let from_square = chess_set::Square::new(chess_set::Rank::Eight, chess_set::File::E);
@@ -142,7 +267,75 @@ mod tests {
 
         assert_eq!(
             move_json,
-            "{\"from_square\":\"E8\",\"to_square\":\"C5\",\"player\":\"White\"}"
+            "{\"from_square\":\"E8\",\"to_square\":\"C5\",\"player\":\"White\",\"promote_to\":null}"
+        );
+    }
+
+    #[test]
+    fn serializes_promotion_move_to_json() {
+        let from_square = chess_set::Square::new(chess_set::Rank::Seven, chess_set::File::A);
+        let to_square = chess_set::Square::new(chess_set::Rank::Eight, chess_set::File::A);
+        let piece = chess_set::Piece::new(chess_set::Colour::White, chess_set::PieceType::Pawn);
+
+        let chess_move = rulebook::Move::new_promotion(
+            piece,
+            from_square,
+            to_square,
+            chess_set::PieceType::Queen,
+        );
+
+        let move_json = serde_json::to_string(&chess_move).unwrap();
+
+        assert_eq!(
+            move_json,
+            "{\"from_square\":\"A7\",\"to_square\":\"A8\",\"player\":\"White\",\"promote_to\":\"Queen\"}"
+        );
+    }
+
+    #[test]
+    fn serializes_moves_played_in_game_as_san() {
+        let mut game = game::Game::new(1);
+        game.play_san_move(&chess_set::Colour::White, "e4").unwrap();
+
+        let game_json = serde_json::to_string(&game).unwrap();
+
+        assert!(game_json.ends_with(r#""moves":["e4"]}"#));
+    }
+
+    #[test]
+    fn serializes_move_classifications_of_game() {
+        let mut game = game::Game::new(1);
+        game.play_san_move(&chess_set::Colour::White, "e4").unwrap();
+
+        let game_json = serde_json::to_string(&game).unwrap();
+
+        assert!(game_json.contains(
+            r#""move_classifications":[{"kind":"DoublePush","captured_piece":null,"gives_check":false,"gives_checkmate":false}]"#
+        ));
+    }
+
+    #[test]
+    fn serializes_legal_move_to_json() {
+        let from_square = chess_set::Square::new(chess_set::Rank::One, chess_set::File::G);
+        let to_square = chess_set::Square::new(chess_set::Rank::Three, chess_set::File::F);
+        let piece = chess_set::Piece::new(chess_set::Colour::White, chess_set::PieceType::Knight);
+
+        let legal_move = LegalMove {
+            chess_move: rulebook::Move::new(piece, from_square, to_square),
+            san: "Nf3".to_string(),
+            classification: rulebook::MoveClassification {
+                kind: rulebook::MoveKind::Quiet,
+                captured_piece: None,
+                gives_check: false,
+                gives_checkmate: false,
+            },
+        };
+
+        let move_json = serde_json::to_string(&legal_move).unwrap();
+
+        assert_eq!(
+            move_json,
+            "{\"from_square\":\"G1\",\"to_square\":\"F3\",\"player\":\"White\",\"promote_to\":null,\"san\":\"Nf3\",\"uci\":\"g1f3\",\"kind\":\"Quiet\",\"captured_piece\":null,\"gives_check\":false,\"gives_checkmate\":false}"
         );
     }
 }
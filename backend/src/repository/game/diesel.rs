@@ -5,7 +5,7 @@ use std::collections::BTreeMap;
 
 use super::repo;
 use crate::data::{connection, models};
-use crate::domain::{chess_set, game};
+use crate::domain::{chess_set, game, rulebook};
 
 pub struct DieselGameRepository {
     connection: PgConnection,
@@ -21,15 +21,49 @@ impl DieselGameRepository {
 
 impl repo::GameRepository for DieselGameRepository {
     fn get(&mut self, id: &i32) -> Option<game::Game> {
+        let db_game = models::Game::get(&mut self.connection, id)?;
         let chessboard_squares =
-            models::OccupiedChessboardSquare::select_for_game(&mut self.connection, &id);
-        let chessboard_history =
-            convert_chessboard_squares_to_chessboard_history(chessboard_squares);
+            models::OccupiedChessboardSquare::select_for_game(&mut self.connection, id);
+        let chessboard_states = models::ChessboardState::select_for_game(&mut self.connection, id);
+        let mut chessboard_history = convert_chessboard_squares_to_chessboard_history(
+            chessboard_squares,
+            &chessboard_states,
+        );
 
-        match models::Game::get(&mut self.connection, id) {
-            Some(db_game) => Some(db_game.to_domain(chessboard_history)),
-            None => None,
+        // Games created before chessboard states were stored have none for their earlier
+        // chessboards, so work them out by replaying the game, and store them from now on.
+        let restored_state_indexes = restore_missing_chessboard_states(
+            db_game.to_domain_starting_colour(),
+            &chessboard_states,
+            &mut chessboard_history,
+        );
+
+        // Likewise, games created before moves were stored have none for their earlier moves.
+        let db_moves = models::PlayedMove::select_for_game(&mut self.connection, id);
+        let (moves, restored_move_indexes) = restore_missing_moves(
+            db_game.to_domain_starting_colour(),
+            &db_moves,
+            &chessboard_history,
+        );
+        let game = db_game.to_domain(moves, chessboard_history);
+
+        for chessboard_history_index in restored_state_indexes {
+            models::ChessboardState::create_for_chessboard(
+                &mut self.connection,
+                &game,
+                chessboard_history_index,
+            );
         }
+        for chessboard_history_index in restored_move_indexes {
+            let played_move = &game.get_moves()[chessboard_history_index - 1];
+            models::PlayedMove::create_for_move(
+                &mut self.connection,
+                &game,
+                chessboard_history_index,
+                played_move,
+            );
+        }
+        Some(game)
     }
 
     fn create(&mut self) -> game::Game {
@@ -43,6 +77,61 @@ impl repo::GameRepository for DieselGameRepository {
                 models::OccupiedChessboardSquare::bulk_create_for_latest_chessboard(
                     connection, &game,
                 );
+                models::ChessboardState::create_for_latest_chessboard(connection, &game);
+
+                Ok(game)
+            });
+
+        match result {
+            Ok(game) => game.clone(),
+            Err(_) => panic!("Error creating game!"),
+        }
+    }
+
+    fn create_from_position(
+        &mut self,
+        fen: &rulebook::Fen,
+        variant: game::GameVariant,
+    ) -> game::Game {
+        let result = &self
+            .connection
+            .transaction::<game::Game, diesel_result::Error, _>(|connection| {
+                let db_game = models::Game::create(connection, game::GameStatus::ToPlayWhite);
+                let game = game::Game::from_position(db_game.id, fen, variant);
+                models::Game::update_status(connection, &game);
+                models::Game::update_start(connection, &game);
+
+                // Persist the initial chessboard.
+                models::OccupiedChessboardSquare::bulk_create_for_latest_chessboard(
+                    connection, &game,
+                );
+                models::ChessboardState::create_for_latest_chessboard(connection, &game);
+
+                Ok(game)
+            });
+
+        match result {
+            Ok(game) => game.clone(),
+            Err(_) => panic!("Error creating game!"),
+        }
+    }
+
+    fn create_from_game(&mut self, game: &game::Game) -> game::Game {
+        let result = &self
+            .connection
+            .transaction::<game::Game, diesel_result::Error, _>(|connection| {
+                let db_game = models::Game::create(connection, game.get_status().clone());
+                let game = game.with_id(db_game.id);
+                models::Game::update_status(connection, &game);
+                models::Game::update_start(connection, &game);
+                models::Game::update_clock(connection, &game);
+
+                // Persist every chessboard and move at once.
+                models::OccupiedChessboardSquare::bulk_create_for_chessboard_history(
+                    connection, &game,
+                );
+                models::ChessboardState::create_for_chessboard_history(connection, &game);
+                models::PlayedMove::create_for_moves(connection, &game);
 
                 Ok(game)
             });
@@ -58,9 +147,31 @@ impl repo::GameRepository for DieselGameRepository {
             .connection
             .transaction::<(), diesel_result::Error, _>(|connection| {
                 models::Game::update_status(connection, &game);
+                models::Game::update_clock(connection, game);
+
+                // Rewrite the latest chessboard, truncating any later chessboards that
+                // have since been taken back.
+                let latest_index = game.get_chessboard_history().len() - 1;
+                models::OccupiedChessboardSquare::delete_from_chessboard_history_index(
+                    connection,
+                    game.get_id(),
+                    latest_index,
+                );
+                models::ChessboardState::delete_from_chessboard_history_index(
+                    connection,
+                    game.get_id(),
+                    latest_index,
+                );
+                models::PlayedMove::delete_from_chessboard_history_index(
+                    connection,
+                    game.get_id(),
+                    latest_index,
+                );
                 models::OccupiedChessboardSquare::bulk_create_for_latest_chessboard(
                     connection, &game,
                 );
+                models::ChessboardState::create_for_latest_chessboard(connection, game);
+                models::PlayedMove::create_for_latest_move(connection, game);
 
                 Ok(())
             });
@@ -73,6 +184,7 @@ impl repo::GameRepository for DieselGameRepository {
 
 fn convert_chessboard_squares_to_chessboard_history(
     squares: Vec<models::OccupiedChessboardSquare>,
+    states: &[models::ChessboardState],
 ) -> Vec<chess_set::Chessboard> {
     let mut chessboard_history: Vec<BTreeMap<chess_set::Square, chess_set::Piece>> = vec![];
 
@@ -88,8 +200,93 @@ fn convert_chessboard_squares_to_chessboard_history(
         chessboard_history[db_square.chessboard_history_index as usize].insert(square, piece);
     }
 
-    chessboard_history
+    let mut chessboard_history = chessboard_history
         .iter()
         .map(|position| chess_set::Chessboard::new(position.clone()))
-        .collect::<Vec<chess_set::Chessboard>>()
+        .collect::<Vec<chess_set::Chessboard>>();
+
+    for db_state in states.iter() {
+        let chessboard = &mut chessboard_history[db_state.chessboard_history_index as usize];
+        chessboard.castling_rights = db_state.to_domain_castling_rights();
+        chessboard.en_passant_square = db_state.to_domain_en_passant_square();
+    }
+
+    chessboard_history
+}
+
+/// Work out the castling rights and en passant square of each chessboard without a stored state,
+/// returning the indexes of the chessboards that were restored.
+///
+/// The first chessboard can castle with any rook left on its back rank with its king, and each
+/// later one has the state left by the move that led to it.
+fn restore_missing_chessboard_states(
+    starting_colour: chess_set::Colour,
+    states: &[models::ChessboardState],
+    chessboard_history: &mut [chess_set::Chessboard],
+) -> Vec<usize> {
+    let mut restored_indexes = vec![];
+    let mut player = starting_colour;
+
+    for index in 0..chessboard_history.len() {
+        let has_state = states
+            .iter()
+            .any(|db_state| db_state.chessboard_history_index as usize == index);
+        if !has_state {
+            let chessboard = &chessboard_history[index];
+            let restored_chessboard = match index {
+                0 => chess_set::Chessboard {
+                    castling_rights: rulebook::get_possible_castling_rights(chessboard),
+                    ..chessboard.clone()
+                },
+                _ => {
+                    let previous_history = &chessboard_history[..index];
+                    rulebook::get_played_move(player.swap(), previous_history, chessboard)
+                        .and_then(|chess_move| chess_move.apply_if_valid(previous_history).ok())
+                        .expect("Chessboard history should only contain legal moves.")
+                }
+            };
+            chessboard_history[index] = restored_chessboard;
+            restored_indexes.push(index);
+        };
+        player = player.swap();
+    }
+
+    restored_indexes
+}
+
+/// Work out each move without a stored move from the chessboards before and after it,
+/// returning every move along with the indexes of the chessboards whose moves were restored.
+fn restore_missing_moves(
+    starting_colour: chess_set::Colour,
+    db_moves: &[models::PlayedMove],
+    chessboard_history: &[chess_set::Chessboard],
+) -> (Vec<game::PlayedMove>, Vec<usize>) {
+    let mut moves = vec![];
+    let mut restored_indexes = vec![];
+    let mut player = starting_colour;
+
+    for index in 1..chessboard_history.len() {
+        let db_move = db_moves
+            .iter()
+            .find(|db_move| db_move.chessboard_history_index as usize == index);
+        let played_move = match db_move {
+            Some(db_move) => db_move.to_domain(),
+            None => {
+                let previous_history = &chessboard_history[..index];
+                let chess_move =
+                    rulebook::get_played_move(player, previous_history, &chessboard_history[index])
+                        .expect("Chessboard history should only contain legal moves.");
+                restored_indexes.push(index);
+                game::PlayedMove {
+                    san: rulebook::to_san(&chess_move, previous_history),
+                    classification: rulebook::classify_move(&chess_move, previous_history)
+                        .expect("Chessboard history should only contain legal moves."),
+                }
+            }
+        };
+        moves.push(played_move);
+        player = player.swap();
+    }
+
+    (moves, restored_indexes)
 }
@@ -1,5 +1,5 @@
 use super::repo;
-use crate::domain::game;
+use crate::domain::{game, rulebook};
 use std::collections::BTreeMap;
 
 pub struct FakeGameRepository {
@@ -21,6 +21,24 @@ impl repo::GameRepository for FakeGameRepository {
         game
     }
 
+    fn create_from_position(
+        &mut self,
+        fen: &rulebook::Fen,
+        variant: game::GameVariant,
+    ) -> game::Game {
+        let id = self.get_next_id();
+        let game = game::Game::from_position(id, fen, variant);
+        self.games.insert(id, game.clone());
+        game
+    }
+
+    fn create_from_game(&mut self, game: &game::Game) -> game::Game {
+        let id = self.get_next_id();
+        let game = game.with_id(id);
+        self.games.insert(id, game.clone());
+        game
+    }
+
     fn update(&mut self, game: &game::Game) {
         self.games.insert(game.get_id().clone(), game.clone());
     }
@@ -74,7 +92,8 @@ mod tests {
     mod create_tests {
         use super::super::repo::GameRepository;
         use super::super::FakeGameRepository;
-        use crate::domain::game;
+        use crate::domain::chess_set::Colour;
+        use crate::domain::{game, rulebook};
 
         #[test]
         fn creates_first_game_with_id_one() {
@@ -97,6 +116,38 @@ mod tests {
             assert_eq!(result.get_id(), &10);
             assert_eq!(result.get_chessboard_history().len(), 1);
         }
+
+        #[test]
+        fn creates_game_from_position() {
+            let mut repo = FakeGameRepository::new();
+            let fen = rulebook::Fen::parse("7k/8/8/8/8/8/8/R6K b - - 0 1").unwrap();
+
+            let result = repo.create_from_position(&fen, game::GameVariant::Standard);
+
+            assert_eq!(result.get_id(), &1);
+            assert_eq!(result.get_status(), &game::GameStatus::ToPlayBlack);
+            assert_eq!(result.get_starting_colour(), Colour::Black);
+            assert_eq!(
+                result.get_chessboard_history(),
+                &fen.to_chessboard_history()
+            );
+            assert_eq!(repo.games.get(&1), Some(&result));
+        }
+
+        #[test]
+        fn creates_game_from_game() {
+            let mut repo = FakeGameRepository::new();
+            repo.create();
+            let mut game = game::Game::new(0);
+            game.play_san_move(&Colour::White, "e4").unwrap();
+
+            let result = repo.create_from_game(&game);
+
+            assert_eq!(result.get_id(), &2);
+            assert_eq!(result.get_moves_in_san(), vec!["e4"]);
+            assert_eq!(result, game.with_id(2));
+            assert_eq!(repo.games.get(&2), Some(&result));
+        }
     }
 
     #[cfg(test)]
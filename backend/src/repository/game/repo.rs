@@ -1,9 +1,20 @@
-use crate::domain::game;
+use crate::domain::{game, rulebook};
 
 pub trait GameRepository {
     fn get(&mut self, id: &i32) -> Option<game::Game>;
 
     fn create(&mut self) -> game::Game;
 
+    /// Create a game of a variant starting from the position described by a FEN.
+    fn create_from_position(
+        &mut self,
+        fen: &rulebook::Fen,
+        variant: game::GameVariant,
+    ) -> game::Game;
+
+    /// Create a game with everything played in a game that hasn't been persisted,
+    /// such as one replayed from a PGN file.
+    fn create_from_game(&mut self, game: &game::Game) -> game::Game;
+
     fn update(&mut self, game: &game::Game);
 }
@@ -0,0 +1,61 @@
+use crate::domain::chess_set;
+use crate::domain::game;
+use crate::repository;
+
+use super::get_game;
+
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ClaimDrawError {
+    #[error("Game {0} does not exist")]
+    GameDoesNotExist(i32),
+
+    #[error("{0}")]
+    InvalidClaim(game::GameError),
+}
+
+pub fn claim_draw(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, ClaimDrawError> {
+    let Some(mut game) = get_game::load_game(game_repo.as_mut(), game_id) else {
+        return Err(ClaimDrawError::GameDoesNotExist(*game_id));
+    };
+
+    if let Err(err) = game.claim_draw(player) {
+        return Err(ClaimDrawError::InvalidClaim(err));
+    }
+
+    game_repo.update(&game);
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::repository::GameRepository;
+
+    #[test]
+    fn errors_when_game_does_not_exist() {
+        let game_repo = repository::FakeGameRepository::new();
+        let invalid_id = 37;
+
+        let result = claim_draw(Box::new(game_repo), &invalid_id, &Colour::White);
+
+        assert_eq!(result, Err(ClaimDrawError::GameDoesNotExist(invalid_id)))
+    }
+
+    #[test]
+    fn errors_when_there_is_no_draw_to_claim() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = claim_draw(Box::new(game_repo), game.get_id(), &Colour::White);
+
+        let game_error = game::GameError::NoDrawToClaim;
+        assert_eq!(result, Err(ClaimDrawError::InvalidClaim(game_error)))
+    }
+}
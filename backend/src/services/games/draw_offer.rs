@@ -0,0 +1,188 @@
+use crate::domain::chess_set;
+use crate::domain::{engine, game};
+use crate::repository;
+
+use super::get_game;
+
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum DrawOfferError {
+    #[error("Game {0} does not exist")]
+    GameDoesNotExist(i32),
+
+    #[error("{0}")]
+    InvalidDrawOffer(game::GameError),
+}
+
+pub fn offer_draw(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, DrawOfferError> {
+    update_draw_offer(game_repo, game_id, |game| game.offer_draw(player))
+}
+
+pub fn accept_draw(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, DrawOfferError> {
+    update_draw_offer(game_repo, game_id, |game| game.accept_draw(player))
+}
+
+pub fn decline_draw(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, DrawOfferError> {
+    update_draw_offer(game_repo, game_id, |game| game.decline_draw(player))
+}
+
+/// Let an engine accept or decline the draw its opponent has offered.
+pub fn respond_to_draw_offer(
+    game_repo: Box<dyn repository::GameRepository>,
+    mut chess_engine: Box<dyn engine::ChessEngine>,
+    game_id: &i32,
+) -> Result<game::Game, DrawOfferError> {
+    update_draw_offer(game_repo, game_id, |game| {
+        if let Some(budget) = game.get_search_time_budget() {
+            chess_engine.set_search_time_budget(budget);
+        }
+
+        let Some(draw_offer) = *game.get_draw_offer() else {
+            return Err(game::GameError::NoDrawOffer);
+        };
+        let responder = draw_offer.offered_by.swap();
+
+        match chess_engine.should_accept_draw_offer(game) {
+            true => game.accept_draw(&responder),
+            false => game.decline_draw(&responder),
+        }
+    })
+}
+
+fn update_draw_offer(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    update: impl FnOnce(&mut game::Game) -> Result<&game::GameStatus, game::GameError>,
+) -> Result<game::Game, DrawOfferError> {
+    let Some(mut game) = get_game::load_game(game_repo.as_mut(), game_id) else {
+        return Err(DrawOfferError::GameDoesNotExist(*game_id));
+    };
+
+    if let Err(err) = update(&mut game) {
+        return Err(DrawOfferError::InvalidDrawOffer(err));
+    }
+
+    game_repo.update(&game);
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::repository::GameRepository;
+
+    #[test]
+    fn player_can_offer_draw() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = offer_draw(Box::new(game_repo), game.get_id(), &Colour::White);
+
+        let expected_offer = game::DrawOffer {
+            offered_by: Colour::White,
+            ply: 0,
+        };
+        assert_eq!(result.unwrap().get_draw_offer(), &Some(expected_offer));
+    }
+
+    #[test]
+    fn offered_draw_can_be_accepted() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = game_repo.create();
+        game.offer_draw(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = accept_draw(Box::new(game_repo), game.get_id(), &Colour::Black);
+
+        let drawn_game = result.unwrap();
+        assert_eq!(drawn_game.get_status(), &game::GameStatus::Drawn);
+        assert_eq!(
+            drawn_game.get_termination(),
+            &Some(game::GameTermination::Agreement)
+        );
+    }
+
+    #[test]
+    fn offered_draw_can_be_declined() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = game_repo.create();
+        game.offer_draw(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = decline_draw(Box::new(game_repo), game.get_id(), &Colour::Black);
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayWhite);
+        assert_eq!(updated_game.get_draw_offer(), &None);
+    }
+
+    #[test]
+    fn errors_when_accepting_own_draw_offer() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = game_repo.create();
+        game.offer_draw(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = accept_draw(Box::new(game_repo), game.get_id(), &Colour::White);
+
+        let game_error = game::GameError::CannotRespondToOwnDrawOffer(Colour::White);
+        assert_eq!(result, Err(DrawOfferError::InvalidDrawOffer(game_error)))
+    }
+
+    #[test]
+    fn engine_declines_draw_offer_by_default() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = game_repo.create();
+        game.offer_draw(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = respond_to_draw_offer(
+            Box::new(game_repo),
+            Box::new(engine::Random::new()),
+            game.get_id(),
+        );
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayWhite);
+        assert_eq!(updated_game.get_draw_offer(), &None);
+    }
+
+    #[test]
+    fn errors_when_engine_has_no_draw_offer_to_respond_to() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = respond_to_draw_offer(
+            Box::new(game_repo),
+            Box::new(engine::Random::new()),
+            game.get_id(),
+        );
+
+        let game_error = game::GameError::NoDrawOffer;
+        assert_eq!(result, Err(DrawOfferError::InvalidDrawOffer(game_error)))
+    }
+
+    #[test]
+    fn errors_when_game_does_not_exist() {
+        let game_repo = repository::FakeGameRepository::new();
+        let invalid_id = 37;
+
+        let result = offer_draw(Box::new(game_repo), &invalid_id, &Colour::White);
+
+        assert_eq!(result, Err(DrawOfferError::GameDoesNotExist(invalid_id)))
+    }
+}
@@ -15,19 +15,35 @@ pub enum GenerateNextMoveError {
 
 pub fn generate_and_play_next_move(
     mut game_repo: Box<dyn repository::GameRepository>,
-    chess_engine: Box<dyn engine::ChessEngine>,
+    mut chess_engine: Box<dyn engine::ChessEngine>,
     game_id: i32,
 ) -> Result<game::Game, GenerateNextMoveError> {
     let Some(mut game) = game_repo.get(&game_id) else {
         return Err(GenerateNextMoveError::GameDoesNotExist(game_id));
     };
 
-    let Some(_) = game.get_status().to_play_colour() else {
+    let Some(to_play_colour) = game.get_status().to_play_colour() else {
         return Err(GenerateNextMoveError::GameHasAlreadyEnded);
     };
 
+    // The engine may have run out of time before it was asked to move.
+    if game.check_for_timeout().to_play_colour().is_none() {
+        game_repo.update(&game);
+        return Ok(game);
+    }
+
+    if let Some(budget) = game.get_search_time_budget() {
+        chess_engine.set_search_time_budget(budget);
+    }
+
     let move_to_play = match chess_engine.generate_next_move(&game) {
         Ok(move_to_play) => move_to_play,
+        Err(engine::SuggestNextMoveError::EngineResigned) => {
+            game.resign(&to_play_colour)
+                .unwrap_or_else(|_| panic!("Engine resigned a game that has ended!"));
+            game_repo.update(&game);
+            return Ok(game);
+        }
         Err(err) => return Err(GenerateNextMoveError::SuggestMoveError(err)),
     };
 
@@ -42,8 +58,11 @@ pub fn generate_and_play_next_move(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config;
     use crate::domain::chess_set::{Colour, File, Rank, Square};
+    use crate::domain::{clock, rulebook};
     use crate::repository::GameRepository;
+    use crate::testing::factories;
 
     #[test]
     fn plays_move_for_white_generated_by_random_move_engine() {
@@ -86,6 +105,93 @@ mod tests {
         assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayWhite);
     }
 
+    #[test]
+    fn resigns_game_when_engine_resigns() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        // An engine that resigns any position it is asked to play.
+        let evaluator = engine::PiecePlacementChessboardEvaluator;
+        let engine = engine::Minimax::new_with_resign_threshold(Box::new(evaluator), 1, i32::MAX);
+
+        let result =
+            generate_and_play_next_move(Box::new(game_repo), Box::new(engine), *game.get_id());
+
+        let resigned_game = result.unwrap();
+        assert_eq!(resigned_game.get_chessboard_history().len(), 1);
+        assert_eq!(resigned_game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(
+            resigned_game.get_termination(),
+            &Some(game::GameTermination::Resignation)
+        );
+    }
+
+    #[test]
+    fn configured_minimax_engine_resigns_hopeless_position() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let fen = rulebook::Fen::parse("k7/8/8/8/7Q/7Q/7Q/5RRK b - - 0 1").unwrap();
+        let game = game_repo.create_from_position(&fen, game::GameVariant::Standard);
+        let engine = config::get_chess_engine(&config::ChessEngineImplementation::Minimax);
+
+        let result = generate_and_play_next_move(Box::new(game_repo), engine, *game.get_id());
+
+        let resigned_game = result.unwrap();
+        assert_eq!(resigned_game.get_status(), &game::GameStatus::WonByWhite);
+        assert_eq!(
+            resigned_game.get_termination(),
+            &Some(game::GameTermination::Resignation)
+        );
+    }
+
+    #[test]
+    fn configured_minimax_engine_plays_on_in_level_position() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let fen = rulebook::Fen::parse("k7/8/8/8/3q4/8/8/5RRK b - - 0 1").unwrap();
+        let game = game_repo.create_from_position(&fen, game::GameVariant::Standard);
+        let engine = config::get_chess_engine(&config::ChessEngineImplementation::Minimax);
+
+        let result = generate_and_play_next_move(Box::new(game_repo), engine, *game.get_id());
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_chessboard_history().len(), 2);
+        assert_eq!(updated_game.get_termination(), &None);
+    }
+
+    #[test]
+    fn ends_game_when_engine_has_run_out_of_time() {
+        let time_control = clock::TimeControl::Fischer {
+            base_ms: 60_000,
+            increment_ms: 0,
+        };
+        // The engine's turn started at the Unix epoch, so its time has long run out.
+        let expired_clock = clock::Clock::reincarnate(time_control, 60_000, 60_000, 0, vec![]);
+        let game = game::Game::reincarnate(
+            1,
+            game::GameStatus::ToPlayWhite,
+            None,
+            None,
+            None,
+            Some(expired_clock),
+            game::GameVariant::Standard,
+            game::GameStart::default(),
+            vec![],
+            vec![factories::chessboard()],
+        );
+        let mut game_repo = repository::FakeGameRepository::new();
+        game_repo.update(&game);
+
+        let result =
+            generate_and_play_next_move(Box::new(game_repo), Box::new(engine::Random::new()), 1);
+
+        let timed_out_game = result.unwrap();
+        assert_eq!(timed_out_game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(
+            timed_out_game.get_termination(),
+            &Some(game::GameTermination::Timeout)
+        );
+        assert_eq!(timed_out_game.get_chessboard_history().len(), 1);
+    }
+
     #[test]
     fn returns_error_when_game_does_not_exist() {
         let game_repo = repository::FakeGameRepository::new();
@@ -99,7 +205,18 @@ mod tests {
 
     #[test]
     fn returns_error_when_game_has_already_ended() {
-        let drawn_game = game::Game::reincarnate(1, game::GameStatus::Drawn, vec![]);
+        let drawn_game = game::Game::reincarnate(
+            1,
+            game::GameStatus::Drawn,
+            None,
+            None,
+            None,
+            None,
+            game::GameVariant::Standard,
+            game::GameStart::default(),
+            vec![],
+            vec![],
+        );
         let mut game_repo = repository::FakeGameRepository::new();
         game_repo.update(&drawn_game);
 
@@ -0,0 +1,96 @@
+use crate::domain::game;
+use crate::repository;
+
+/// Get a game, ending it first if the player to move has run out of time.
+pub fn get_game(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+) -> Option<game::Game> {
+    load_game(game_repo.as_mut(), game_id)
+}
+
+/// Load a game, persisting its end if the player to move has run out of time.
+///
+/// Clocks keep running between requests, so a player's flag can fall without them
+/// playing another move. Games are checked whenever they are loaded, so that a game
+/// that has ended on time is never served or played on as if it were in progress.
+pub(super) fn load_game(
+    game_repo: &mut dyn repository::GameRepository,
+    game_id: &i32,
+) -> Option<game::Game> {
+    let mut game = game_repo.get(game_id)?;
+
+    let was_in_progress = game.get_status().to_play_colour().is_some();
+    if was_in_progress && game.check_for_timeout().to_play_colour().is_none() {
+        game_repo.update(&game);
+    }
+    Some(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::clock;
+    use crate::repository::GameRepository;
+
+    fn create_game_with_expired_clock(
+        game_repo: &mut repository::FakeGameRepository,
+    ) -> game::Game {
+        let game = game_repo.create();
+        let time_control = clock::TimeControl::Fischer {
+            base_ms: 60_000,
+            increment_ms: 0,
+        };
+        // White's turn started at the Unix epoch, so their time has long run out.
+        let expired_clock = clock::Clock::reincarnate(time_control, 60_000, 60_000, 0, vec![]);
+        let game = game::Game::reincarnate(
+            *game.get_id(),
+            game::GameStatus::ToPlayWhite,
+            None,
+            None,
+            None,
+            Some(expired_clock),
+            game::GameVariant::Standard,
+            game::GameStart::default(),
+            vec![],
+            game.get_chessboard_history().clone(),
+        );
+        game_repo.update(&game);
+        game
+    }
+
+    #[test]
+    fn ends_game_on_timeout_when_it_is_loaded() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = create_game_with_expired_clock(&mut game_repo);
+
+        let loaded_game = load_game(&mut game_repo, game.get_id()).unwrap();
+
+        assert_eq!(loaded_game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(
+            loaded_game.get_termination(),
+            &Some(game::GameTermination::Timeout)
+        );
+        let persisted_game = game_repo.get(game.get_id()).unwrap();
+        assert_eq!(persisted_game, loaded_game);
+    }
+
+    #[test]
+    fn gets_game_in_progress_unchanged() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = get_game(Box::new(game_repo), game.get_id());
+
+        assert_eq!(result, Some(game));
+    }
+
+    #[test]
+    fn gets_none_when_game_does_not_exist() {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let result = get_game(Box::new(game_repo), &37);
+
+        assert_eq!(result, None);
+    }
+}
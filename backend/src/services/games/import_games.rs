@@ -0,0 +1,337 @@
+use crate::domain::{chess_set, game, pgn, rulebook};
+use crate::repository;
+
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ImportGameError {
+    #[error("{0}")]
+    InvalidPgn(pgn::PgnError),
+
+    #[error("Ply {ply} ('{san}') is not a legal move.")]
+    IllegalMove { ply: usize, san: String },
+
+    #[error("The result '{0}' contradicts the final position.")]
+    ContradictoryResult(String),
+}
+
+/// Create a game for each game recorded in a PGN file.
+///
+/// Each game is replayed before anything is persisted, so games that can't be
+/// imported leave no trace.
+pub fn import_games(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    pgn: &str,
+) -> Vec<Result<game::Game, ImportGameError>> {
+    pgn::Pgn::parse_all(pgn)
+        .into_iter()
+        .map(|parsed_pgn| match parsed_pgn {
+            Ok(parsed_pgn) => import_game(&mut game_repo, &parsed_pgn),
+            Err(error) => Err(ImportGameError::InvalidPgn(error)),
+        })
+        .collect()
+}
+
+fn import_game(
+    game_repo: &mut Box<dyn repository::GameRepository>,
+    parsed_pgn: &pgn::Pgn,
+) -> Result<game::Game, ImportGameError> {
+    let setup = match parsed_pgn.get_setup() {
+        Ok(setup) => setup,
+        Err(error) => return Err(ImportGameError::InvalidPgn(error)),
+    };
+
+    // Replay the game before creating it, so only valid games are persisted.
+    let fen = setup.unwrap_or_else(|| {
+        let chessboard = rulebook::get_official_starting_chessboard();
+        rulebook::Fen::from_chessboard_history(chess_set::Colour::White, 0, 1, &[chessboard])
+    });
+    let mut game = game::Game::from_position(0, &fen, parsed_pgn.get_variant());
+    for (index, san) in parsed_pgn.moves.iter().enumerate() {
+        if play_san(&mut game, san).is_err() {
+            return Err(ImportGameError::IllegalMove {
+                ply: index + 1,
+                san: san.clone(),
+            });
+        };
+    }
+    apply_result(&mut game, parsed_pgn)?;
+
+    Ok(game_repo.create_from_game(&game))
+}
+
+/// End a game that is still in progress after its moves, as its result records.
+///
+/// Decisive results are applied as resignations, unless the game was lost on time,
+/// and draws are claimed if the rules allow it or else agreed.
+fn apply_result(game: &mut game::Game, parsed_pgn: &pgn::Pgn) -> Result<(), ImportGameError> {
+    let is_time_forfeit = parsed_pgn
+        .get_termination()
+        .is_some_and(|termination| termination.eq_ignore_ascii_case("time forfeit"));
+
+    if let Some(to_play_colour) = game.get_status().to_play_colour() {
+        let in_progress = "The game is in progress.";
+        let loser = match parsed_pgn.result.as_str() {
+            "1-0" => Some(chess_set::Colour::Black),
+            "0-1" => Some(chess_set::Colour::White),
+            _ => None,
+        };
+        match (loser, parsed_pgn.result.as_str()) {
+            (Some(loser), _) if is_time_forfeit => {
+                game.forfeit_on_time(&loser).expect(in_progress);
+            }
+            (Some(loser), _) => {
+                game.resign(&loser).expect(in_progress);
+            }
+            // A player who runs out of time draws if their opponent can't checkmate them.
+            (None, "1/2-1/2") if is_time_forfeit => {
+                game.forfeit_on_time(&to_play_colour).expect(in_progress);
+            }
+            (None, "1/2-1/2") => {
+                let is_claimed = game.claim_draw(&to_play_colour).is_ok();
+                if !is_claimed {
+                    game.offer_draw(&to_play_colour.swap()).expect(in_progress);
+                    game.accept_draw(&to_play_colour).expect(in_progress);
+                };
+            }
+            _ => {}
+        };
+    };
+
+    if pgn::get_result(game.get_status()) != parsed_pgn.result {
+        return Err(ImportGameError::ContradictoryResult(
+            parsed_pgn.result.clone(),
+        ));
+    };
+    Ok(())
+}
+
+/// Play a move written in standard algebraic notation, on behalf of the player to move.
+fn play_san(game: &mut game::Game, san: &str) -> Result<(), game::GameError> {
+    let Some(player) = game.get_status().to_play_colour() else {
+        return Err(game::GameError::GameHasAlreadyEnded);
+    };
+    match game.play_san_move(&player, san) {
+        Ok(_) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::repository::GameRepository;
+    use rstest::rstest;
+
+    #[test]
+    fn can_import_game() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "[Event \"Fool's mate\"]
+
+1. f3 e5 2. g4 Qh4# 0-1";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        assert_eq!(games.len(), 1);
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(game.get_chessboard_history().len(), 5);
+    }
+
+    #[test]
+    fn can_import_game_from_position() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "[SetUp \"1\"]
+[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 1\"]
+
+1... Kd8 2. e4 *";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::ToPlayBlack);
+        assert_eq!(
+            game.get_fen().to_string(),
+            "3k4/8/8/8/4P3/8/8/4K3 b - e3 0 2"
+        );
+    }
+
+    #[test]
+    fn can_import_chess960_game() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "[Variant \"Chess960\"]
+[SetUp \"1\"]
+[FEN \"1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3KR1 w KQkq - 0 1\"]
+
+1. O-O+ *";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_variant(), &game::GameVariant::Chess960);
+        assert_eq!(
+            game.get_fen().to_string(),
+            "1r3kr1/1p4p1/8/8/8/8/1P4P1/1R3RK1 b kq - 1 1"
+        );
+    }
+
+    #[test]
+    fn imported_games_are_created_in_repository() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        game_repo.create();
+        let pgn = "1. e4 e5 * 1. d4 *";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        assert_eq!(games[0].as_ref().unwrap().get_id(), &2);
+        assert_eq!(games[1].as_ref().unwrap().get_id(), &3);
+    }
+
+    #[test]
+    fn returns_error_for_each_invalid_game() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "1. e4 e5 2. Ke3 *
+
+[Event unquoted]
+1. d4 *
+
+1. d4 d5 1/2-1/2";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        assert_eq!(games.len(), 3);
+        assert_eq!(
+            games[0],
+            Err(ImportGameError::IllegalMove {
+                ply: 3,
+                san: "Ke3".to_string()
+            })
+        );
+        assert_eq!(
+            games[1],
+            Err(ImportGameError::InvalidPgn(pgn::PgnError::InvalidTag(
+                "Event unquoted".to_string()
+            )))
+        );
+        let game = games[2].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::Drawn);
+    }
+
+    #[test]
+    fn applies_decisive_result_as_resignation() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "1. e4 e5 2. Qh5 1-0";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::WonByWhite);
+        assert_eq!(
+            game.get_termination(),
+            &Some(game::GameTermination::Resignation)
+        );
+        assert_eq!(game.get_chessboard_history().len(), 4);
+    }
+
+    #[test]
+    fn applies_time_forfeit_from_termination_tag() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "[Termination \"Time forfeit\"]
+
+1. e4 0-1";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(
+            game.get_termination(),
+            &Some(game::GameTermination::Timeout)
+        );
+    }
+
+    #[rstest]
+    #[case::agreed("1. d4 d5 1/2-1/2", game::GameTermination::Agreement)]
+    #[case::claimed(
+        "1. Nf3 Nf6 2. Ng1 Ng8 3. Nf3 Nf6 4. Ng1 Ng8 1/2-1/2",
+        game::GameTermination::Repetition
+    )]
+    fn applies_drawn_result(
+        #[case] pgn: &str,
+        #[case] expected_termination: game::GameTermination,
+    ) {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::Drawn);
+        assert_eq!(game.get_termination(), &Some(expected_termination));
+    }
+
+    #[test]
+    fn leaves_unfinished_game_in_progress() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "1. e4 e5 *";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status().to_play_colour(), Some(Colour::White));
+        assert_eq!(game.get_termination(), &None);
+    }
+
+    #[rstest]
+    #[case::wrong_winner("1. f3 e5 2. g4 Qh4# 1-0", "1-0")]
+    #[case::unfinished_after_checkmate("1. f3 e5 2. g4 Qh4# *", "*")]
+    #[case::draw_after_checkmate("1. f3 e5 2. g4 Qh4# 1/2-1/2", "1/2-1/2")]
+    fn errors_when_result_contradicts_final_position(#[case] pgn: &str, #[case] result: &str) {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = format!("{} 1. e4 *", pgn);
+
+        let games = import_games(Box::new(game_repo), &pgn);
+
+        assert_eq!(
+            games[0],
+            Err(ImportGameError::ContradictoryResult(result.to_string()))
+        );
+        // Nothing was persisted for the contradictory game.
+        assert_eq!(games[1].as_ref().unwrap().get_id(), &1);
+    }
+
+    #[test]
+    fn replays_moves_under_rules_of_variant() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "[Variant \"Three-check\"]
+[SetUp \"1\"]
+[FEN \"4k3/8/8/8/8/8/8/4K2R w - - 0 1 +2+0\"]
+
+1. Rh8+ 1-0";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        let game = games[0].as_ref().unwrap();
+        assert_eq!(game.get_status(), &game::GameStatus::WonByWhite);
+        assert_eq!(
+            game.get_termination(),
+            &Some(game::GameTermination::ThreeChecks)
+        );
+    }
+
+    #[test]
+    fn errors_when_moves_continue_after_game_has_ended() {
+        let game_repo = repository::FakeGameRepository::new();
+        let pgn = "1. f3 e5 2. g4 Qh4# 3. a3 0-1";
+
+        let games = import_games(Box::new(game_repo), pgn);
+
+        assert_eq!(
+            games,
+            vec![Err(ImportGameError::IllegalMove {
+                ply: 5,
+                san: "a3".to_string()
+            })]
+        );
+    }
+}
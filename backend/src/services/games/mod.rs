@@ -1,7 +1,23 @@
+mod claim_draw;
+mod draw_offer;
 mod generate_and_play_next_move;
+mod get_game;
+mod import_games;
 mod play_move;
+mod resign;
 mod start_game;
+mod takeback;
 
+pub use claim_draw::{claim_draw, ClaimDrawError};
+pub use draw_offer::{
+    accept_draw, decline_draw, offer_draw, respond_to_draw_offer, DrawOfferError,
+};
 pub use generate_and_play_next_move::{generate_and_play_next_move, GenerateNextMoveError};
-pub use play_move::{play_move, PlayMoveError};
-pub use start_game::start_game;
+pub use get_game::get_game;
+pub use import_games::{import_games, ImportGameError};
+pub use play_move::{play_move, play_san_move, play_uci_move, PlayMoveError};
+pub use resign::{resign, ResignError};
+pub use start_game::{start_game, StartGameError};
+pub use takeback::{
+    accept_takeback, decline_takeback, request_takeback, respond_to_takeback, TakebackError,
+};
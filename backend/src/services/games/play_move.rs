@@ -1,7 +1,10 @@
 use crate::domain::chess_set;
 use crate::domain::game;
+use crate::domain::rulebook;
 use crate::repository;
 
+use super::get_game;
+
 use thiserror;
 
 #[derive(thiserror::Error, Debug, PartialEq)]
@@ -11,6 +14,9 @@ pub enum PlayMoveError {
 
     #[error("{0}")]
     InvalidMove(game::GameError),
+
+    #[error("{0}")]
+    InvalidUci(rulebook::UciError),
 }
 
 pub fn play_move(
@@ -19,20 +25,61 @@ pub fn play_move(
     player: &chess_set::Colour,
     from_square: &chess_set::Square,
     to_square: &chess_set::Square,
+    promote_to: Option<chess_set::PieceType>,
 ) -> Result<game::Game, PlayMoveError> {
-    let Some(mut game) = game_repo.get(&game_id) else {
+    let Some(mut game) = get_game::load_game(game_repo.as_mut(), game_id) else {
         return Err(PlayMoveError::GameDoesNotExist(game_id.clone()));
     };
 
-    match game.play_move(&player, &from_square, &to_square) {
-        Err(err) => return Err(PlayMoveError::InvalidMove(err)),
-        Ok(_) => {}
-    }
+    if let Err(err) = game.play_move_with_promotion(player, from_square, to_square, promote_to) {
+        return Err(PlayMoveError::InvalidMove(err));
+    };
+
+    game_repo.update(&game);
+    Ok(game)
+}
+
+/// Play a move written in standard algebraic notation, e.g. `Nf3`.
+pub fn play_san_move(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+    san: &str,
+) -> Result<game::Game, PlayMoveError> {
+    let Some(mut game) = get_game::load_game(game_repo.as_mut(), game_id) else {
+        return Err(PlayMoveError::GameDoesNotExist(*game_id));
+    };
+
+    if let Err(err) = game.play_san_move(player, san) {
+        return Err(PlayMoveError::InvalidMove(err));
+    };
 
     game_repo.update(&game);
     Ok(game)
 }
 
+/// Play a move written in UCI's long algebraic notation, e.g. `e7e8q`.
+pub fn play_uci_move(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+    uci: &str,
+) -> Result<game::Game, PlayMoveError> {
+    let uci_move = match rulebook::parse_uci(uci) {
+        Ok(uci_move) => uci_move,
+        Err(err) => return Err(PlayMoveError::InvalidUci(err)),
+    };
+
+    play_move(
+        game_repo,
+        game_id,
+        player,
+        &uci_move.from_square,
+        &uci_move.to_square,
+        uci_move.promote_to,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,6 +100,7 @@ mod tests {
             &Colour::White,
             &from_square,
             &to_square,
+            None,
         );
 
         let updated_game = result.unwrap();
@@ -74,6 +122,7 @@ mod tests {
             &Colour::White,
             &Square::new(Rank::Two, File::D),
             &Square::new(Rank::Four, File::D),
+            None,
         );
 
         assert_eq!(result, Err(PlayMoveError::GameDoesNotExist(invalid_id)))
@@ -90,9 +139,56 @@ mod tests {
             &Colour::Black,
             &Square::new(Rank::Two, File::D),
             &Square::new(Rank::Four, File::D),
+            None,
         );
 
         let game_error = game::GameError::PlayIsOutOfTurn(Colour::Black);
         assert_eq!(result, Err(PlayMoveError::InvalidMove(game_error)))
     }
+
+    #[test]
+    fn can_play_move_in_san() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = play_san_move(Box::new(game_repo), game.get_id(), &Colour::White, "Nc3");
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayBlack);
+        let knight_square = Square::new(Rank::Three, File::C);
+        assert_ne!(updated_game.get_piece_at_square(&knight_square), None);
+    }
+
+    #[test]
+    fn errors_when_move_in_san_is_illegal() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = play_san_move(Box::new(game_repo), game.get_id(), &Colour::White, "Ke2");
+
+        let game_error = game::GameError::InvalidSan("Ke2".to_string());
+        assert_eq!(result, Err(PlayMoveError::InvalidMove(game_error)))
+    }
+
+    #[test]
+    fn can_play_move_in_uci() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = play_uci_move(Box::new(game_repo), game.get_id(), &Colour::White, "b1c3");
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_moves_in_san(), vec!["Nc3"]);
+    }
+
+    #[test]
+    fn errors_when_uci_is_invalid() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = play_uci_move(Box::new(game_repo), game.get_id(), &Colour::White, "b1");
+
+        let uci_error = rulebook::UciError::InvalidUci("b1".to_string());
+        assert_eq!(result, Err(PlayMoveError::InvalidUci(uci_error)))
+    }
 }
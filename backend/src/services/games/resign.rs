@@ -0,0 +1,88 @@
+use crate::domain::chess_set;
+use crate::domain::game;
+use crate::repository;
+
+use super::get_game;
+
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ResignError {
+    #[error("Game {0} does not exist")]
+    GameDoesNotExist(i32),
+
+    #[error("{0}")]
+    InvalidResignation(game::GameError),
+}
+
+pub fn resign(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, ResignError> {
+    let Some(mut game) = get_game::load_game(game_repo.as_mut(), game_id) else {
+        return Err(ResignError::GameDoesNotExist(*game_id));
+    };
+
+    if let Err(err) = game.resign(player) {
+        return Err(ResignError::InvalidResignation(err));
+    }
+
+    game_repo.update(&game);
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::Colour;
+    use crate::repository::GameRepository;
+
+    #[test]
+    fn resigns_game_for_player() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = game_repo.create();
+
+        let result = resign(Box::new(game_repo), game.get_id(), &Colour::White);
+
+        let resigned_game = result.unwrap();
+        assert_eq!(resigned_game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(
+            resigned_game.get_termination(),
+            &Some(game::GameTermination::Resignation)
+        );
+    }
+
+    #[test]
+    fn errors_when_game_does_not_exist() {
+        let game_repo = repository::FakeGameRepository::new();
+        let invalid_id = 37;
+
+        let result = resign(Box::new(game_repo), &invalid_id, &Colour::White);
+
+        assert_eq!(result, Err(ResignError::GameDoesNotExist(invalid_id)))
+    }
+
+    #[test]
+    fn errors_when_game_has_already_ended() {
+        let drawn_game = game::Game::reincarnate(
+            1,
+            game::GameStatus::Drawn,
+            None,
+            None,
+            None,
+            None,
+            game::GameVariant::Standard,
+            game::GameStart::default(),
+            vec![],
+            vec![],
+        );
+        let mut game_repo = repository::FakeGameRepository::new();
+        game_repo.update(&drawn_game);
+
+        let result = resign(Box::new(game_repo), drawn_game.get_id(), &Colour::Black);
+
+        let game_error = game::GameError::GameHasAlreadyEnded;
+        assert_eq!(result, Err(ResignError::InvalidResignation(game_error)))
+    }
+}
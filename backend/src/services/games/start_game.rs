@@ -1,21 +1,277 @@
-use crate::domain::game;
+use crate::domain::{chess_set, clock, game, rulebook};
 use crate::repository;
 
-pub fn start_game(mut game_repo: Box<dyn repository::GameRepository>) -> game::Game {
-    game_repo.create()
+use rand::Rng;
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum StartGameError {
+    #[error("{0}")]
+    InvalidFen(rulebook::FenError),
+
+    #[error("There is no starting position {0} in the chosen variant.")]
+    InvalidStartingPosition(u16),
+
+    #[error("{0}")]
+    CannotStartClock(game::GameError),
+}
+
+/// Start a game, either from the official starting position or from a FEN.
+///
+/// Games of other variants without a FEN start from the given starting position of
+/// the variant, or from one picked at random. Games given a time control have their
+/// clock started straight away.
+pub fn start_game(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    fen: Option<&str>,
+    time_control: Option<clock::TimeControl>,
+    variant: game::GameVariant,
+    starting_position: Option<u16>,
+) -> Result<game::Game, StartGameError> {
+    // Set the game up before creating it, so games that can't be started are never persisted.
+    let mut game = match fen {
+        Some(fen) => {
+            let fen = match rulebook::Fen::parse(fen) {
+                Ok(fen) => fen,
+                Err(error) => return Err(StartGameError::InvalidFen(error)),
+            };
+            game::Game::from_position(0, &fen, variant)
+        }
+        None if variant == game::GameVariant::Standard => game::Game::new(0),
+        None => {
+            let rules = variant.get_rules();
+            let index = starting_position.unwrap_or_else(|| {
+                rand::thread_rng().gen_range(0..rules.get_starting_position_count())
+            });
+            let Some(chessboard) = rules.get_starting_chessboard(index) else {
+                return Err(StartGameError::InvalidStartingPosition(index));
+            };
+            let fen = rulebook::Fen::from_chessboard_history(
+                chess_set::Colour::White,
+                0,
+                1,
+                &[chessboard],
+            );
+            game::Game::from_position(0, &fen, variant)
+        }
+    };
+
+    if let Some(time_control) = time_control {
+        if let Err(error) = game.start_clock(time_control) {
+            return Err(StartGameError::CannotStartClock(error));
+        }
+    };
+
+    Ok(game_repo.create_from_game(&game))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::chess_set::Colour;
 
     #[test]
     fn can_start_game() {
         let game_repo = repository::FakeGameRepository::new();
 
-        let game = start_game(Box::new(game_repo));
+        let game = start_game(
+            Box::new(game_repo),
+            None,
+            None,
+            game::GameVariant::Standard,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(game.get_status(), &game::GameStatus::ToPlayWhite);
         assert_eq!(game.get_chessboard_history().len(), 1);
     }
+
+    #[test]
+    fn can_start_game_from_fen() {
+        let game_repo = repository::FakeGameRepository::new();
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 b - - 0 1";
+
+        let game = start_game(
+            Box::new(game_repo),
+            Some(fen),
+            None,
+            game::GameVariant::Standard,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(game.get_status(), &game::GameStatus::ToPlayBlack);
+        assert_eq!(game.get_chessboard_history().len(), 1);
+        assert_eq!(game.get_fen().to_string(), fen);
+    }
+
+    #[test]
+    fn game_started_from_fen_keeps_its_move_counters() {
+        let game_repo = repository::FakeGameRepository::new();
+        let fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 57 40";
+
+        let game = start_game(
+            Box::new(game_repo),
+            Some(fen),
+            None,
+            game::GameVariant::Standard,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(game.get_halfmove_clock(), 57);
+        assert_eq!(game.get_fen().to_string(), fen);
+    }
+
+    #[test]
+    fn can_start_game_with_time_control() {
+        let game_repo = repository::FakeGameRepository::new();
+        let time_control = clock::TimeControl::Fischer {
+            base_ms: 180_000,
+            increment_ms: 2_000,
+        };
+
+        let game = start_game(
+            Box::new(game_repo),
+            None,
+            Some(time_control),
+            game::GameVariant::Standard,
+            None,
+        )
+        .unwrap();
+
+        let game_clock = game.get_clock().as_ref().unwrap();
+        assert_eq!(game_clock.get_time_control(), &time_control);
+        assert_eq!(game_clock.get_remaining_ms(Colour::White), 180_000);
+    }
+
+    #[test]
+    fn errors_when_clock_cannot_be_started_for_game_that_has_ended() {
+        let game_repo = repository::FakeGameRepository::new();
+        let time_control = clock::TimeControl::Fischer {
+            base_ms: 180_000,
+            increment_ms: 2_000,
+        };
+
+        let result = start_game(
+            Box::new(game_repo),
+            Some("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1"),
+            Some(time_control),
+            game::GameVariant::Standard,
+            None,
+        );
+
+        let expected_error = StartGameError::CannotStartClock(game::GameError::GameHasAlreadyEnded);
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn can_start_chess960_game_from_position_index() {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let game = start_game(
+            Box::new(game_repo),
+            None,
+            None,
+            game::GameVariant::Chess960,
+            Some(0),
+        )
+        .unwrap();
+
+        assert_eq!(game.get_variant(), &game::GameVariant::Chess960);
+        assert_eq!(
+            game.get_fen().to_string(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn can_start_chess960_game_from_random_position() {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let game = start_game(
+            Box::new(game_repo),
+            None,
+            None,
+            game::GameVariant::Chess960,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(game.get_variant(), &game::GameVariant::Chess960);
+        assert_eq!(game.get_status(), &game::GameStatus::ToPlayWhite);
+    }
+
+    #[test]
+    fn can_start_king_of_the_hill_game_from_standard_position() {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let game = start_game(
+            Box::new(game_repo),
+            None,
+            None,
+            game::GameVariant::KingOfTheHill,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(game.get_variant(), &game::GameVariant::KingOfTheHill);
+        assert_eq!(
+            game.get_fen().to_string(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn king_of_the_hill_game_from_won_position_has_ended() {
+        let game_repo = repository::FakeGameRepository::new();
+        let fen = "4k3/8/8/8/4K3/8/8/8 b - - 0 1";
+
+        let game = start_game(
+            Box::new(game_repo),
+            Some(fen),
+            None,
+            game::GameVariant::KingOfTheHill,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(game.get_status(), &game::GameStatus::WonByWhite);
+        assert_eq!(
+            game.get_termination(),
+            &Some(game::GameTermination::KingOfTheHill)
+        );
+    }
+
+    #[test]
+    fn errors_when_chess960_position_is_out_of_range() {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let result = start_game(
+            Box::new(game_repo),
+            None,
+            None,
+            game::GameVariant::Chess960,
+            Some(960),
+        );
+
+        assert_eq!(result, Err(StartGameError::InvalidStartingPosition(960)));
+    }
+
+    #[test]
+    fn errors_when_fen_is_invalid() {
+        let game_repo = repository::FakeGameRepository::new();
+
+        let result = start_game(
+            Box::new(game_repo),
+            Some("not a fen"),
+            None,
+            game::GameVariant::Standard,
+            None,
+        );
+
+        let fen_error = rulebook::FenError::WrongNumberOfFields(3);
+        assert_eq!(result, Err(StartGameError::InvalidFen(fen_error)));
+    }
 }
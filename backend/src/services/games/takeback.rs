@@ -0,0 +1,182 @@
+use crate::domain::chess_set;
+use crate::domain::{engine, game};
+use crate::repository;
+
+use super::get_game;
+
+use thiserror;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum TakebackError {
+    #[error("Game {0} does not exist")]
+    GameDoesNotExist(i32),
+
+    #[error("{0}")]
+    InvalidTakeback(game::GameError),
+}
+
+/// Request to take back the player's last move, which stands until the opponent responds.
+pub fn request_takeback(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, TakebackError> {
+    update_takeback(game_repo, game_id, |game| game.request_takeback(player))
+}
+
+pub fn accept_takeback(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, TakebackError> {
+    update_takeback(game_repo, game_id, |game| game.accept_takeback(player))
+}
+
+pub fn decline_takeback(
+    game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    player: &chess_set::Colour,
+) -> Result<game::Game, TakebackError> {
+    update_takeback(game_repo, game_id, |game| game.decline_takeback(player))
+}
+
+/// Let an engine accept or decline the takeback its opponent has requested.
+pub fn respond_to_takeback(
+    game_repo: Box<dyn repository::GameRepository>,
+    chess_engine: Box<dyn engine::ChessEngine>,
+    game_id: &i32,
+) -> Result<game::Game, TakebackError> {
+    update_takeback(game_repo, game_id, |game| {
+        let Some(takeback_request) = *game.get_takeback_request() else {
+            return Err(game::GameError::NoTakebackRequest);
+        };
+        let responder = takeback_request.requested_by.swap();
+
+        match chess_engine.should_accept_takeback(game) {
+            true => game.accept_takeback(&responder),
+            false => game.decline_takeback(&responder),
+        }
+    })
+}
+
+fn update_takeback(
+    mut game_repo: Box<dyn repository::GameRepository>,
+    game_id: &i32,
+    update: impl FnOnce(&mut game::Game) -> Result<&game::GameStatus, game::GameError>,
+) -> Result<game::Game, TakebackError> {
+    let Some(mut game) = get_game::load_game(game_repo.as_mut(), game_id) else {
+        return Err(TakebackError::GameDoesNotExist(*game_id));
+    };
+
+    if let Err(err) = update(&mut game) {
+        return Err(TakebackError::InvalidTakeback(err));
+    }
+
+    game_repo.update(&game);
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::chess_set::{Colour, File, Rank, Square};
+    use crate::repository::GameRepository;
+
+    fn create_game_with_opening_move(game_repo: &mut repository::FakeGameRepository) -> game::Game {
+        let mut game = game_repo.create();
+        let from_square = Square::new(Rank::Two, File::E);
+        let to_square = Square::new(Rank::Four, File::E);
+        game.play_move(&Colour::White, &from_square, &to_square)
+            .unwrap();
+        game_repo.update(&game);
+        game
+    }
+
+    #[test]
+    fn takeback_is_pending_until_opponent_consents() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = create_game_with_opening_move(&mut game_repo);
+
+        let result = request_takeback(Box::new(game_repo), game.get_id(), &Colour::White);
+
+        let updated_game = result.unwrap();
+        let expected_request = game::TakebackRequest {
+            requested_by: Colour::White,
+            plies: 1,
+        };
+        assert_eq!(updated_game.get_takeback_request(), &Some(expected_request));
+        assert_eq!(updated_game.get_chessboard_history().len(), 2);
+    }
+
+    #[test]
+    fn engine_accepts_takeback() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = create_game_with_opening_move(&mut game_repo);
+        game.request_takeback(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = respond_to_takeback(
+            Box::new(game_repo),
+            Box::new(engine::Random::new()),
+            game.get_id(),
+        );
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_takeback_request(), &None);
+        assert_eq!(updated_game.get_chessboard_history().len(), 1);
+        assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayWhite);
+    }
+
+    #[test]
+    fn errors_when_engine_has_no_takeback_request_to_respond_to() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let game = create_game_with_opening_move(&mut game_repo);
+
+        let result = respond_to_takeback(
+            Box::new(game_repo),
+            Box::new(engine::Random::new()),
+            game.get_id(),
+        );
+
+        let expected_error = TakebackError::InvalidTakeback(game::GameError::NoTakebackRequest);
+        assert_eq!(result, Err(expected_error));
+    }
+
+    #[test]
+    fn accepted_takeback_undoes_move() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = create_game_with_opening_move(&mut game_repo);
+        game.request_takeback(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = accept_takeback(Box::new(game_repo), game.get_id(), &Colour::Black);
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_chessboard_history().len(), 1);
+        assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayWhite);
+    }
+
+    #[test]
+    fn declined_takeback_leaves_moves_in_place() {
+        let mut game_repo = repository::FakeGameRepository::new();
+        let mut game = create_game_with_opening_move(&mut game_repo);
+        game.request_takeback(&Colour::White).unwrap();
+        game_repo.update(&game);
+
+        let result = decline_takeback(Box::new(game_repo), game.get_id(), &Colour::Black);
+
+        let updated_game = result.unwrap();
+        assert_eq!(updated_game.get_takeback_request(), &None);
+        assert_eq!(updated_game.get_chessboard_history().len(), 2);
+    }
+
+    #[test]
+    fn errors_when_game_does_not_exist() {
+        let game_repo = repository::FakeGameRepository::new();
+        let invalid_id = 37;
+
+        let result = accept_takeback(Box::new(game_repo), &invalid_id, &Colour::Black);
+
+        assert_eq!(result, Err(TakebackError::GameDoesNotExist(invalid_id)))
+    }
+}
@@ -1,4 +1,5 @@
-use crate::domain::{chess_set, rulebook};
+use crate::domain::{chess_set, game, rulebook};
+use std::collections::BTreeMap;
 
 pub fn some_square() -> chess_set::Square {
     chess_set::Square::new(chess_set::Rank::One, chess_set::File::A)
@@ -17,6 +18,55 @@ pub fn some_other_piece() -> chess_set::Piece {
 }
 
 pub fn chessboard() -> chess_set::Chessboard {
-    let starting_position = rulebook::get_official_starting_position();
-    chess_set::Chessboard::new(starting_position)
+    rulebook::get_official_starting_chessboard()
+}
+
+/// A game starting from the position described by a FEN.
+pub fn game_from_fen(fen: &str) -> game::Game {
+    variant_game_from_fen(fen, game::GameVariant::Standard)
+}
+
+/// A game of a variant starting from the position described by a FEN.
+pub fn variant_game_from_fen(fen: &str, variant: game::GameVariant) -> game::Game {
+    let fen = rulebook::Fen::parse(fen).unwrap();
+    game::Game::from_position(1, &fen, variant)
+}
+
+/// A history of distinct chessboards, in which no pawn moves or captures are made.
+///
+/// Each chessboard has the kings on H1 and H8, and rooks shuffled around the board.
+pub fn chessboard_history_without_progress(length: usize) -> Vec<chess_set::Chessboard> {
+    let mut white_rook_squares = vec![];
+    for rank_index in 2..=5 {
+        for file_index in 1..=7 {
+            white_rook_squares.push(chess_set::Square::from_indexes(rank_index, file_index));
+        }
+    }
+
+    let white_king = chess_set::Piece::new(chess_set::Colour::White, chess_set::PieceType::King);
+    let black_king = chess_set::Piece::new(chess_set::Colour::Black, chess_set::PieceType::King);
+    let white_rook = chess_set::Piece::new(chess_set::Colour::White, chess_set::PieceType::Rook);
+    let black_rook = chess_set::Piece::new(chess_set::Colour::Black, chess_set::PieceType::Rook);
+
+    let mut chessboard_history = vec![];
+    for index in 0..length {
+        let mut position = BTreeMap::new();
+        position.insert(
+            chess_set::Square::new(chess_set::Rank::One, chess_set::File::H),
+            white_king,
+        );
+        position.insert(
+            chess_set::Square::new(chess_set::Rank::Eight, chess_set::File::H),
+            black_king,
+        );
+
+        let white_rook_square = white_rook_squares[index % white_rook_squares.len()];
+        position.insert(white_rook_square, white_rook);
+        let black_rook_file_index = (index / white_rook_squares.len()) as i8 + 1;
+        let black_rook_square = chess_set::Square::from_indexes(7, black_rook_file_index);
+        position.insert(black_rook_square, black_rook);
+
+        chessboard_history.push(chess_set::Chessboard::new(position));
+    }
+    chessboard_history
 }
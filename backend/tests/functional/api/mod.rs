@@ -1,5 +1,13 @@
+mod test_claim_draw;
+mod test_draw_offers;
 mod test_generate_and_play_next_move;
+mod test_get_attacks;
+mod test_get_fen;
 mod test_get_game_state;
 mod test_get_legal_moves;
+mod test_get_pgn;
+mod test_import_games;
 mod test_play_move;
+mod test_resign;
 mod test_start_game;
+mod test_takeback;
@@ -0,0 +1,70 @@
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+use chess::config;
+use chess::domain::chess_set::{Colour, File, Rank, Square};
+use chess::domain::game::GameStatus;
+
+#[test]
+fn can_claim_draw_by_threefold_repetition() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+
+    // Shuffle the knights out and back twice, so the starting position occurs three times.
+    let g1 = Square::new(Rank::One, File::G);
+    let f3 = Square::new(Rank::Three, File::F);
+    let g8 = Square::new(Rank::Eight, File::G);
+    let f6 = Square::new(Rank::Six, File::F);
+    let moves = [
+        (Colour::White, g1, f3),
+        (Colour::Black, g8, f6),
+        (Colour::White, f3, g1),
+        (Colour::Black, f6, g8),
+    ];
+    for (colour, from_square, to_square) in moves.iter().chain(moves.iter()) {
+        let _ = game.play_move(colour, from_square, to_square).unwrap();
+        repo.update(&game);
+    }
+
+    let url = format!("/api/games/{}/claim-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "White"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_status(), &GameStatus::Drawn);
+}
+
+#[test]
+fn bad_response_when_there_is_no_draw_to_claim() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/claim-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "White"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"There is no draw that can be claimed.\\\"}\""
+    );
+
+    let unchanged_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(unchanged_game.get_status(), game.get_status());
+}
@@ -0,0 +1,130 @@
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+use chess::config;
+use chess::domain::chess_set::Colour;
+use chess::domain::game::{DrawOffer, GameStatus, GameTermination};
+
+#[test]
+fn can_offer_then_accept_draw() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/offer-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "White"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    let offered_game = repo.get(game.get_id()).unwrap();
+    let expected_offer = DrawOffer {
+        offered_by: Colour::White,
+        ply: 0,
+    };
+    assert_eq!(offered_game.get_draw_offer(), &Some(expected_offer));
+
+    let url = format!("/api/games/{}/accept-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "Black"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let drawn_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(drawn_game.get_status(), &GameStatus::Drawn);
+    assert_eq!(
+        drawn_game.get_termination(),
+        &Some(GameTermination::Agreement)
+    );
+    assert_eq!(drawn_game.get_draw_offer(), &None);
+}
+
+#[test]
+fn can_decline_draw() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+    game.offer_draw(&Colour::White).unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/decline-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "Black"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_status(), &GameStatus::ToPlayWhite);
+    assert_eq!(updated_game.get_draw_offer(), &None);
+}
+
+#[test]
+fn bad_response_when_accepting_own_draw_offer() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+    game.offer_draw(&Colour::White).unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/accept-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "White"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"W player cannot respond to their own draw offer.\\\"}\""
+    );
+
+    let unchanged_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(unchanged_game.get_status(), &GameStatus::ToPlayWhite);
+}
+
+#[test]
+fn engine_can_respond_to_draw_offer() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+    game.offer_draw(&Colour::White).unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/generate-draw-offer-response/", game.get_id());
+    let payload = serde_json::json!({"engine": "Random"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_draw_offer(), &None);
+}
+
+#[test]
+fn bad_response_when_player_is_invalid() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/offer-draw/", game.get_id());
+    let payload = serde_json::json!({"player": "Green"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"'Green' is not a valid player; it should be White or Black.\\\"}\""
+    );
+
+    let unchanged_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(unchanged_game.get_status(), &GameStatus::ToPlayWhite);
+}
@@ -0,0 +1,37 @@
+use chess::config;
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+#[test]
+fn can_get_attacks_of_game() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/attacks", game.get_id());
+    let request = client.get(url);
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let payload: String = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let attacks: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    assert_eq!(attacks["White"]["F3"], 3);
+    assert_eq!(attacks["White"]["F6"], serde_json::Value::Null);
+    assert_eq!(attacks["Black"]["F6"], 3);
+}
+
+#[test]
+fn not_found_response_when_game_does_not_exist() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let request = client.get("/api/games/0/attacks");
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::NotFound);
+}
@@ -16,7 +16,7 @@ fn can_get_legal_moves_for_active_game() {
     let response = request.dispatch();
     assert_eq!(
         response.into_string().unwrap(),
-        "\"[{\\\"from_square\\\":\\\"B1\\\",\\\"to_square\\\":\\\"A3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"B1\\\",\\\"to_square\\\":\\\"C3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"G1\\\",\\\"to_square\\\":\\\"F3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"G1\\\",\\\"to_square\\\":\\\"H3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"A2\\\",\\\"to_square\\\":\\\"A3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"A2\\\",\\\"to_square\\\":\\\"A4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"B2\\\",\\\"to_square\\\":\\\"B3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"B2\\\",\\\"to_square\\\":\\\"B4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"C2\\\",\\\"to_square\\\":\\\"C3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"C2\\\",\\\"to_square\\\":\\\"C4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"D2\\\",\\\"to_square\\\":\\\"D3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"D2\\\",\\\"to_square\\\":\\\"D4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"E2\\\",\\\"to_square\\\":\\\"E3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"E2\\\",\\\"to_square\\\":\\\"E4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"F2\\\",\\\"to_square\\\":\\\"F3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"F2\\\",\\\"to_square\\\":\\\"F4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"G2\\\",\\\"to_square\\\":\\\"G3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"G2\\\",\\\"to_square\\\":\\\"G4\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"H2\\\",\\\"to_square\\\":\\\"H3\\\",\\\"player\\\":\\\"White\\\"},{\\\"from_square\\\":\\\"H2\\\",\\\"to_square\\\":\\\"H4\\\",\\\"player\\\":\\\"White\\\"}]\""
+        "\"[{\\\"from_square\\\":\\\"B1\\\",\\\"to_square\\\":\\\"A3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"Na3\\\",\\\"uci\\\":\\\"b1a3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"B1\\\",\\\"to_square\\\":\\\"C3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"Nc3\\\",\\\"uci\\\":\\\"b1c3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"G1\\\",\\\"to_square\\\":\\\"F3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"Nf3\\\",\\\"uci\\\":\\\"g1f3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"G1\\\",\\\"to_square\\\":\\\"H3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"Nh3\\\",\\\"uci\\\":\\\"g1h3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"A2\\\",\\\"to_square\\\":\\\"A3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"a3\\\",\\\"uci\\\":\\\"a2a3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"A2\\\",\\\"to_square\\\":\\\"A4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"a4\\\",\\\"uci\\\":\\\"a2a4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"B2\\\",\\\"to_square\\\":\\\"B3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"b3\\\",\\\"uci\\\":\\\"b2b3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"B2\\\",\\\"to_square\\\":\\\"B4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"b4\\\",\\\"uci\\\":\\\"b2b4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"C2\\\",\\\"to_square\\\":\\\"C3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"c3\\\",\\\"uci\\\":\\\"c2c3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"C2\\\",\\\"to_square\\\":\\\"C4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"c4\\\",\\\"uci\\\":\\\"c2c4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"D2\\\",\\\"to_square\\\":\\\"D3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"d3\\\",\\\"uci\\\":\\\"d2d3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"D2\\\",\\\"to_square\\\":\\\"D4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"d4\\\",\\\"uci\\\":\\\"d2d4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"E2\\\",\\\"to_square\\\":\\\"E3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"e3\\\",\\\"uci\\\":\\\"e2e3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"E2\\\",\\\"to_square\\\":\\\"E4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"e4\\\",\\\"uci\\\":\\\"e2e4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"F2\\\",\\\"to_square\\\":\\\"F3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"f3\\\",\\\"uci\\\":\\\"f2f3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"F2\\\",\\\"to_square\\\":\\\"F4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"f4\\\",\\\"uci\\\":\\\"f2f4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"G2\\\",\\\"to_square\\\":\\\"G3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"g3\\\",\\\"uci\\\":\\\"g2g3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"G2\\\",\\\"to_square\\\":\\\"G4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"g4\\\",\\\"uci\\\":\\\"g2g4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"H2\\\",\\\"to_square\\\":\\\"H3\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"h3\\\",\\\"uci\\\":\\\"h2h3\\\",\\\"kind\\\":\\\"Quiet\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false},{\\\"from_square\\\":\\\"H2\\\",\\\"to_square\\\":\\\"H4\\\",\\\"player\\\":\\\"White\\\",\\\"promote_to\\\":null,\\\"san\\\":\\\"h4\\\",\\\"uci\\\":\\\"h2h4\\\",\\\"kind\\\":\\\"DoublePush\\\",\\\"captured_piece\\\":null,\\\"gives_check\\\":false,\\\"gives_checkmate\\\":false}]\""
     );
 }
 
@@ -43,6 +43,13 @@ fn bad_request_when_game_is_complete() {
     let updated_game = game::Game::reincarnate(
         new_game.get_id().clone(),
         game::GameStatus::Drawn, // Artificially end the game.
+        None,
+        None,
+        None,
+        None,
+        game::GameVariant::Standard,
+        game::GameStart::default(),
+        vec![],
         new_game.get_chessboard_history().clone(),
     );
     repo.update(&updated_game);
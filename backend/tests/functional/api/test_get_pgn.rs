@@ -0,0 +1,46 @@
+use chess::config;
+use chess::domain::chess_set::{Colour, File, Rank, Square};
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+#[test]
+fn can_get_pgn_of_game() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+
+    let from_square = Square::new(Rank::Two, File::E);
+    let to_square = Square::new(Rank::Four, File::E);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/pgn", game.get_id());
+    let request = client.get(url);
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let payload: String = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let payload: serde_json::Value = serde_json::from_str(&payload).unwrap();
+    let pgn = payload["pgn"].as_str().unwrap();
+    assert!(pgn.starts_with("[Event \"?\"]\n"));
+    assert!(pgn.contains("[Result \"*\"]\n"));
+    assert!(pgn.ends_with("\n1. e4 *\n"));
+}
+
+#[test]
+fn not_found_response_when_game_does_not_exist() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let request = client.get("/api/games/0/pgn");
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::NotFound);
+}
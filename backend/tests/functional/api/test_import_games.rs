@@ -0,0 +1,65 @@
+use chess::config;
+use chess::domain::game::GameStatus;
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+#[test]
+fn can_import_games_from_pgn() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let pgn = "[Event \"Fool's mate\"]
+
+1. f3 {A weak move} e5 2. g4?? (2. e4) Qh4# 0-1
+
+[Event \"Illegal\"]
+
+1. e4 e5 2. Ke3 *
+";
+    let payload = serde_json::json!({ "pgn": pgn });
+
+    let request = client.post("/api/games/import").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Created);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let response_json: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let payload: serde_json::Value = serde_json::from_str(response_json.as_str().unwrap()).unwrap();
+    let games = payload["games"].as_array().unwrap();
+    assert_eq!(games.len(), 2);
+
+    let game_id = games[0]["game"]["id"].as_i64().unwrap() as i32;
+    let mut repo = config::get_game_repo();
+    let game = repo.get(&game_id).unwrap();
+    assert_eq!(game.get_status(), &GameStatus::WonByBlack);
+    assert_eq!(game.get_chessboard_history().len(), 5);
+
+    assert_eq!(
+        games[1],
+        serde_json::json!({
+            "error": "Ply 3 ('Ke3') is not a legal move.",
+            "ply": 3,
+            "san": "Ke3",
+        })
+    );
+}
+
+#[test]
+fn bad_response_when_no_games_can_be_imported() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let payload = serde_json::json!({ "pgn": "1. e4 {unfinished comment" });
+
+    let request = client.post("/api/games/import").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"games\\\":[{\\\"error\\\":\\\"A comment was not closed.\\\"}]}\""
+    );
+}
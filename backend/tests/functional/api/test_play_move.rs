@@ -53,3 +53,106 @@ fn bad_response_when_opening_move_is_invalid() {
         "\"{\\\"error\\\":\\\"MoveIsNotLegalForPiece\\\"}\""
     );
 }
+
+#[test]
+fn can_play_a_move_in_san() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/play-move/", game.get_id());
+    let payload = serde_json::json!({"player": "White", "san": "Nf3"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert!(response
+        .into_string()
+        .unwrap()
+        .contains("\\\"moves\\\":[\\\"Nf3\\\"]"));
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_moves_in_san(), vec!["Nf3"]);
+}
+
+#[test]
+fn bad_response_when_move_in_san_is_illegal() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/play-move/", game.get_id());
+    let payload = serde_json::json!({"player": "White", "san": "Nf4"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"'Nf4' is not a legal move.\\\"}\""
+    );
+}
+
+#[test]
+fn bad_response_when_move_is_not_given() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/play-move/", game.get_id());
+    let payload = serde_json::json!({"player": "White", "from_square": "E2"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+}
+
+#[test]
+fn can_play_a_move_in_uci() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/play-move/", game.get_id());
+    let payload = serde_json::json!({"player": "White", "uci": "d2d4"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_moves_in_san(), vec!["d4"]);
+}
+
+#[test]
+fn bad_response_when_move_in_uci_is_invalid() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/play-move/", game.get_id());
+    let payload = serde_json::json!({"player": "White", "uci": "d2d9"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"'d2d9' is not a valid UCI move.\\\"}\""
+    );
+}
@@ -0,0 +1,80 @@
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+use chess::config;
+use chess::domain::chess_set::Colour;
+use chess::domain::game::{GameStatus, GameTermination};
+
+#[test]
+fn can_resign_game() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/resign/", game.get_id());
+    let payload = serde_json::json!({"player": "Black"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_status(), &GameStatus::WonByWhite);
+    assert_eq!(
+        updated_game.get_termination(),
+        &Some(GameTermination::Resignation)
+    );
+}
+
+#[test]
+fn bad_response_when_game_has_already_ended() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+    game.resign(&Colour::White).unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/resign/", game.get_id());
+    let payload = serde_json::json!({"player": "Black"});
+
+    let request = client.post(url).body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"The game has already ended.\\\"}\""
+    );
+
+    let unchanged_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(unchanged_game.get_status(), &GameStatus::WonByBlack);
+}
+
+#[test]
+fn bad_response_when_player_is_invalid() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/resign/", game.get_id());
+    let payload = serde_json::json!({"player": "Green"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"'Green' is not a valid player; it should be White or Black.\\\"}\""
+    );
+
+    let unchanged_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(unchanged_game.get_status(), &GameStatus::ToPlayWhite);
+}
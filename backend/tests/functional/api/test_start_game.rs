@@ -1,3 +1,5 @@
+use chess::config;
+use chess::domain::game::{GameStatus, GameVariant};
 use chess::interfaces::api;
 use rocket::http;
 use rocket::local;
@@ -15,3 +17,141 @@ fn can_start_a_new_game() {
 
     // TODO -> check the JSON.
 }
+
+#[test]
+fn can_start_a_game_from_fen() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let fen = "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1";
+    let payload = serde_json::json!({ "fen": fen });
+
+    let request = client.post("/api/games/start/").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Created);
+
+    let response_json: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let game_json: serde_json::Value =
+        serde_json::from_str(response_json.as_str().unwrap()).unwrap();
+    let game_id = game_json["id"].as_i64().unwrap() as i32;
+
+    let mut repo = config::get_game_repo();
+    let game = repo.get(&game_id).unwrap();
+    assert_eq!(game.get_status(), &GameStatus::ToPlayBlack);
+    assert_eq!(game.get_fen().to_string(), fen);
+}
+
+#[test]
+fn can_start_a_timed_game() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let payload = serde_json::json!({
+        "time_control": {"Fischer": {"base_ms": 300000, "increment_ms": 2000}}
+    });
+
+    let request = client.post("/api/games/start/").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Created);
+
+    let response_json: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let game_json: serde_json::Value =
+        serde_json::from_str(response_json.as_str().unwrap()).unwrap();
+    assert_eq!(game_json["clock"]["white_remaining_ms"], 300000);
+    assert_eq!(game_json["clock"]["black_remaining_ms"], 300000);
+
+    let game_id = game_json["id"].as_i64().unwrap() as i32;
+    let mut repo = config::get_game_repo();
+    let game = repo.get(&game_id).unwrap();
+    assert!(game.get_clock().is_some());
+}
+
+#[test]
+fn can_start_a_chess960_game() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let payload = serde_json::json!({ "variant": "Chess960", "chess960_position": 959 });
+
+    let request = client.post("/api/games/start/").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Created);
+
+    let response_json: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let game_json: serde_json::Value =
+        serde_json::from_str(response_json.as_str().unwrap()).unwrap();
+    assert_eq!(game_json["variant"], "Chess960");
+
+    let game_id = game_json["id"].as_i64().unwrap() as i32;
+    let mut repo = config::get_game_repo();
+    let game = repo.get(&game_id).unwrap();
+    assert_eq!(game.get_variant(), &GameVariant::Chess960);
+    assert_eq!(
+        game.get_fen().to_string(),
+        "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1"
+    );
+}
+
+#[test]
+fn can_start_a_three_check_game() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let payload = serde_json::json!({ "variant": "ThreeCheck" });
+
+    let request = client.post("/api/games/start/").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::Created);
+
+    let response_json: serde_json::Value =
+        serde_json::from_str(&response.into_string().unwrap()).unwrap();
+    let game_json: serde_json::Value =
+        serde_json::from_str(response_json.as_str().unwrap()).unwrap();
+    assert_eq!(game_json["variant"], "ThreeCheck");
+    assert_eq!(game_json["white_checks_given"], 0);
+    assert_eq!(game_json["black_checks_given"], 0);
+
+    let game_id = game_json["id"].as_i64().unwrap() as i32;
+    let mut repo = config::get_game_repo();
+    let game = repo.get(&game_id).unwrap();
+    assert_eq!(game.get_variant(), &GameVariant::ThreeCheck);
+}
+
+#[test]
+fn bad_response_when_chess960_position_is_out_of_range() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let payload = serde_json::json!({ "variant": "Chess960", "chess960_position": 960 });
+
+    let request = client.post("/api/games/start/").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+}
+
+#[test]
+fn bad_response_when_fen_is_invalid() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let payload = serde_json::json!({ "fen": "4k3/8/8/8/8/8/8/4K3 w" });
+
+    let request = client.post("/api/games/start/").body(payload.to_string());
+    let response = request.dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"A FEN should have 6 fields, or 7 with the checks given, but 2 were given.\\\"}\""
+    );
+}
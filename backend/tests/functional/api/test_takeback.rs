@@ -0,0 +1,115 @@
+use chess::interfaces::api;
+use rocket::http;
+use rocket::local;
+
+use chess::config;
+use chess::domain::chess_set::{Colour, File, Rank, Square};
+use chess::domain::game::GameStatus;
+
+#[test]
+fn can_request_then_accept_takeback() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+    let from_square = Square::new(Rank::Two, File::E);
+    let to_square = Square::new(Rank::Four, File::E);
+    game.play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/request-takeback/", game.get_id());
+    let payload = serde_json::json!({"player": "White"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    let requested_game = repo.get(game.get_id()).unwrap();
+    assert!(requested_game.get_takeback_request().is_some());
+
+    let url = format!("/api/games/{}/accept-takeback/", game.get_id());
+    let payload = serde_json::json!({"player": "Black"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+    assert_eq!(response.content_type(), Some(http::ContentType::JSON));
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_status(), &GameStatus::ToPlayWhite);
+    assert_eq!(updated_game.get_chessboard_history().len(), 1);
+}
+
+#[test]
+fn engine_accepts_takeback() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let mut game = repo.create();
+    let from_square = Square::new(Rank::Two, File::E);
+    let to_square = Square::new(Rank::Four, File::E);
+    game.play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+    repo.update(&game);
+
+    let url = format!("/api/games/{}/request-takeback/", game.get_id());
+    let payload = serde_json::json!({"player": "White", "against_engine": true});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    // The client can't skip the opponent's consent.
+    assert_eq!(response.status(), http::Status::Ok);
+    let requested_game = repo.get(game.get_id()).unwrap();
+    assert!(requested_game.get_takeback_request().is_some());
+    assert_eq!(requested_game.get_chessboard_history().len(), 2);
+
+    let url = format!("/api/games/{}/generate-takeback-response/", game.get_id());
+    let payload = serde_json::json!({"engine": "Random"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::Ok);
+
+    let updated_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(updated_game.get_status(), &GameStatus::ToPlayWhite);
+    assert_eq!(updated_game.get_chessboard_history().len(), 1);
+}
+
+#[test]
+fn bad_response_when_there_are_no_moves_to_take_back() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/request-takeback/", game.get_id());
+    let payload = serde_json::json!({"player": "White"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"W player has no moves to take back.\\\"}\""
+    );
+}
+
+#[test]
+fn bad_response_when_player_is_invalid() {
+    let build = api::rocket_build();
+    let client = local::blocking::Client::tracked(build).unwrap();
+
+    let mut repo = config::get_game_repo();
+    let game = repo.create();
+
+    let url = format!("/api/games/{}/request-takeback/", game.get_id());
+    let payload = serde_json::json!({"player": "Green"});
+    let response = client.post(url).body(payload.to_string()).dispatch();
+
+    assert_eq!(response.status(), http::Status::BadRequest);
+    assert_eq!(
+        response.into_string().unwrap(),
+        "\"{\\\"error\\\":\\\"'Green' is not a valid player; it should be White or Black.\\\"}\""
+    );
+
+    let unchanged_game = repo.get(game.get_id()).unwrap();
+    assert_eq!(unchanged_game.get_status(), &GameStatus::ToPlayWhite);
+}
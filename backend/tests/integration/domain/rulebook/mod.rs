@@ -2,3 +2,5 @@ mod test_castle;
 mod test_check;
 mod test_checkmate;
 mod test_en_passant;
+mod test_perft;
+mod test_promotion;
@@ -1,5 +1,5 @@
 use chess::domain::chess_set::{Colour, File, Rank, Square};
-use chess::domain::game::{Game, GameStatus};
+use chess::domain::game::{Game, GameStatus, GameTermination};
 
 #[test]
 fn fools_mate_by_black() {
@@ -30,6 +30,7 @@ fn fools_mate_by_black() {
         .unwrap();
 
     assert_eq!(game.get_status(), &GameStatus::WonByBlack);
+    assert_eq!(game.get_termination(), &Some(GameTermination::Checkmate));
 }
 
 #[test]
@@ -79,4 +80,5 @@ fn scholars_mate_by_white() {
         .unwrap();
 
     assert_eq!(game.get_status(), &GameStatus::WonByWhite);
+    assert_eq!(game.get_termination(), &Some(GameTermination::Checkmate));
 }
@@ -1,5 +1,6 @@
 use chess::domain::chess_set::{Colour, File, PieceType, Rank, Square};
-use chess::domain::game::{Game, GameStatus};
+use chess::domain::game::{Game, GameError, GameStatus};
+use chess::domain::rulebook::MoveValidationError;
 
 #[test]
 fn white_can_play_en_passant() {
@@ -88,3 +89,45 @@ fn black_can_play_en_passant() {
     assert_eq!(en_passant_pawn.get_piece_type(), &PieceType::Pawn);
     assert_eq!(en_passant_pawn.get_colour(), &Colour::Black);
 }
+
+#[test]
+fn cannot_play_en_passant_past_the_captured_pawn() {
+    let mut game = Game::new(1);
+
+    let from_square = Square::new(Rank::Two, File::B);
+    let to_square = Square::new(Rank::Four, File::B);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Seven, File::H);
+    let to_square = Square::new(Rank::Six, File::H);
+    let _ = game
+        .play_move(&Colour::Black, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Four, File::B);
+    let to_square = Square::new(Rank::Five, File::B);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Seven, File::D);
+    let to_square = Square::new(Rank::Five, File::D);
+    let _ = game
+        .play_move(&Colour::Black, &from_square, &to_square)
+        .unwrap();
+
+    // The pawn at d5 has just made a double advancement, but d7 is not adjacent to b5.
+    let from_square = Square::new(Rank::Five, File::B);
+    let to_square = Square::new(Rank::Seven, File::D);
+    let result = game.play_move(&Colour::White, &from_square, &to_square);
+
+    assert_eq!(
+        result,
+        Err(GameError::MoveValidationErrorV2(
+            MoveValidationError::MoveIsNotLegalForPiece
+        ))
+    );
+    assert!(game.get_piece_at_square(&from_square).is_some());
+}
@@ -0,0 +1,74 @@
+use chess::domain::rulebook::{perft, Fen};
+use rstest::rstest;
+
+// Node counts are from https://www.chessprogramming.org/Perft_Results.
+// Depths are kept shallow enough for the suite to run quickly in debug builds.
+
+fn perft_from_fen(fen: &str, depth: u8) -> u64 {
+    let fen = Fen::parse(fen).unwrap();
+    let chessboard_history = fen.to_chessboard_history();
+    perft(fen.to_play_colour, &chessboard_history, depth)
+}
+
+#[rstest]
+#[case::depth_1(1, 20)]
+#[case::depth_2(2, 400)]
+#[case::depth_3(3, 8902)]
+#[case::depth_4(4, 197281)]
+fn initial_position(#[case] depth: u8, #[case] expected_node_count: u64) {
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    assert_eq!(perft_from_fen(fen, depth), expected_node_count);
+}
+
+#[rstest]
+#[case::depth_1(1, 48)]
+#[case::depth_2(2, 2039)]
+#[case::depth_3(3, 97862)]
+fn kiwipete(#[case] depth: u8, #[case] expected_node_count: u64) {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    assert_eq!(perft_from_fen(fen, depth), expected_node_count);
+}
+
+#[rstest]
+#[case::depth_1(1, 14)]
+#[case::depth_2(2, 191)]
+#[case::depth_3(3, 2812)]
+#[case::depth_4(4, 43238)]
+fn position_3(#[case] depth: u8, #[case] expected_node_count: u64) {
+    let fen = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+
+    assert_eq!(perft_from_fen(fen, depth), expected_node_count);
+}
+
+#[rstest]
+#[case::depth_1(1, 6)]
+#[case::depth_2(2, 264)]
+#[case::depth_3(3, 9467)]
+#[case::depth_4(4, 422333)]
+fn position_4(#[case] depth: u8, #[case] expected_node_count: u64) {
+    let fen = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+
+    assert_eq!(perft_from_fen(fen, depth), expected_node_count);
+}
+
+#[rstest]
+#[case::depth_1(1, 44)]
+#[case::depth_2(2, 1486)]
+#[case::depth_3(3, 62379)]
+fn position_5(#[case] depth: u8, #[case] expected_node_count: u64) {
+    let fen = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+
+    assert_eq!(perft_from_fen(fen, depth), expected_node_count);
+}
+
+#[rstest]
+#[case::depth_1(1, 46)]
+#[case::depth_2(2, 2079)]
+#[case::depth_3(3, 89890)]
+fn position_6(#[case] depth: u8, #[case] expected_node_count: u64) {
+    let fen = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+    assert_eq!(perft_from_fen(fen, depth), expected_node_count);
+}
@@ -0,0 +1,71 @@
+use chess::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+use chess::domain::game::{Game, GameStatus};
+
+#[test]
+fn white_can_promote_pawn_by_capturing_on_the_back_rank() {
+    let mut game = Game::new(1);
+
+    let from_square = Square::new(Rank::Two, File::H);
+    let to_square = Square::new(Rank::Four, File::H);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Seven, File::G);
+    let to_square = Square::new(Rank::Five, File::G);
+    let _ = game
+        .play_move(&Colour::Black, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Four, File::H);
+    let to_square = Square::new(Rank::Five, File::G);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Seven, File::H);
+    let to_square = Square::new(Rank::Six, File::H);
+    let _ = game
+        .play_move(&Colour::Black, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Five, File::G);
+    let to_square = Square::new(Rank::Six, File::H);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Eight, File::G);
+    let to_square = Square::new(Rank::Six, File::F);
+    let _ = game
+        .play_move(&Colour::Black, &from_square, &to_square)
+        .unwrap();
+
+    let from_square = Square::new(Rank::Six, File::H);
+    let to_square = Square::new(Rank::Seven, File::H);
+    let _ = game
+        .play_move(&Colour::White, &from_square, &to_square)
+        .unwrap();
+
+    // Move the rook into the pawn's capture square.
+    let from_square = Square::new(Rank::Eight, File::H);
+    let to_square = Square::new(Rank::Eight, File::G);
+    let _ = game
+        .play_move(&Colour::Black, &from_square, &to_square)
+        .unwrap();
+
+    // Promote!!!
+    let from_square = Square::new(Rank::Seven, File::H);
+    let to_square = Square::new(Rank::Eight, File::G);
+    let result = game.play_move_with_promotion(
+        &Colour::White,
+        &from_square,
+        &to_square,
+        Some(PieceType::Knight),
+    );
+
+    assert_eq!(result, Ok(&GameStatus::ToPlayBlack));
+    assert_eq!(game.get_piece_at_square(&from_square), None);
+    let white_knight = Piece::new(Colour::White, PieceType::Knight);
+    assert_eq!(game.get_piece_at_square(&to_square), Some(white_knight));
+}
@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
-    use chess::domain::chess_set::{Colour, File, Rank, Square};
+    use chess::data::{connection, models};
+    use chess::domain::chess_set::{Colour, File, Piece, PieceType, Rank, Square};
+    use chess::domain::clock;
     use chess::domain::game;
+    use chess::domain::rulebook::{self, Fen};
     use chess::repository::{DieselGameRepository, GameRepository};
 
     #[test]
@@ -27,10 +30,418 @@ mod tests {
         let maybe_update_game = repo.get(created_game.get_id());
         let updated_game = maybe_update_game.unwrap();
         assert_eq!(updated_game.get_status(), &game::GameStatus::ToPlayBlack);
+        assert_eq!(updated_game.get_termination(), &None);
         assert_eq!(updated_game.get_chessboard_history().len(), 2);
         assert_eq!(updated_game, got_game);
     }
 
+    #[test]
+    fn promoted_piece_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+
+        let moves = [
+            (Colour::White, (Rank::Two, File::H), (Rank::Four, File::H)),
+            (Colour::Black, (Rank::Seven, File::G), (Rank::Five, File::G)),
+            (Colour::White, (Rank::Four, File::H), (Rank::Five, File::G)),
+            (Colour::Black, (Rank::Seven, File::H), (Rank::Six, File::H)),
+            (Colour::White, (Rank::Five, File::G), (Rank::Six, File::H)),
+            (Colour::Black, (Rank::Eight, File::G), (Rank::Six, File::F)),
+            (Colour::White, (Rank::Six, File::H), (Rank::Seven, File::H)),
+            (
+                Colour::Black,
+                (Rank::Eight, File::H),
+                (Rank::Eight, File::G),
+            ),
+        ];
+        for (player, (from_rank, from_file), (to_rank, to_file)) in moves {
+            let from_square = Square::new(from_rank, from_file);
+            let to_square = Square::new(to_rank, to_file);
+            game.play_move(&player, &from_square, &to_square).unwrap();
+            repo.update(&game);
+        }
+
+        let from_square = Square::new(Rank::Seven, File::H);
+        let to_square = Square::new(Rank::Eight, File::H);
+        game.play_move_with_promotion(
+            &Colour::White,
+            &from_square,
+            &to_square,
+            Some(PieceType::Queen),
+        )
+        .unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        let white_queen = Piece::new(Colour::White, PieceType::Queen);
+        assert_eq!(
+            reloaded_game.get_piece_at_square(&to_square),
+            Some(white_queen)
+        );
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn termination_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+
+        // Play a fool's mate.
+        let moves = [
+            (Colour::White, (Rank::Two, File::F), (Rank::Three, File::F)),
+            (Colour::Black, (Rank::Seven, File::E), (Rank::Six, File::E)),
+            (Colour::White, (Rank::Two, File::G), (Rank::Four, File::G)),
+            (Colour::Black, (Rank::Eight, File::D), (Rank::Four, File::H)),
+        ];
+        for (player, (from_rank, from_file), (to_rank, to_file)) in moves {
+            let from_square = Square::new(from_rank, from_file);
+            let to_square = Square::new(to_rank, to_file);
+            game.play_move(&player, &from_square, &to_square).unwrap();
+            repo.update(&game);
+        }
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_status(), &game::GameStatus::WonByBlack);
+        assert_eq!(
+            reloaded_game.get_termination(),
+            &Some(game::GameTermination::Checkmate)
+        );
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn can_create_game_from_position() {
+        let mut repo = DieselGameRepository::new();
+        let fen = Fen::parse("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1").unwrap();
+
+        let game = repo.create_from_position(&fen, game::GameVariant::Standard);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_status(), &game::GameStatus::ToPlayBlack);
+        assert_eq!(reloaded_game.get_chessboard_history().len(), 1);
+        assert_eq!(reloaded_game.get_fen(), fen);
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn move_counters_of_starting_position_are_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let fen = Fen::parse("4k3/8/8/8/8/8/8/R3K3 w - - 57 40").unwrap();
+        let mut game = repo.create_from_position(&fen, game::GameVariant::Standard);
+
+        let from_square = Square::new(Rank::One, File::A);
+        let to_square = Square::new(Rank::Two, File::A);
+        game.play_move(&Colour::White, &from_square, &to_square)
+            .unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(
+            reloaded_game.get_fen().to_string(),
+            "4k3/8/8/8/8/8/R7/4K3 b - - 58 40"
+        );
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn castling_rights_are_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let fen = Fen::parse("r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1").unwrap();
+
+        let game = repo.create_from_position(&fen, game::GameVariant::Standard);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_fen().to_string(), fen.to_string());
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn draw_offer_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+
+        let from_square = Square::new(Rank::Two, File::E);
+        let to_square = Square::new(Rank::Four, File::E);
+        game.play_move(&Colour::White, &from_square, &to_square)
+            .unwrap();
+        game.offer_draw(&Colour::White).unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        let expected_offer = game::DrawOffer {
+            offered_by: Colour::White,
+            ply: 1,
+        };
+        assert_eq!(reloaded_game.get_draw_offer(), &Some(expected_offer));
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn clock_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+        let time_control = clock::TimeControl::Bronstein {
+            base_ms: 300_000,
+            delay_ms: 5_000,
+        };
+        game.start_clock(time_control).unwrap();
+
+        let from_square = Square::new(Rank::Two, File::E);
+        let to_square = Square::new(Rank::Four, File::E);
+        game.play_move(&Colour::White, &from_square, &to_square)
+            .unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        let reloaded_clock = reloaded_game.get_clock().as_ref().unwrap();
+        assert_eq!(reloaded_clock.get_time_control(), &time_control);
+        assert_eq!(reloaded_clock.get_move_remaining_ms().len(), 1);
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn chess960_game_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let chessboard = rulebook::get_chess960_starting_chessboard(0).unwrap();
+        let fen = Fen::from_chessboard_history(Colour::White, 0, 1, &[chessboard]);
+        let game = repo.create_from_position(&fen, game::GameVariant::Chess960);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_variant(), &game::GameVariant::Chess960);
+        assert_eq!(
+            reloaded_game.get_fen().to_string(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+        );
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn three_check_termination_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let chessboard = rulebook::get_official_starting_chessboard();
+        let fen = Fen::from_chessboard_history(Colour::White, 0, 1, &[chessboard]);
+        let mut game = repo.create_from_position(&fen, game::GameVariant::ThreeCheck);
+
+        let moves = [
+            (Colour::White, "e4"),
+            (Colour::Black, "e5"),
+            (Colour::White, "Bc4"),
+            (Colour::Black, "a6"),
+            (Colour::White, "Bxf7+"),
+            (Colour::Black, "Kxf7"),
+            (Colour::White, "Qh5+"),
+            (Colour::Black, "g6"),
+            (Colour::White, "Qf3+"),
+        ];
+        for (player, san) in moves {
+            game.play_san_move(&player, san).unwrap();
+            repo.update(&game);
+        }
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_variant(), &game::GameVariant::ThreeCheck);
+        assert_eq!(reloaded_game.get_status(), &game::GameStatus::WonByWhite);
+        assert_eq!(
+            reloaded_game.get_termination(),
+            &Some(game::GameTermination::ThreeChecks)
+        );
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn checks_given_before_three_check_game_started_are_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let fen = Fen::parse("4k3/8/8/8/8/8/8/R3K3 w - - 0 1 +1+2").unwrap();
+        let mut game = repo.create_from_position(&fen, game::GameVariant::ThreeCheck);
+
+        game.play_san_move(&Colour::White, "Ra8+").unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_checks_given(&Colour::White), 2);
+        assert_eq!(reloaded_game.get_checks_given(&Colour::Black), 2);
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn taken_back_chessboards_are_truncated() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+
+        let moves = [
+            (Colour::White, (Rank::Two, File::E), (Rank::Four, File::E)),
+            (Colour::Black, (Rank::Seven, File::E), (Rank::Five, File::E)),
+        ];
+        for (player, (from_rank, from_file), (to_rank, to_file)) in moves {
+            let from_square = Square::new(from_rank, from_file);
+            let to_square = Square::new(to_rank, to_file);
+            game.play_move(&player, &from_square, &to_square).unwrap();
+            repo.update(&game);
+        }
+
+        game.request_takeback(&Colour::White).unwrap();
+        repo.update(&game);
+        game.accept_takeback(&Colour::Black).unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_chessboard_history().len(), 1);
+        assert_eq!(reloaded_game.get_status(), &game::GameStatus::ToPlayWhite);
+        assert_eq!(reloaded_game, game);
+
+        // Moves played after the takeback replace the ones that were taken back.
+        let from_square = Square::new(Rank::Two, File::D);
+        let to_square = Square::new(Rank::Four, File::D);
+        game.play_move(&Colour::White, &from_square, &to_square)
+            .unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_moves_in_san(), vec!["d4"]);
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn missing_chessboard_states_are_restored_when_game_is_loaded() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+        for san in ["Nf3", "d5", "Rg1", "d4", "e4"] {
+            let player = game.get_status().to_play_colour().unwrap();
+            game.play_san_move(&player, san).unwrap();
+            repo.update(&game);
+        }
+
+        // Games created before chessboard states were stored have none.
+        let mut connection = connection::establish_connection();
+        models::ChessboardState::delete_from_chessboard_history_index(
+            &mut connection,
+            game.get_id(),
+            0,
+        );
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        let latest_chessboard = reloaded_game.current_chessboard();
+        assert_eq!(
+            latest_chessboard.en_passant_square,
+            Some(Square::new(Rank::Three, File::E))
+        );
+        assert!(!latest_chessboard
+            .castling_rights
+            .contains(&Square::new(Rank::One, File::H)));
+        assert!(latest_chessboard
+            .castling_rights
+            .contains(&Square::new(Rank::One, File::A)));
+        assert_eq!(reloaded_game, game);
+
+        let chessboard_states =
+            models::ChessboardState::select_for_game(&mut connection, game.get_id());
+        assert_eq!(chessboard_states.len(), 6);
+    }
+
+    #[test]
+    fn played_moves_are_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+
+        let moves = [
+            (Colour::White, (Rank::Two, File::E), (Rank::Four, File::E)),
+            (Colour::Black, (Rank::Seven, File::D), (Rank::Five, File::D)),
+            (Colour::White, (Rank::Four, File::E), (Rank::Five, File::D)),
+        ];
+        for (player, (from_rank, from_file), (to_rank, to_file)) in moves {
+            let from_square = Square::new(from_rank, from_file);
+            let to_square = Square::new(to_rank, to_file);
+            game.play_move(&player, &from_square, &to_square).unwrap();
+            repo.update(&game);
+        }
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_moves_in_san(), vec!["e4", "d5", "exd5"]);
+        let capture = &reloaded_game.get_moves()[2].classification;
+        assert_eq!(capture.kind, rulebook::MoveKind::Capture);
+        assert_eq!(
+            capture.captured_piece,
+            Some(Piece::new(Colour::Black, PieceType::Pawn))
+        );
+        assert_eq!(reloaded_game, game);
+    }
+
+    #[test]
+    fn can_create_game_from_game() {
+        let mut repo = DieselGameRepository::new();
+        let fen = Fen::parse("4k3/8/8/8/8/8/4P3/4K3 b - - 0 1").unwrap();
+        let mut game = game::Game::from_position(0, &fen, game::GameVariant::Standard);
+        for san in ["Kd8", "e4", "Kc7"] {
+            let player = game.get_status().to_play_colour().unwrap();
+            game.play_san_move(&player, san).unwrap();
+        }
+        game.resign(&Colour::Black).unwrap();
+
+        let created_game = repo.create_from_game(&game);
+
+        assert_eq!(created_game, game.with_id(*created_game.get_id()));
+        let reloaded_game = repo.get(created_game.get_id()).unwrap();
+        assert_eq!(reloaded_game.get_moves_in_san(), vec!["Kd8", "e4", "Kc7"]);
+        assert_eq!(reloaded_game, created_game);
+    }
+
+    #[test]
+    fn missing_played_moves_are_restored_when_game_is_loaded() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+        for san in ["e4", "d5", "exd5", "Qxd5"] {
+            let player = game.get_status().to_play_colour().unwrap();
+            game.play_san_move(&player, san).unwrap();
+            repo.update(&game);
+        }
+
+        // Games created before moves were stored have none.
+        let mut connection = connection::establish_connection();
+        models::PlayedMove::delete_from_chessboard_history_index(&mut connection, game.get_id(), 0);
+
+        let mut reloaded_game = repo.get(game.get_id()).unwrap();
+        assert_eq!(
+            reloaded_game.get_moves_in_san(),
+            vec!["e4", "d5", "exd5", "Qxd5"]
+        );
+        assert_eq!(reloaded_game, game);
+
+        // Later moves are stored at the index of the chessboard they led to.
+        reloaded_game.play_san_move(&Colour::White, "Nc3").unwrap();
+        repo.update(&reloaded_game);
+
+        let db_moves = models::PlayedMove::select_for_game(&mut connection, game.get_id());
+        let indexes: Vec<i16> = db_moves
+            .iter()
+            .map(|db_move| db_move.chessboard_history_index)
+            .collect();
+        assert_eq!(indexes, vec![1, 2, 3, 4, 5]);
+        assert_eq!(repo.get(game.get_id()).unwrap(), reloaded_game);
+    }
+
+    #[test]
+    fn takeback_request_is_persisted() {
+        let mut repo = DieselGameRepository::new();
+        let mut game = repo.create();
+
+        let from_square = Square::new(Rank::Two, File::E);
+        let to_square = Square::new(Rank::Four, File::E);
+        game.play_move(&Colour::White, &from_square, &to_square)
+            .unwrap();
+        game.request_takeback(&Colour::White).unwrap();
+        repo.update(&game);
+
+        let reloaded_game = repo.get(game.get_id()).unwrap();
+        let expected_request = game::TakebackRequest {
+            requested_by: Colour::White,
+            plies: 1,
+        };
+        assert_eq!(
+            reloaded_game.get_takeback_request(),
+            &Some(expected_request)
+        );
+    }
+
     #[test]
     fn gets_none_when_game_does_not_exist() {
         let mut repo = DieselGameRepository::new();